@@ -0,0 +1,22 @@
+//! Times vector equality on long vectors that differ at the start, at the
+//! end or not at all. A comparison that stops at the first difference makes
+//! the first case nearly free.
+extern crate numrs;
+
+use std::time::Instant;
+use numrs::vector;
+
+fn main() {
+    let n = 10_000_000;
+    let a = vector::from_vec((0..n).map(|i| i as f64).collect());
+    for &(label, at) in [("first", Some(0)), ("last", Some(n - 1)), ("none", None)].iter() {
+        let mut b = a.clone();
+        if let Some(i) = at {
+            b[i] = -1.0;
+        }
+        let start = Instant::now();
+        let eq = a == b;
+        let elapsed = start.elapsed();
+        println!("eq {} elements, differing at {}: {:?} ({})", n, label, elapsed, eq);
+    }
+}
@@ -0,0 +1,19 @@
+//! Times square matrix products. Run with and without `--features parallel`
+//! to compare the serial and multithreaded paths.
+extern crate numrs;
+
+use std::time::Instant;
+use numrs::matrix;
+
+fn main() {
+    for &n in [256, 512, 1024].iter() {
+        let elems: Vec<f64> = (0..n * n).map(|i| (i % 17) as f64 - 8.0).collect();
+        let a = matrix::from_elems(n, n, &elems);
+        let b = matrix::from_elems(n, n, &elems);
+
+        let start = Instant::now();
+        let c = a.matmul(&b);
+        let elapsed = start.elapsed();
+        println!("matmul {}x{}: {:?} (trace {})", n, n, elapsed, c.trace());
+    }
+}
@@ -0,0 +1,42 @@
+/* C interface to numrs, available when the crate is built with the `ffi`
+ * feature. Generated from src/ffi.rs by cbindgen; do not edit by hand. */
+
+#ifndef NUMRS_H
+#define NUMRS_H
+
+#include <stddef.h>
+
+typedef enum NumrsStatus {
+  NUMRS_STATUS_OK = 0,
+  NUMRS_STATUS_NULL_POINTER = 1,
+  NUMRS_STATUS_LENGTH_MISMATCH = 2,
+  NUMRS_STATUS_INDEX_OUT_OF_BOUNDS = 3,
+} NumrsStatus;
+
+typedef struct NumrsVector NumrsVector;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+NumrsVector *numrs_vector_new(const double *data, size_t len);
+
+void numrs_vector_free(NumrsVector *v);
+
+size_t numrs_vector_len(const NumrsVector *v);
+
+const double *numrs_vector_data(const NumrsVector *v);
+
+NumrsStatus numrs_vector_get(const NumrsVector *v, size_t index, double *out);
+
+NumrsVector *numrs_vector_add(const NumrsVector *a, const NumrsVector *b, NumrsStatus *out_err);
+
+NumrsVector *numrs_vector_sub(const NumrsVector *a, const NumrsVector *b, NumrsStatus *out_err);
+
+NumrsStatus numrs_vector_dot(const NumrsVector *a, const NumrsVector *b, double *out);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* NUMRS_H */
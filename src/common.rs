@@ -1,9 +1,9 @@
 extern crate num;
 
-use self::num::traits::Num;
+use self::num::traits::{Num, Float};
 
 /// A generic number trait for primitive integers and floating point numbers
-pub trait Number: Num + Clone + Copy {}
+pub trait Number: Num + Clone + Copy + Send + Sync {}
 
 impl Number for f64 {}
 impl Number for f32 {}
@@ -16,3 +16,9 @@ impl Number for u32 {}
 impl Number for u16 {}
 impl Number for u8 {}
 impl Number for usize {}
+
+/// A generic floating point trait for `f32` and `f64`
+pub trait Real: Number + Float {}
+
+impl Real for f64 {}
+impl Real for f32 {}
@@ -0,0 +1,90 @@
+//! A 1D Vector type of complex numbers
+//!
+//! # Examples
+//! ```
+//! use numrs::complex;
+//! use numrs::complex::Complex;
+//!
+//! let elems = [Complex::new(1.0, 2.0), Complex::new(3.0, -1.0)];
+//! let v = complex::from_elems(&elems);
+//!
+//! // Hermitian inner product of the vector with itself
+//! let energy = v.hermitian_dot(&v).unwrap();
+//! assert_eq!(energy, Complex::new(15.0, 0.0));
+//! ```
+
+extern crate num;
+
+pub use self::num::complex::Complex;
+
+use std::ops::{Index, IndexMut};
+use common::Real;
+
+/// 1D Vector of complex numbers
+pub struct ComplexVector<T: Real> {
+    pub data: Vec<Complex<T>>,
+}
+
+impl<T: Real> Index<usize> for ComplexVector<T> {
+    type Output = Complex<T>;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Complex<T> {
+        &self.data[index]
+    }
+}
+
+impl<T: Real> IndexMut<usize> for ComplexVector<T> {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut Complex<T> {
+        &mut self.data[index]
+    }
+}
+
+impl<T: Real> Clone for ComplexVector<T> {
+    fn clone(&self) -> ComplexVector<T> {
+        ComplexVector::<T> { data: self.data.clone() }
+    }
+
+    fn clone_from(&mut self, source: &ComplexVector<T>) {
+        self.data = source.data.clone();
+    }
+}
+
+impl<T: Real> PartialEq for ComplexVector<T> {
+    fn eq(&self, other: &ComplexVector<T>) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<T: Real> ComplexVector<T> {
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn new(length: usize, default: Complex<T>) -> ComplexVector<T> {
+        ComplexVector::<T> { data: vec![default; length] }
+    }
+
+    /// Hermitian inner product `sum(self[i] * conj(other[i]))`.
+    pub fn hermitian_dot(&self, other: &ComplexVector<T>) -> Result<Complex<T>, String> {
+        if self.len() != other.len() {
+            return Err("Vectors are not conformable for dot product.".to_string());
+        }
+        let mut sum = Complex::new(T::zero(), T::zero());
+        for i in 0..self.len() {
+            sum = sum + self.data[i] * other.data[i].conj();
+        }
+        Ok(sum)
+    }
+}
+
+pub fn from_elems<T: Real>(elems: &[Complex<T>]) -> ComplexVector<T> {
+    ComplexVector::<T> { data: elems.to_vec() }
+}
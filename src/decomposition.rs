@@ -0,0 +1,275 @@
+//! Matrix decompositions
+//!
+//! # Examples
+//! ```
+//! use numrs::decomposition;
+//! use numrs::matrix;
+//!
+//! // 4 samples with 2 features lying on the line y = 2x
+//! let elems = [1.0, 2.0, 2.0, 4.0, 3.0, 6.0, 4.0, 8.0];
+//! let data = matrix::from_elems(4, 2, &elems);
+//!
+//! let pca = decomposition::pca(&data, 1).unwrap();
+//! assert_eq!(pca.components.num_rows(), 1);
+//! assert_eq!(pca.transformed.num_cols(), 1);
+//! ```
+
+use common::Real;
+use matrix;
+use matrix::Matrix;
+use vector::Vector;
+
+/// Result of a principal component analysis.
+pub struct PcaResult<T: Real> {
+    /// Principal axes in feature space, one per row, ordered by decreasing
+    /// explained variance.
+    pub components: Matrix<T>,
+    /// Variance along each of the principal axes.
+    pub explained_variance: Vector<T>,
+    /// Per-feature mean of the data the analysis was fitted on.
+    pub mean: Vector<T>,
+    /// The fitted data projected onto the principal axes.
+    pub transformed: Matrix<T>,
+}
+
+impl<T: Real> PcaResult<T> {
+    /// Projects the rows of `data` onto the principal axes.
+    pub fn transform(&self, data: &Matrix<T>) -> Result<Matrix<T>, String> {
+        if data.num_cols() != self.mean.len() {
+            return Err("Matrices are not conformable for projection.".to_string());
+        }
+        let mut axes = self.components.clone();
+        axes.transpose();
+        Ok(center(data, &self.mean) * axes)
+    }
+}
+
+/// QR factorization `A = QR` of an `m x n` matrix, with `Q` an `m x m`
+/// orthogonal matrix and `R` an `m x n` upper triangular (or trapezoidal)
+/// one. Rows can be appended to `A` without refactorizing, which makes it
+/// suited to least-squares problems that grow one observation at a time.
+#[derive(Clone, Debug)]
+pub struct QrFactorization<T: Real> {
+    q: Matrix<T>,
+    r: Matrix<T>,
+}
+
+impl<T: Real> QrFactorization<T> {
+    /// Factorization of a matrix with `cols` columns and no rows yet.
+    pub fn new(cols: usize) -> QrFactorization<T> {
+        QrFactorization { q: Matrix::new(0, 0, T::zero()), r: Matrix::new(0, cols, T::zero()) }
+    }
+
+    /// The orthogonal factor `Q`.
+    pub fn q(&self) -> &Matrix<T> {
+        &self.q
+    }
+
+    /// The upper triangular factor `R`.
+    pub fn r(&self) -> &Matrix<T> {
+        &self.r
+    }
+
+    /// Updates the factorization for `A` with `row` appended at the bottom,
+    /// using Givens rotations to zero the new row of `R` against the rows
+    /// above it. This costs `O(mn + m^2)` for an `m x n` matrix instead of
+    /// the `O(mn^2)` of refactorizing.
+    pub fn update_append_row(&mut self, row: &Vector<T>) -> Result<(), String> {
+        let (m, n) = (self.r.num_rows(), self.r.num_cols());
+        if row.len() != n {
+            return Err("Row and factorization are not conformable.".to_string());
+        }
+        let mut q = Matrix::new(m + 1, m + 1, T::zero());
+        let mut r = Matrix::new(m + 1, n, T::zero());
+        for i in 0..m {
+            for j in 0..m {
+                q.set(i, j, self.q.get(i, j));
+            }
+            for j in 0..n {
+                r.set(i, j, self.r.get(i, j));
+            }
+        }
+        q.set(m, m, T::one());
+        for j in 0..n {
+            r.set(m, j, row[j]);
+        }
+        for j in 0..m.min(n) {
+            let (a, b) = (r.get(j, j), r.get(m, j));
+            if b == T::zero() {
+                continue;
+            }
+            let h = a.hypot(b);
+            let (c, s) = (a / h, b / h);
+            for k in j..n {
+                let (x, y) = (r.get(j, k), r.get(m, k));
+                r.set(j, k, c * x + s * y);
+                r.set(m, k, c * y - s * x);
+            }
+            r.set(m, j, T::zero());
+            for i in 0..m + 1 {
+                let (x, y) = (q.get(i, j), q.get(i, m));
+                q.set(i, j, c * x + s * y);
+                q.set(i, m, c * y - s * x);
+            }
+        }
+        self.q = q;
+        self.r = r;
+        Ok(())
+    }
+
+    /// Least-squares solution `x` minimising `|Ax - b|`. Fails if `b` does
+    /// not have a row per row of `A`, or if `A` has fewer rows than columns
+    /// or is rank deficient, in which case the solution is not unique.
+    pub fn solve(&self, b: &Vector<T>) -> Result<Vector<T>, String> {
+        let (m, n) = (self.r.num_rows(), self.r.num_cols());
+        if b.len() != m {
+            return Err("Factorization and vector are not conformable for solving.".to_string());
+        }
+        if m < n {
+            return Err("The system is underdetermined.".to_string());
+        }
+        let scale = (0..n).fold(T::zero(), |s, j| s.max(self.r.get(j, j).abs()));
+        if (0..n).any(|j| self.r.get(j, j).abs() <= T::epsilon() * scale * T::from(n).unwrap()) {
+            return Err("The matrix is rank deficient.".to_string());
+        }
+        // x = R^-1 (Q^T b), using the first n rows of R.
+        let mut x = Vector::new(n, T::zero());
+        for j in (0..n).rev() {
+            let mut sum = (0..m).fold(T::zero(), |s, i| s + self.q.get(i, j) * b[i]);
+            for k in j + 1..n {
+                sum = sum - self.r.get(j, k) * x[k];
+            }
+            x[j] = sum / self.r.get(j, j);
+        }
+        Ok(x)
+    }
+}
+
+/// Principal component analysis of `data`, where every row is a sample and
+/// every column a feature. The covariance matrix of the centered columns is
+/// diagonalised and the `n_components` axes with the largest variance kept.
+/// Fails if the data contains NaN or infinite values, or its covariance
+/// overflows.
+pub fn pca<T: Real>(data: &Matrix<T>, n_components: usize) -> Result<PcaResult<T>, String> {
+    let (n, p) = (data.num_rows(), data.num_cols());
+    if n_components > p {
+        return Err("Number of components exceeds the number of features.".to_string());
+    }
+    if n < 2 {
+        return Err("At least two samples are needed for PCA.".to_string());
+    }
+    if (0..n).any(|i| data[i].iter().any(|x| !x.is_finite())) {
+        return Err("The data should be finite.".to_string());
+    }
+
+    let mut mean = Vector::new(p, T::zero());
+    for i in 0..n {
+        for j in 0..p {
+            mean[j] = mean[j] + data.get(i, j);
+        }
+    }
+    mean = mean * (T::one() / T::from(n).unwrap());
+
+    let centered = center(data, &mean);
+    let mut centered_t = centered.clone();
+    centered_t.transpose();
+    let cov = centered_t * centered.clone() * (T::one() / T::from(n - 1).unwrap());
+    if (0..p).any(|i| cov[i].iter().any(|x| !x.is_finite())) {
+        return Err("The covariance of the data overflows.".to_string());
+    }
+
+    let (values, vectors) = symmetric_eigen(&cov);
+    let mut order: Vec<usize> = (0..p).collect();
+    order.sort_by(|&a, &b| values[b].partial_cmp(&values[a]).unwrap());
+
+    let mut components = Matrix::new(n_components, p, T::zero());
+    let mut explained_variance = Vector::new(n_components, T::zero());
+    for (k, &idx) in order.iter().take(n_components).enumerate() {
+        // Fix the sign so that the largest entry of every axis is positive.
+        let mut largest = T::zero();
+        for j in 0..p {
+            if vectors.get(j, idx).abs() > largest.abs() {
+                largest = vectors.get(j, idx);
+            }
+        }
+        let sign = if largest < T::zero() { -T::one() } else { T::one() };
+        for j in 0..p {
+            components.set(k, j, sign * vectors.get(j, idx));
+        }
+        explained_variance[k] = values[idx].max(T::zero());
+    }
+
+    let mut axes = components.clone();
+    axes.transpose();
+    let transformed = centered * axes;
+    Ok(PcaResult {
+        components,
+        explained_variance,
+        mean,
+        transformed,
+    })
+}
+
+/// Subtracts `mean` from every row of `data`.
+fn center<T: Real>(data: &Matrix<T>, mean: &Vector<T>) -> Matrix<T> {
+    let mut centered = data.clone();
+    for i in 0..data.num_rows() {
+        for j in 0..data.num_cols() {
+            centered.set(i, j, data.get(i, j) - mean[j]);
+        }
+    }
+    centered
+}
+
+/// Eigen decomposition of a symmetric matrix using cyclic Jacobi rotations.
+/// Returns the eigenvalues and a matrix with the eigenvectors as its columns.
+fn symmetric_eigen<T: Real>(m: &Matrix<T>) -> (Vec<T>, Matrix<T>) {
+    let n = m.num_rows();
+    let mut a = m.clone();
+    let mut v = matrix::identity::<T>(n);
+    let two = T::one() + T::one();
+    for _ in 0..100 {
+        let (mut off, mut total) = (T::zero(), T::zero());
+        for p in 0..n {
+            for q in 0..n {
+                let sq = a.get(p, q) * a.get(p, q);
+                total = total + sq;
+                if p != q {
+                    off = off + sq;
+                }
+            }
+        }
+        if off <= T::epsilon() * T::epsilon() * total {
+            break;
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                let apq = a.get(p, q);
+                if apq == T::zero() {
+                    continue;
+                }
+                let theta = (a.get(q, q) - a.get(p, p)) / (two * apq);
+                let t = if theta >= T::zero() { T::one() } else { -T::one() }
+                    / (theta.abs() + (theta * theta + T::one()).sqrt());
+                let c = T::one() / (t * t + T::one()).sqrt();
+                let s = t * c;
+                for k in 0..n {
+                    let (akp, akq) = (a.get(k, p), a.get(k, q));
+                    a.set(k, p, c * akp - s * akq);
+                    a.set(k, q, s * akp + c * akq);
+                }
+                for k in 0..n {
+                    let (apk, aqk) = (a.get(p, k), a.get(q, k));
+                    a.set(p, k, c * apk - s * aqk);
+                    a.set(q, k, s * apk + c * aqk);
+                }
+                for k in 0..n {
+                    let (vkp, vkq) = (v.get(k, p), v.get(k, q));
+                    v.set(k, p, c * vkp - s * vkq);
+                    v.set(k, q, s * vkp + c * vkq);
+                }
+            }
+        }
+    }
+    ((0..n).map(|i| a.get(i, i)).collect(), v)
+}
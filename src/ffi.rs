@@ -0,0 +1,176 @@
+//! C-compatible interface to `Vector<f64>`
+//!
+//! Vectors are handed to C as opaque `NumrsVector*` handles which must be
+//! released with `numrs_vector_free`. Functions report failures through the
+//! `NumrsStatus` codes instead of Rust `Result`s and check every pointer
+//! argument for null. The matching declarations in `include/numrs.h` are
+//! generated by cbindgen; after changing this module, regenerate them with
+//! `cbindgen --config cbindgen.toml --crate numrs --output include/numrs.h`.
+//!
+//! The module is only built with the `ffi` feature. A shared or static
+//! library for linking from C can be produced with
+//! `cargo rustc --release --features ffi --crate-type cdylib` (or
+//! `staticlib`).
+
+use std::ptr;
+use std::slice;
+use vector::{Vector, VectorOps};
+use vector;
+
+/// Opaque handle to a `Vector<f64>`
+pub struct NumrsVector(Vector<f64>);
+
+/// Status codes returned across the C interface
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NumrsStatus {
+    Ok = 0,
+    NullPointer = 1,
+    LengthMismatch = 2,
+    IndexOutOfBounds = 3,
+}
+
+/// Borrows the vector behind a non-null handle.
+unsafe fn vector_ref<'a>(v: *const NumrsVector) -> &'a Vector<f64> {
+    &(*v).0
+}
+
+unsafe fn set_status(out: *mut NumrsStatus, status: NumrsStatus) {
+    if !out.is_null() {
+        *out = status;
+    }
+}
+
+/// Creates a vector by copying `len` elements from `data`. Returns null if
+/// `data` is null and `len` is not 0.
+///
+/// # Safety
+///
+/// `data` must point to `len` readable `double`s.
+#[no_mangle]
+pub unsafe extern "C" fn numrs_vector_new(data: *const f64, len: usize) -> *mut NumrsVector {
+    if len == 0 {
+        return Box::into_raw(Box::new(NumrsVector(Vector::new(0, 0.0))));
+    }
+    if data.is_null() {
+        return ptr::null_mut();
+    }
+    let v = vector::from_elems(slice::from_raw_parts(data, len));
+    Box::into_raw(Box::new(NumrsVector(v)))
+}
+
+/// Releases a vector. Passing null is a no-op.
+///
+/// # Safety
+///
+/// `v` must be null or a handle returned by this library which has not been
+/// freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn numrs_vector_free(v: *mut NumrsVector) {
+    if !v.is_null() {
+        drop(Box::from_raw(v));
+    }
+}
+
+/// Returns the number of elements, or 0 for a null handle.
+///
+/// # Safety
+///
+/// `v` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn numrs_vector_len(v: *const NumrsVector) -> usize {
+    if v.is_null() {
+        return 0;
+    }
+    vector_ref(v).len()
+}
+
+/// Returns a pointer to the elements, valid until the vector is freed, or
+/// null for a null handle.
+///
+/// # Safety
+///
+/// `v` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn numrs_vector_data(v: *const NumrsVector) -> *const f64 {
+    if v.is_null() {
+        return ptr::null();
+    }
+    vector_ref(v).as_ptr()
+}
+
+/// Reads the element at `index` into `out`.
+///
+/// # Safety
+///
+/// `v` must be null or a live handle and `out` null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn numrs_vector_get(v: *const NumrsVector, index: usize, out: *mut f64) -> NumrsStatus {
+    if v.is_null() || out.is_null() {
+        return NumrsStatus::NullPointer;
+    }
+    match vector_ref(v).data.get(index) {
+        Some(&x) => {
+            *out = x;
+            NumrsStatus::Ok
+        }
+        None => NumrsStatus::IndexOutOfBounds,
+    }
+}
+
+unsafe fn binary_op<F>(a: *const NumrsVector, b: *const NumrsVector, out_err: *mut NumrsStatus, f: F) -> *mut NumrsVector
+    where F: Fn(&Vector<f64>, &Vector<f64>) -> Vector<f64>
+{
+    if a.is_null() || b.is_null() {
+        set_status(out_err, NumrsStatus::NullPointer);
+        return ptr::null_mut();
+    }
+    let (a, b) = (vector_ref(a), vector_ref(b));
+    if a.len() != b.len() {
+        set_status(out_err, NumrsStatus::LengthMismatch);
+        return ptr::null_mut();
+    }
+    set_status(out_err, NumrsStatus::Ok);
+    Box::into_raw(Box::new(NumrsVector(f(a, b))))
+}
+
+/// Returns a new vector with the element-wise sum of `a` and `b`, or null on
+/// error with the reason stored in `out_err` (which may be null).
+///
+/// # Safety
+///
+/// `a` and `b` must be null or live handles and `out_err` null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn numrs_vector_add(a: *const NumrsVector, b: *const NumrsVector, out_err: *mut NumrsStatus) -> *mut NumrsVector {
+    binary_op(a, b, out_err, |a, b| a.clone() + b.clone())
+}
+
+/// Returns a new vector with the element-wise difference of `a` and `b`, or
+/// null on error with the reason stored in `out_err` (which may be null).
+///
+/// # Safety
+///
+/// `a` and `b` must be null or live handles and `out_err` null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn numrs_vector_sub(a: *const NumrsVector, b: *const NumrsVector, out_err: *mut NumrsStatus) -> *mut NumrsVector {
+    binary_op(a, b, out_err, |a, b| a.clone() - b.clone())
+}
+
+/// Stores the dot product of `a` and `b` in `out`.
+///
+/// # Safety
+///
+/// `a` and `b` must be null or live handles and `out` null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn numrs_vector_dot(a: *const NumrsVector, b: *const NumrsVector, out: *mut f64) -> NumrsStatus {
+    if a.is_null() || b.is_null() || out.is_null() {
+        return NumrsStatus::NullPointer;
+    }
+    match vector_ref(a).dot(vector_ref(b)) {
+        Ok(x) => {
+            *out = x;
+            NumrsStatus::Ok
+        }
+        Err(_) => NumrsStatus::LengthMismatch,
+    }
+}
@@ -0,0 +1,123 @@
+//! Text formatting options for vectors and matrices
+//!
+//! `Vector::to_string_with` and `Matrix::to_string_with` render their
+//! elements according to a `FormatOptions`, which controls the precision and
+//! notation of the numbers, the separator between them and how long inputs
+//! are elided.
+//!
+//! # Examples
+//! ```
+//! use numrs::vector;
+//! use numrs::format::{FormatOptions, Notation};
+//!
+//! let v = vector::from_elems(&[1.5, 25000.0, 0.001]);
+//! let opts = FormatOptions { precision: 1, notation: Notation::Scientific,
+//!                            ..FormatOptions::default() };
+//! assert_eq!(v.to_string_with(opts), "[1.5e0, 2.5e4, 1.0e-3]");
+//! ```
+
+extern crate num;
+
+use self::num::traits::ToPrimitive;
+
+/// How numbers are written.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Notation {
+    /// Plain decimal notation, e.g. `1234.50`.
+    Fixed,
+    /// A mantissa in `[1, 10)` and an exponent, e.g. `1.23e3`.
+    Scientific,
+    /// A mantissa in `[1, 1000)` and an exponent that is a multiple of 3,
+    /// e.g. `1.23e3` or `12.35e-6`.
+    Engineering,
+}
+
+/// Options for `Vector::to_string_with` and `Matrix::to_string_with`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FormatOptions {
+    /// Digits after the decimal point.
+    pub precision: usize,
+    /// Notation used for every number.
+    pub notation: Notation,
+    /// Text placed between consecutive elements.
+    pub separator: String,
+    /// Inputs longer than this are shown as their first and last elements
+    /// with `...` in between. For matrices this applies to the rows and to
+    /// the columns separately. `None` never elides.
+    pub max_elements: Option<usize>,
+    /// Prefixes every element of a vector, or every row of a matrix, with
+    /// its index.
+    pub show_indices: bool,
+}
+
+impl Default for FormatOptions {
+    /// Four fixed decimal places separated by `", "`, eliding beyond 1000
+    /// elements, without indices.
+    fn default() -> FormatOptions {
+        FormatOptions {
+            precision: 4,
+            notation: Notation::Fixed,
+            separator: ", ".to_string(),
+            max_elements: Some(1000),
+            show_indices: false,
+        }
+    }
+}
+
+impl FormatOptions {
+    /// Formats a single number with the precision and notation of the options.
+    pub fn format_value<T: ToPrimitive>(&self, value: T) -> String {
+        let x = value.to_f64().unwrap_or(f64::NAN);
+        let p = self.precision;
+        match self.notation {
+            Notation::Fixed => format!("{:.*}", p, x),
+            Notation::Scientific => format!("{:.*e}", p, x),
+            Notation::Engineering => {
+                if !x.is_finite() {
+                    return format!("{}", x);
+                }
+                if x == 0.0 {
+                    return format!("{:.*}e0", p, x);
+                }
+                let mut exp = (x.abs().log10() / 3.0).floor() as i32 * 3;
+                let mut mantissa = format!("{:.*}", p, x / 10f64.powi(exp));
+                // Rounding can carry the mantissa up to 1000.
+                if mantissa.parse::<f64>().unwrap().abs() >= 1000.0 {
+                    exp += 3;
+                    mantissa = format!("{:.*}", p, x / 10f64.powi(exp));
+                }
+                format!("{}e{}", mantissa, exp)
+            }
+        }
+    }
+
+    /// Renders `values` as `[a, b, ..., z]`, right-aligning every number to
+    /// `width` and, if `indices` is set, labelling it with its index.
+    pub(crate) fn render_row<T: ToPrimitive + Copy>(&self, values: &[T], width: usize,
+                                                    indices: bool) -> String {
+        let item = |i: usize| {
+            let s = format!("{:>w$}", self.format_value(values[i]), w = width);
+            if indices { format!("{}: {}", i, s) } else { s }
+        };
+        let n = values.len();
+        let parts: Vec<String> = match self.elided(n) {
+            Some((head, tail)) => {
+                (0..head).map(&item)
+                    .chain(Some(format!("{:>w$}", "...", w = width)))
+                    .chain((n - tail..n).map(&item))
+                    .collect()
+            }
+            None => (0..n).map(item).collect(),
+        };
+        format!("[{}]", parts.join(&self.separator))
+    }
+
+    /// Splits a run of `len` elements into the number shown before and after
+    /// the ellipsis, or `None` if nothing is elided.
+    pub(crate) fn elided(&self, len: usize) -> Option<(usize, usize)> {
+        match self.max_elements {
+            Some(max) if len > max => Some((max - max / 2, max / 2)),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! Half precision storage for `f32` data
+//!
+//! `F16Vector` stores IEEE 754 binary16 values and `Bf16Vector` bfloat16
+//! values as their `u16` bit patterns, halving the memory of a
+//! `Vector<f32>`. Elements are widened to `f32` when read, and the fused
+//! operations convert them on the fly so all arithmetic happens in `f32`.
+//!
+//! Narrowing from `f32` rounds to the nearest value, ties to even. For
+//! binary16, values beyond the largest half float, 65504, become infinity,
+//! and values below the smallest subnormal, about `6e-8`, become zero.
+//! bfloat16 keeps the exponent range of `f32` and only drops mantissa bits.
+//!
+//! # Examples
+//! ```
+//! use numrs::half::F16Vector;
+//! use numrs::vector;
+//!
+//! let weights = F16Vector::from_vector(&vector::from_elems(&[0.5, -2.0, 1.0e-3]));
+//! assert_eq!(weights.get(1), -2.0);
+//! let x = vector::from_elems(&[2.0, 1.0, 0.0]);
+//! assert_eq!(weights.dot_f32(&x).unwrap(), -1.0);
+//! ```
+
+use vector;
+use vector::Vector;
+
+/// Vector of half precision floats which computes in `f32`
+#[derive(Clone, Debug, PartialEq)]
+pub struct F16Vector {
+    /// The bit patterns of the elements.
+    pub bits: Vec<u16>,
+}
+
+impl F16Vector {
+    /// Wraps the bit patterns of half floats.
+    pub fn from_bits(bits: Vec<u16>) -> F16Vector {
+        F16Vector { bits }
+    }
+
+    /// Narrows every element of `v` to a half float.
+    pub fn from_vector(v: &Vector<f32>) -> F16Vector {
+        F16Vector { bits: v.data.iter().map(|&x| f32_to_f16(x)).collect() }
+    }
+
+    /// Widens every element to an `f32`, which is exact.
+    pub fn to_vector(&self) -> Vector<f32> {
+        vector::from_vec(self.bits.iter().map(|&h| f16_to_f32(h)).collect())
+    }
+
+    /// Number of elements.
+    pub fn len(&self) -> usize {
+        self.bits.len()
+    }
+
+    /// Whether there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    /// The element at `index` widened to an `f32`. Panics if out of bounds.
+    pub fn get(&self, index: usize) -> f32 {
+        f16_to_f32(self.bits[index])
+    }
+
+    /// Narrows `value` and stores it at `index`. Panics if out of bounds.
+    pub fn set(&mut self, index: usize, value: f32) {
+        self.bits[index] = f32_to_f16(value);
+    }
+
+    /// Dot product with an `f32` vector, widening each element as it is
+    /// used rather than converting the whole vector first.
+    pub fn dot_f32(&self, other: &Vector<f32>) -> Result<f32, String> {
+        if self.len() != other.len() {
+            return Err("Vectors are not conformable for dot product.".to_string());
+        }
+        let mut sum = 0.0;
+        for (&h, &y) in self.bits.iter().zip(other.data.iter()) {
+            sum += f16_to_f32(h) * y;
+        }
+        Ok(sum)
+    }
+}
+
+/// Vector of bfloat16 values which computes in `f32`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bf16Vector {
+    /// The bit patterns of the elements.
+    pub bits: Vec<u16>,
+}
+
+impl Bf16Vector {
+    /// Wraps the bit patterns of bfloat16 values.
+    pub fn from_bits(bits: Vec<u16>) -> Bf16Vector {
+        Bf16Vector { bits }
+    }
+
+    /// Narrows every element of `v` to bfloat16.
+    pub fn from_vector(v: &Vector<f32>) -> Bf16Vector {
+        Bf16Vector { bits: v.data.iter().map(|&x| f32_to_bf16(x)).collect() }
+    }
+
+    /// Widens every element to an `f32`, which is exact.
+    pub fn to_vector(&self) -> Vector<f32> {
+        vector::from_vec(self.bits.iter().map(|&b| bf16_to_f32(b)).collect())
+    }
+
+    /// Number of elements.
+    pub fn len(&self) -> usize {
+        self.bits.len()
+    }
+
+    /// Whether there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    /// The element at `index` widened to an `f32`. Panics if out of bounds.
+    pub fn get(&self, index: usize) -> f32 {
+        bf16_to_f32(self.bits[index])
+    }
+
+    /// Narrows `value` and stores it at `index`. Panics if out of bounds.
+    pub fn set(&mut self, index: usize, value: f32) {
+        self.bits[index] = f32_to_bf16(value);
+    }
+
+    /// Sum of the elements, accumulated in `f32`.
+    pub fn sum(&self) -> f32 {
+        self.bits.iter().map(|&b| bf16_to_f32(b)).sum()
+    }
+
+    /// Dot product with an `f32` vector, see `F16Vector::dot_f32`.
+    pub fn dot_f32(&self, other: &Vector<f32>) -> Result<f32, String> {
+        if self.len() != other.len() {
+            return Err("Vectors are not conformable for dot product.".to_string());
+        }
+        let mut sum = 0.0;
+        for (&b, &y) in self.bits.iter().zip(other.data.iter()) {
+            sum += bf16_to_f32(b) * y;
+        }
+        Ok(sum)
+    }
+
+    /// Adds `a` times the vector to `y` in place, `y += a * self`.
+    pub fn axpy(&self, a: f32, y: &mut Vector<f32>) -> Result<(), String> {
+        if self.len() != y.len() {
+            return Err("Vectors are not conformable for axpy.".to_string());
+        }
+        for (&b, y) in self.bits.iter().zip(y.data.iter_mut()) {
+            *y += a * bf16_to_f32(b);
+        }
+        Ok(())
+    }
+}
+
+/// Widens the bfloat16 value with bit pattern `b` to an `f32`.
+pub fn bf16_to_f32(b: u16) -> f32 {
+    f32::from_bits((b as u32) << 16)
+}
+
+/// Narrows `x` to the bit pattern of the nearest bfloat16 value, ties to
+/// even. NaNs stay NaN.
+pub fn f32_to_bf16(x: f32) -> u16 {
+    let bits = x.to_bits();
+    if x.is_nan() {
+        // Set the quiet bit so the payload cannot round away to infinity.
+        return ((bits >> 16) | 0x40) as u16;
+    }
+    // Adding just under half, plus the lowest kept bit, rounds ties to even.
+    // A carry correctly moves to the next exponent, or to infinity.
+    ((bits + 0x7fff + ((bits >> 16) & 1)) >> 16) as u16
+}
+
+/// Widens the half float with bit pattern `h` to an `f32`.
+pub fn f16_to_f32(h: u16) -> f32 {
+    let sign = ((h & 0x8000) as u32) << 16;
+    let exp = ((h >> 10) & 0x1f) as u32;
+    let mant = (h & 0x3ff) as u32;
+    match exp {
+        // Zero or subnormal, mant * 2^-24.
+        0 => {
+            let x = mant as f32 * f32::from_bits(0x3380_0000);
+            f32::from_bits(sign | x.to_bits())
+        }
+        0x1f => f32::from_bits(sign | 0x7f80_0000 | (mant << 13)),
+        _ => f32::from_bits(sign | ((exp + 112) << 23) | (mant << 13)),
+    }
+}
+
+/// Narrows `x` to the bit pattern of the nearest half float, ties to even.
+/// NaNs stay NaN.
+pub fn f32_to_f16(x: f32) -> u16 {
+    let bits = x.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exp = ((bits >> 23) & 0xff) as i32;
+    let mant = bits & 0x7f_ffff;
+    if exp == 0xff {
+        // Keep NaNs quiet and non-zero after dropping the low mantissa bits.
+        return sign | 0x7c00 | if mant == 0 { 0 } else { 0x200 | (mant >> 13) as u16 };
+    }
+    let e = exp - 127 + 15;
+    if e >= 0x1f {
+        return sign | 0x7c00;
+    }
+    if e <= 0 {
+        if e < -10 {
+            return sign;
+        }
+        // Subnormal: shift the mantissa, with its implicit bit, into place.
+        let m = mant | 0x80_0000;
+        let shift = (14 - e) as u32;
+        return sign | round_shift(m, shift) as u16;
+    }
+    // A carry out of the mantissa correctly moves to the next exponent, or
+    // to infinity.
+    sign | (((e as u32) << 10) + round_shift(mant, 13)) as u16
+}
+
+/// `m >> shift` rounded to nearest, ties to even.
+fn round_shift(m: u32, shift: u32) -> u32 {
+    let half = 1 << (shift - 1);
+    let rem = m & ((1 << shift) - 1);
+    let q = m >> shift;
+    if rem > half || (rem == half && q & 1 == 1) { q + 1 } else { q }
+}
@@ -0,0 +1,114 @@
+//! `AbsDiffEq`, `RelativeEq` and `UlpsEq` for `Vector` and `Matrix`
+//!
+//! Two vectors are approximately equal if they have the same length and
+//! every pair of elements is approximately equal with the given tolerances,
+//! which default to those of the element type. Matrices must also have the
+//! same dimensions. Differently sized operands compare as not equal.
+//!
+//! # Examples
+//! ```
+//! #[macro_use]
+//! extern crate approx;
+//! extern crate numrs;
+//!
+//! use numrs::vector;
+//!
+//! # fn main() {
+//! let a = vector::from_elems(&[0.1 + 0.2, 1.0]);
+//! assert_relative_eq!(a, vector::from_elems(&[0.3, 1.0]));
+//! assert_relative_ne!(a, vector::from_elems(&[0.3]));
+//! # }
+//! ```
+
+extern crate approx;
+
+use self::approx::{AbsDiffEq, RelativeEq, UlpsEq};
+use common::Number;
+use matrix::Matrix;
+use vector::Vector;
+
+/// Whether `a` and `b` have the same dimensions and `f` holds for every pair
+/// of elements.
+fn all_pairs<T: Number, F: Fn(&T, &T) -> bool>(a: &Matrix<T>, b: &Matrix<T>, f: F) -> bool {
+    a.num_rows() == b.num_rows() && a.num_cols() == b.num_cols() &&
+    (0..a.num_rows()).all(|i| a[i].iter().zip(b[i].iter()).all(|(x, y)| f(x, y)))
+}
+
+impl<T: Number + AbsDiffEq> AbsDiffEq for Vector<T>
+    where T::Epsilon: Copy
+{
+    type Epsilon = T::Epsilon;
+
+    fn default_epsilon() -> T::Epsilon {
+        T::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Vector<T>, epsilon: T::Epsilon) -> bool {
+        self.len() == other.len() &&
+        self.data.iter().zip(other.data.iter()).all(|(x, y)| x.abs_diff_eq(y, epsilon))
+    }
+}
+
+impl<T: Number + RelativeEq> RelativeEq for Vector<T>
+    where T::Epsilon: Copy
+{
+    fn default_max_relative() -> T::Epsilon {
+        T::default_max_relative()
+    }
+
+    fn relative_eq(&self, other: &Vector<T>, epsilon: T::Epsilon, max_relative: T::Epsilon) -> bool {
+        self.len() == other.len() &&
+        self.data.iter().zip(other.data.iter()).all(|(x, y)| x.relative_eq(y, epsilon, max_relative))
+    }
+}
+
+impl<T: Number + UlpsEq> UlpsEq for Vector<T>
+    where T::Epsilon: Copy
+{
+    fn default_max_ulps() -> u32 {
+        T::default_max_ulps()
+    }
+
+    fn ulps_eq(&self, other: &Vector<T>, epsilon: T::Epsilon, max_ulps: u32) -> bool {
+        self.len() == other.len() &&
+        self.data.iter().zip(other.data.iter()).all(|(x, y)| x.ulps_eq(y, epsilon, max_ulps))
+    }
+}
+
+impl<T: Number + AbsDiffEq> AbsDiffEq for Matrix<T>
+    where T::Epsilon: Copy
+{
+    type Epsilon = T::Epsilon;
+
+    fn default_epsilon() -> T::Epsilon {
+        T::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Matrix<T>, epsilon: T::Epsilon) -> bool {
+        all_pairs(self, other, |x, y| x.abs_diff_eq(y, epsilon))
+    }
+}
+
+impl<T: Number + RelativeEq> RelativeEq for Matrix<T>
+    where T::Epsilon: Copy
+{
+    fn default_max_relative() -> T::Epsilon {
+        T::default_max_relative()
+    }
+
+    fn relative_eq(&self, other: &Matrix<T>, epsilon: T::Epsilon, max_relative: T::Epsilon) -> bool {
+        all_pairs(self, other, |x, y| x.relative_eq(y, epsilon, max_relative))
+    }
+}
+
+impl<T: Number + UlpsEq> UlpsEq for Matrix<T>
+    where T::Epsilon: Copy
+{
+    fn default_max_ulps() -> u32 {
+        T::default_max_ulps()
+    }
+
+    fn ulps_eq(&self, other: &Matrix<T>, epsilon: T::Epsilon, max_ulps: u32) -> bool {
+        all_pairs(self, other, |x, y| x.ulps_eq(y, epsilon, max_ulps))
+    }
+}
@@ -0,0 +1,13 @@
+//! Conversions to and from the types of other numeric crates
+
+/// Approximate equality from the `approx` crate
+#[cfg(feature = "approx")]
+pub mod approx;
+
+/// `nalgebra` dynamically sized vectors and matrices
+#[cfg(feature = "nalgebra")]
+pub mod nalgebra;
+
+/// `ndarray` arrays
+#[cfg(feature = "ndarray")]
+pub mod ndarray;
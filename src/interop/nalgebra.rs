@@ -0,0 +1,62 @@
+//! Conversions between `Vector` and `DVector`, and between `Matrix` and
+//! `DMatrix`
+//!
+//! Vectors are converted in both directions without copying. nalgebra stores
+//! matrices in column major order, so converting a matrix copies its elements
+//! into the other layout; the element at row `i` and column `j` stays there.
+//!
+//! # Examples
+//! ```
+//! extern crate nalgebra;
+//! extern crate numrs;
+//!
+//! use nalgebra::{DMatrix, DVector};
+//! use numrs::matrix;
+//! use numrs::vector;
+//!
+//! # fn main() {
+//! // A result computed with numrs ...
+//! let a = matrix::from_elems(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+//! let product = a.matmul(&matrix::identity(3));
+//!
+//! // ... can be handed to nalgebra.
+//! let m: DMatrix<f64> = product.into();
+//! let x: DVector<f64> = vector::from_elems(&[1.0, 0.0, -1.0]).into();
+//! assert_eq!(m * x, DVector::from_vec(vec![-2.0, -2.0]));
+//! # }
+//! ```
+
+extern crate nalgebra;
+
+use self::nalgebra::{DMatrix, DVector, Scalar};
+use common::Number;
+use matrix;
+use matrix::Matrix;
+use vector;
+use vector::Vector;
+
+impl<T: Number + Scalar> From<DVector<T>> for Vector<T> {
+    fn from(v: DVector<T>) -> Vector<T> {
+        vector::from_vec(v.data.into())
+    }
+}
+
+impl<T: Number + Scalar> From<Vector<T>> for DVector<T> {
+    fn from(v: Vector<T>) -> DVector<T> {
+        DVector::from_vec(v.data)
+    }
+}
+
+impl<T: Number + Scalar> From<DMatrix<T>> for Matrix<T> {
+    fn from(m: DMatrix<T>) -> Matrix<T> {
+        let (rows, cols) = m.shape();
+        // The column major storage of the transpose is the row major order.
+        matrix::from_vec(rows, cols, m.transpose().data.into())
+    }
+}
+
+impl<T: Number + Scalar> From<Matrix<T>> for DMatrix<T> {
+    fn from(m: Matrix<T>) -> DMatrix<T> {
+        DMatrix::from_row_slice(m.num_rows(), m.num_cols(), &m.into_vec())
+    }
+}
@@ -0,0 +1,73 @@
+//! Conversions between `Vector` and `Array1`, and between `Matrix` and
+//! `Array2`
+//!
+//! Converting to ndarray never copies. Converting from ndarray takes over the
+//! array's buffer when the array is contiguous in row major order, which
+//! includes slices along the first axis. Arrays with any other layout, e.g.
+//! strided slices, reversed axes or column major arrays, are copied element by
+//! element in logical order.
+//!
+//! # Examples
+//! ```
+//! extern crate ndarray;
+//! extern crate numrs;
+//!
+//! use ndarray::{arr2, Array2};
+//! use numrs::matrix::Matrix;
+//!
+//! # fn main() {
+//! let m = Matrix::from(arr2(&[[1.0, 2.0], [3.0, 4.0]]).reversed_axes());
+//! assert_eq!(m.get_vec(), vec![1.0, 3.0, 2.0, 4.0]);
+//! let a: Array2<f64> = m.into();
+//! assert_eq!(a[[0, 1]], 3.0);
+//! # }
+//! ```
+
+extern crate ndarray;
+
+use self::ndarray::{Array, Array1, Array2, Dimension};
+use common::Number;
+use matrix;
+use matrix::Matrix;
+use vector;
+use vector::Vector;
+
+impl<T: Number> From<Array1<T>> for Vector<T> {
+    fn from(a: Array1<T>) -> Vector<T> {
+        vector::from_vec(into_row_major(a))
+    }
+}
+
+impl<T: Number> From<Vector<T>> for Array1<T> {
+    fn from(v: Vector<T>) -> Array1<T> {
+        Array1::from_vec(v.data)
+    }
+}
+
+impl<T: Number> From<Array2<T>> for Matrix<T> {
+    fn from(a: Array2<T>) -> Matrix<T> {
+        let (rows, cols) = a.dim();
+        matrix::from_vec(rows, cols, into_row_major(a))
+    }
+}
+
+impl<T: Number> From<Matrix<T>> for Array2<T> {
+    fn from(m: Matrix<T>) -> Array2<T> {
+        let shape = (m.num_rows(), m.num_cols());
+        Array2::from_shape_vec(shape, m.into_vec()).unwrap()
+    }
+}
+
+/// The elements of `a` in row major order, reusing its buffer if possible.
+fn into_row_major<T: Number, D: Dimension>(a: Array<T, D>) -> Vec<T> {
+    if !a.is_standard_layout() {
+        return a.iter().cloned().collect();
+    }
+    // A standard layout array is a contiguous run of its buffer, which may
+    // also hold elements that were sliced away.
+    let len = a.len();
+    let (mut data, offset) = a.into_raw_vec_and_offset();
+    data.drain(..offset.unwrap_or(0));
+    data.truncate(len);
+    data
+}
@@ -0,0 +1,178 @@
+//! Interpolation of sampled data
+//!
+//! # Examples
+//! ```
+//! use numrs::interp;
+//! use numrs::vector;
+//!
+//! let xp = vector::from_elems(&[0.0, 1.0, 2.0]);
+//! let fp = vector::from_elems(&[0.0, 10.0, 40.0]);
+//! let y = interp::interp(&vector::from_elems(&[0.5, 1.5]), &xp, &fp).unwrap();
+//! assert_eq!(y.data, vec![5.0, 25.0]);
+//! ```
+//!
+//! A `CubicSpline` passes a smooth curve through the same points:
+//! ```
+//! use numrs::interp::{BoundaryCondition, CubicSpline};
+//! use numrs::vector;
+//!
+//! let x = vector::from_elems(&[0.0, 1.0, 2.0]);
+//! let y = vector::from_elems(&[0.0, 1.0, 0.0]);
+//! let spline = CubicSpline::new(&x, &y, BoundaryCondition::Natural).unwrap();
+//! assert_eq!(spline.eval(&vector::from_elems(&[0.5])).data, vec![0.6875]);
+//! ```
+
+use std::cmp::Ordering;
+use common::Real;
+use vector::Vector;
+
+/// Piecewise linear interpolation of the points `(xp, fp)` at every element
+/// of `x`. `xp` must be non-empty, increasing and as long as `fp`. Points
+/// outside `[xp[0], xp[n - 1]]` take the value at the nearest end and NaN
+/// stays NaN.
+pub fn interp<T: Real>(x: &Vector<T>, xp: &Vector<T>, fp: &Vector<T>) -> Result<Vector<T>, String> {
+    let n = xp.len();
+    if n != fp.len() {
+        return Err("Sample points and values should have the same length.".to_string());
+    }
+    if n == 0 {
+        return Err("At least one sample point is required.".to_string());
+    }
+    if xp.data.windows(2).any(|w| w[0].partial_cmp(&w[1]) != Some(Ordering::Less)) {
+        return Err("Sample points should be strictly increasing.".to_string());
+    }
+    let mut out = Vec::with_capacity(x.len());
+    for &t in &x.data {
+        // Index of the first sample point greater than t.
+        let j = xp.data.partition_point(|&p| p <= t);
+        out.push(if t.is_nan() {
+            t
+        } else if j == 0 {
+            fp[0]
+        } else if j == n {
+            fp[n - 1]
+        } else {
+            let w = (t - xp[j - 1]) / (xp[j] - xp[j - 1]);
+            fp[j - 1] + (fp[j] - fp[j - 1]) * w
+        });
+    }
+    Ok(Vector::<T> { data: out })
+}
+
+/// What a `CubicSpline` assumes at the first and last sample points.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BoundaryCondition<T> {
+    /// The second derivative is zero at both ends.
+    Natural,
+    /// The first derivative takes the given values at the start and the end.
+    Clamped(T, T),
+}
+
+/// A cubic spline through the points `(x, y)`: a piecewise cubic with
+/// continuous first and second derivatives.
+#[derive(Clone, Debug)]
+pub struct CubicSpline<T: Real> {
+    x: Vec<T>,
+    y: Vec<T>,
+    /// Second derivative at every sample point.
+    m: Vec<T>,
+}
+
+impl<T: Real> CubicSpline<T> {
+    /// Fits the spline through `(x, y)`. `x` must be strictly increasing,
+    /// have at least 3 points and be as long as `y`.
+    pub fn new(x: &Vector<T>, y: &Vector<T>, bc: BoundaryCondition<T>) -> Result<CubicSpline<T>, String> {
+        let n = x.len();
+        if n != y.len() {
+            return Err("Sample points and values should have the same length.".to_string());
+        }
+        if n < 3 {
+            return Err("At least three sample points are required.".to_string());
+        }
+        if x.data.windows(2).any(|w| w[0].partial_cmp(&w[1]) != Some(Ordering::Less)) {
+            return Err("Sample points should be strictly increasing.".to_string());
+        }
+        let (x, y) = (&x.data, &y.data);
+        let h: Vec<T> = x.windows(2).map(|w| w[1] - w[0]).collect();
+        let slope: Vec<T> = (0..n - 1).map(|i| (y[i + 1] - y[i]) / h[i]).collect();
+        let (two, six) = (T::from(2).unwrap(), T::from(6).unwrap());
+
+        // Tridiagonal system for the second derivatives: sub, diag and sup
+        // are the three diagonals and rhs the right-hand side.
+        let mut sub = vec![T::zero(); n];
+        let mut diag = vec![T::one(); n];
+        let mut sup = vec![T::zero(); n];
+        let mut rhs = vec![T::zero(); n];
+        for i in 1..n - 1 {
+            sub[i] = h[i - 1];
+            diag[i] = two * (h[i - 1] + h[i]);
+            sup[i] = h[i];
+            rhs[i] = six * (slope[i] - slope[i - 1]);
+        }
+        if let BoundaryCondition::Clamped(start, end) = bc {
+            diag[0] = two * h[0];
+            sup[0] = h[0];
+            rhs[0] = six * (slope[0] - start);
+            sub[n - 1] = h[n - 2];
+            diag[n - 1] = two * h[n - 2];
+            rhs[n - 1] = six * (end - slope[n - 2]);
+        }
+        Ok(CubicSpline { x: x.clone(), y: y.clone(), m: solve_tridiagonal(&sub, &mut diag, &sup, &mut rhs) })
+    }
+
+    /// Values of the spline at every element of `x_new`. Points outside the
+    /// sample range are extrapolated with the cubic of the nearest end
+    /// segment, and NaN stays NaN.
+    pub fn eval(&self, x_new: &Vector<T>) -> Vector<T> {
+        self.map(x_new, |i, a, b, h| {
+            let six = T::from(6).unwrap();
+            a * self.y[i] + b * self.y[i + 1]
+                + ((a * a * a - a) * self.m[i] + (b * b * b - b) * self.m[i + 1]) * h * h / six
+        })
+    }
+
+    /// First derivative of the spline at every element of `x_new`, see
+    /// `eval`.
+    pub fn derivative(&self, x_new: &Vector<T>) -> Vector<T> {
+        self.map(x_new, |i, a, b, h| {
+            let (three, six) = (T::from(3).unwrap(), T::from(6).unwrap());
+            (self.y[i + 1] - self.y[i]) / h
+                - (three * a * a - T::one()) * h * self.m[i] / six
+                + (three * b * b - T::one()) * h * self.m[i + 1] / six
+        })
+    }
+
+    /// Applies `f(i, a, b, h)` to every point, where `i` is the segment
+    /// containing it, `h` the segment width and `a`, `b` its relative
+    /// distances from the right and left ends of the segment.
+    fn map<F: Fn(usize, T, T, T) -> T>(&self, x_new: &Vector<T>, f: F) -> Vector<T> {
+        let n = self.x.len();
+        let data = x_new.data.iter().map(|&t| {
+            if t.is_nan() {
+                return t;
+            }
+            let i = self.x.partition_point(|&p| p <= t).clamp(1, n - 1) - 1;
+            let h = self.x[i + 1] - self.x[i];
+            f(i, (self.x[i + 1] - t) / h, (t - self.x[i]) / h, h)
+        });
+        Vector::<T> { data: data.collect() }
+    }
+}
+
+/// Solves a tridiagonal system with the Thomas algorithm, overwriting `diag`
+/// and `rhs`. The systems built by `CubicSpline` are diagonally dominant, so
+/// no pivoting is needed.
+fn solve_tridiagonal<T: Real>(sub: &[T], diag: &mut [T], sup: &[T], rhs: &mut [T]) -> Vec<T> {
+    let n = diag.len();
+    for i in 1..n {
+        let w = sub[i] / diag[i - 1];
+        diag[i] = diag[i] - w * sup[i - 1];
+        rhs[i] = rhs[i] - w * rhs[i - 1];
+    }
+    let mut out = vec![T::zero(); n];
+    out[n - 1] = rhs[n - 1] / diag[n - 1];
+    for i in (0..n - 1).rev() {
+        out[i] = (rhs[i] - sup[i] * out[i + 1]) / diag[i];
+    }
+    out
+}
@@ -0,0 +1,128 @@
+//! Apache Arrow arrays and Parquet columns
+//!
+//! Any numeric Arrow column can be read, converting the values to `f64`.
+//! Null entries are handled according to a `NullHandling`.
+//!
+//! # Examples
+//! ```
+//! extern crate arrow;
+//! extern crate numrs;
+//!
+//! use arrow::array::Float64Array;
+//! use numrs::io::arrow::NullHandling;
+//! use numrs::vector::Vector;
+//!
+//! # fn main() {
+//! let array = Float64Array::from(vec![Some(1.0), None, Some(3.0)]);
+//! let v = Vector::from_arrow(&array, NullHandling::Nan).unwrap();
+//! assert!(v[1].is_nan());
+//! assert!(Vector::from_arrow(&array, NullHandling::Error).is_err());
+//! # }
+//! ```
+
+extern crate arrow;
+extern crate parquet;
+
+use std::fmt;
+use std::fs::File;
+use std::path::Path;
+use self::arrow::array::{Array, Float64Array};
+use self::arrow::compute;
+use self::arrow::datatypes::DataType;
+use self::arrow::record_batch::RecordBatch;
+use self::parquet::arrow::ProjectionMask;
+use self::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use matrix;
+use matrix::Matrix;
+use vector;
+use vector::Vector;
+
+/// What to do with null entries of an Arrow array.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NullHandling {
+    /// Fail on the first null.
+    Error,
+    /// Read nulls as NaN.
+    Nan,
+}
+
+impl Vector<f64> {
+    /// Copies the values of `array` into a new vector. The copy is needed
+    /// because the array only lends its buffer; see `into_arrow` for the
+    /// zero-copy direction.
+    pub fn from_arrow(array: &Float64Array, nulls: NullHandling) -> Result<Vector<f64>, String> {
+        values(array, nulls).map_err(|i| format!("Arrow array has a null at index {}.", i))
+            .map(vector::from_vec)
+    }
+
+    /// Converts the vector into an Arrow array without copying, by handing
+    /// over its buffer.
+    pub fn into_arrow(self) -> Float64Array {
+        Float64Array::from(self.data)
+    }
+
+    /// Copies the vector into an Arrow array.
+    pub fn to_arrow(&self) -> Float64Array {
+        self.clone().into_arrow()
+    }
+}
+
+impl Matrix<f64> {
+    /// Builds a matrix whose columns are the `columns` of `batch`, in the
+    /// given order. Every selected column must be numeric.
+    pub fn from_record_batch(batch: &RecordBatch, columns: &[&str],
+                             nulls: NullHandling) -> Result<Matrix<f64>, String> {
+        let (rows, cols) = (batch.num_rows(), columns.len());
+        let mut data = vec![0.0; rows * cols];
+        for (j, name) in columns.iter().enumerate() {
+            for (i, x) in numeric_column(batch, name, nulls)?.into_iter().enumerate() {
+                data[i * cols + j] = x;
+            }
+        }
+        Ok(matrix::from_elems(rows, cols, &data))
+    }
+}
+
+/// Reads the numeric `column` of the Parquet file at `path`.
+pub fn read_parquet_column<P: AsRef<Path>>(path: P, column: &str,
+                                           nulls: NullHandling) -> Result<Vector<f64>, String> {
+    let path = path.as_ref();
+    let fail = |e: &dyn fmt::Display| format!("Could not read {}: {}", path.display(), e);
+    let file = File::open(path).map_err(|e| fail(&e))?;
+    let builder = ParquetRecordBatchReaderBuilder::try_new(file).map_err(|e| fail(&e))?;
+    let mask = ProjectionMask::columns(builder.parquet_schema(), vec![column]);
+    let reader = builder.with_projection(mask).build().map_err(|e| fail(&e))?;
+    let mut data = Vec::new();
+    for batch in reader {
+        let batch = batch.map_err(|e| fail(&e))?;
+        data.extend(numeric_column(&batch, column, nulls)?);
+    }
+    Ok(vector::from_vec(data))
+}
+
+/// The values of the column called `name`, converted to `f64`.
+fn numeric_column(batch: &RecordBatch, name: &str, nulls: NullHandling) -> Result<Vec<f64>, String> {
+    let array = batch.column_by_name(name)
+        .ok_or_else(|| format!("No column named '{}'.", name))?;
+    if !array.data_type().is_numeric() {
+        return Err(format!("Column '{}' has non-numeric type {}.", name, array.data_type()));
+    }
+    let cast = compute::cast(array, &DataType::Float64).map_err(|e| e.to_string())?;
+    let array = cast.as_any().downcast_ref::<Float64Array>().unwrap();
+    values(array, nulls).map_err(|i| format!("Column '{}' has a null at row {}.", name, i))
+}
+
+/// The values of `array`, or the index of the first null if nulls are errors.
+fn values(array: &Float64Array, nulls: NullHandling) -> Result<Vec<f64>, usize> {
+    if array.null_count() == 0 {
+        return Ok(array.values().to_vec());
+    }
+    array.iter()
+        .enumerate()
+        .map(|(i, x)| match (x, nulls) {
+            (Some(x), _) => Ok(x),
+            (None, NullHandling::Nan) => Ok(f64::NAN),
+            (None, NullHandling::Error) => Err(i),
+        })
+        .collect()
+}
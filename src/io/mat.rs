@@ -0,0 +1,330 @@
+//! MATLAB level 5 MAT-files
+//!
+//! Only real double and single precision arrays with at most two dimensions
+//! are supported, stored either plainly or zlib compressed. Arrays with a
+//! single row or column are read as vectors and everything else as matrices.
+//! MAT-files store arrays in column major order, so matrices are transposed
+//! into row major order when read and back when written.
+//!
+//! # Examples
+//! ```no_run
+//! use numrs::io::mat::{self, MatVariable};
+//!
+//! let vars = mat::read_mat("data.mat").unwrap();
+//! if let Some(&MatVariable::Matrix(ref m)) = vars.get("A") {
+//!     println!("A is {} x {}", m.num_rows(), m.num_cols());
+//! }
+//! ```
+
+extern crate flate2;
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use self::flate2::read::ZlibDecoder;
+use common::Number;
+use matrix;
+use matrix::Matrix;
+use vector;
+use vector::Vector;
+
+const MI_INT8: u32 = 1;
+const MI_UINT8: u32 = 2;
+const MI_INT16: u32 = 3;
+const MI_UINT16: u32 = 4;
+const MI_INT32: u32 = 5;
+const MI_UINT32: u32 = 6;
+const MI_SINGLE: u32 = 7;
+const MI_DOUBLE: u32 = 9;
+const MI_INT64: u32 = 12;
+const MI_UINT64: u32 = 13;
+const MI_MATRIX: u32 = 14;
+const MI_COMPRESSED: u32 = 15;
+
+const MX_DOUBLE_CLASS: u32 = 6;
+const MX_SINGLE_CLASS: u32 = 7;
+const FLAG_COMPLEX: u32 = 0x0800;
+
+/// A variable read from or written to a MAT-file.
+#[derive(Clone, PartialEq)]
+pub enum MatVariable {
+    /// A double precision row or column vector.
+    Vector(Vector<f64>),
+    /// A double precision matrix.
+    Matrix(Matrix<f64>),
+    /// A single precision row or column vector.
+    SingleVector(Vector<f32>),
+    /// A single precision matrix.
+    SingleMatrix(Matrix<f32>),
+}
+
+/// Reads all the variables of the MAT-file at `path`, keyed by name. Fails if
+/// any variable is not a supported numeric array.
+pub fn read_mat<P: AsRef<Path>>(path: P) -> Result<HashMap<String, MatVariable>, String> {
+    let path = path.as_ref();
+    let bytes = fs::read(path).map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+    if bytes.len() < 128 {
+        return Err("Not a level 5 MAT-file: the header is too short.".to_string());
+    }
+    let big_endian = match &bytes[126..128] {
+        b"IM" => false,
+        b"MI" => true,
+        _ => return Err("Not a level 5 MAT-file: unknown endian indicator.".to_string()),
+    };
+    let mut vars = HashMap::new();
+    let mut cur = Cursor::new(&bytes[128..], big_endian);
+    while !cur.is_done() {
+        let (ty, payload) = cur.element()?;
+        let (name, var) = match ty {
+            MI_MATRIX => variable(payload, big_endian)?,
+            MI_COMPRESSED => {
+                let mut inflated = Vec::new();
+                ZlibDecoder::new(payload)
+                    .read_to_end(&mut inflated)
+                    .map_err(|e| format!("Could not decompress a variable: {}", e))?;
+                let mut inner = Cursor::new(&inflated, big_endian);
+                match inner.element()? {
+                    (MI_MATRIX, payload) => variable(payload, big_endian)?,
+                    _ => continue,
+                }
+            }
+            _ => continue,
+        };
+        vars.insert(name, var);
+    }
+    Ok(vars)
+}
+
+/// Writes `vars` to an uncompressed MAT-file at `path`, in order of their
+/// names. Vectors are written as `1 x n` row vectors.
+pub fn write_mat<P: AsRef<Path>>(path: P, vars: &HashMap<String, MatVariable>) -> Result<(), String> {
+    let mut out = Vec::new();
+    let mut text = b"MATLAB 5.0 MAT-file, written by numrs".to_vec();
+    text.resize(116, b' ');
+    out.extend_from_slice(&text);
+    out.extend_from_slice(&[0; 8]);
+    out.extend_from_slice(&0x0100u16.to_le_bytes());
+    out.extend_from_slice(b"IM");
+
+    let mut names: Vec<&String> = vars.keys().collect();
+    names.sort();
+    for name in names {
+        let (class, ty, rows, cols, data) = match vars[name] {
+            MatVariable::Vector(ref v) => {
+                (MX_DOUBLE_CLASS, MI_DOUBLE, 1, v.len(), le_bytes(&v.data, f64::to_le_bytes))
+            }
+            MatVariable::SingleVector(ref v) => {
+                (MX_SINGLE_CLASS, MI_SINGLE, 1, v.len(), le_bytes(&v.data, f32::to_le_bytes))
+            }
+            MatVariable::Matrix(ref m) => {
+                let t = column_major(m);
+                (MX_DOUBLE_CLASS, MI_DOUBLE, m.num_rows(), m.num_cols(), le_bytes(&t, f64::to_le_bytes))
+            }
+            MatVariable::SingleMatrix(ref m) => {
+                let t = column_major(m);
+                (MX_SINGLE_CLASS, MI_SINGLE, m.num_rows(), m.num_cols(), le_bytes(&t, f32::to_le_bytes))
+            }
+        };
+        let mut body = Vec::new();
+        push_element(&mut body, MI_UINT32, &le_bytes(&[class, 0], u32::to_le_bytes));
+        push_element(&mut body, MI_INT32, &le_bytes(&[rows as i32, cols as i32], i32::to_le_bytes));
+        push_element(&mut body, MI_INT8, name.as_bytes());
+        push_element(&mut body, ty, &data);
+        push_element(&mut out, MI_MATRIX, &body);
+    }
+    fs::write(path.as_ref(), out).map_err(|e| format!("Could not write {}: {}", path.as_ref().display(), e))
+}
+
+/// Reads the name and value of a variable from the payload of a matrix
+/// element.
+fn variable(payload: &[u8], big_endian: bool) -> Result<(String, MatVariable), String> {
+    let mut cur = Cursor::new(payload, big_endian);
+    let flags = cur.element().and_then(|(_, d)| cur.word(d))?;
+    let dims: Vec<f64> = cur.element()
+        .and_then(|(ty, d)| numbers(ty, d, big_endian).ok_or_else(|| "Bad dimensions.".to_string()))?;
+    let name = String::from_utf8_lossy(cur.element()?.1).into_owned();
+
+    let class = flags & 0xff;
+    if class != MX_DOUBLE_CLASS && class != MX_SINGLE_CLASS {
+        return Err(format!("Variable '{}' has unsupported class {}.", name, class_name(class)));
+    }
+    if flags & FLAG_COMPLEX != 0 {
+        return Err(format!("Variable '{}' is complex, which is not supported.", name));
+    }
+    if dims.len() != 2 {
+        return Err(format!("Variable '{}' has {} dimensions but at most 2 are supported.",
+                           name, dims.len()));
+    }
+    if dims.iter().any(|&d| d < 0.0 || d.fract() != 0.0 || d >= usize::MAX as f64) {
+        return Err(format!("Variable '{}' has invalid dimensions.", name));
+    }
+    let (rows, cols) = (dims[0] as usize, dims[1] as usize);
+    let len = rows.checked_mul(cols)
+        .ok_or_else(|| format!("Variable '{}' has invalid dimensions.", name))?;
+    let (ty, real) = cur.element()?;
+    let values = numbers(ty, real, big_endian)
+        .ok_or_else(|| format!("Variable '{}' has unsupported storage type {}.", name, ty))?;
+    if values.len() != len {
+        return Err(format!("Variable '{}' has {} elements but its dimensions are {} x {}.",
+                           name, values.len(), rows, cols));
+    }
+
+    let mut data = Vec::with_capacity(values.len());
+    for i in 0..rows {
+        for j in 0..cols {
+            data.push(values[j * rows + i]);
+        }
+    }
+    let is_vector = rows == 1 || cols == 1;
+    let var = if class == MX_DOUBLE_CLASS {
+        if is_vector {
+            MatVariable::Vector(vector::from_vec(data))
+        } else {
+            MatVariable::Matrix(matrix::from_elems(rows, cols, &data))
+        }
+    } else {
+        let data: Vec<f32> = data.iter().map(|&x| x as f32).collect();
+        if is_vector {
+            MatVariable::SingleVector(vector::from_vec(data))
+        } else {
+            MatVariable::SingleMatrix(matrix::from_elems(rows, cols, &data))
+        }
+    };
+    Ok((name, var))
+}
+
+/// Decodes numeric data stored as MAT data type `ty`, or `None` if `ty` is
+/// not numeric.
+fn numbers(ty: u32, data: &[u8], big_endian: bool) -> Option<Vec<f64>> {
+    let size = match ty {
+        MI_INT8 | MI_UINT8 => 1,
+        MI_INT16 | MI_UINT16 => 2,
+        MI_INT32 | MI_UINT32 | MI_SINGLE => 4,
+        MI_DOUBLE | MI_INT64 | MI_UINT64 => 8,
+        _ => return None,
+    };
+    if !data.len().is_multiple_of(size) {
+        return None;
+    }
+    let values = data.chunks(size).map(|c| {
+        let mut b = [0; 8];
+        if big_endian {
+            for (dst, &src) in b.iter_mut().zip(c.iter().rev()) {
+                *dst = src;
+            }
+        } else {
+            b[..size].copy_from_slice(c);
+        }
+        let bits = u64::from_le_bytes(b);
+        match ty {
+            MI_INT8 => bits as u8 as i8 as f64,
+            MI_UINT8 => bits as u8 as f64,
+            MI_INT16 => bits as u16 as i16 as f64,
+            MI_UINT16 => bits as u16 as f64,
+            MI_INT32 => bits as u32 as i32 as f64,
+            MI_UINT32 => bits as u32 as f64,
+            MI_SINGLE => f32::from_bits(bits as u32) as f64,
+            MI_DOUBLE => f64::from_bits(bits),
+            MI_INT64 => bits as i64 as f64,
+            _ => bits as f64,
+        }
+    });
+    Some(values.collect())
+}
+
+fn class_name(class: u32) -> String {
+    let name = match class {
+        1 => "cell",
+        2 => "struct",
+        3 => "object",
+        4 => "char",
+        5 => "sparse",
+        8 => "int8",
+        9 => "uint8",
+        10 => "int16",
+        11 => "uint16",
+        12 => "int32",
+        13 => "uint32",
+        14 => "int64",
+        15 => "uint64",
+        _ => return format!("{}", class),
+    };
+    name.to_string()
+}
+
+/// The elements of `m` in column major order.
+fn column_major<T: Number>(m: &Matrix<T>) -> Vec<T> {
+    let mut t = m.clone();
+    t.transpose();
+    t.get_vec()
+}
+
+fn le_bytes<T: Copy, B: AsRef<[u8]>>(values: &[T], f: fn(T) -> B) -> Vec<u8> {
+    values.iter().flat_map(|&x| f(x).as_ref().to_vec()).collect()
+}
+
+/// Appends a data element with a full tag, padded to 8 bytes.
+fn push_element(out: &mut Vec<u8>, ty: u32, data: &[u8]) {
+    out.extend_from_slice(&ty.to_le_bytes());
+    out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    out.extend_from_slice(data);
+    while !out.len().is_multiple_of(8) {
+        out.push(0);
+    }
+}
+
+/// Reads the data elements in a buffer, which start at offsets that are
+/// multiples of 8.
+struct Cursor<'a> {
+    data: &'a [u8],
+    pos: usize,
+    big_endian: bool,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(data: &'a [u8], big_endian: bool) -> Cursor<'a> {
+        Cursor { data, pos: 0, big_endian }
+    }
+
+    fn is_done(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+
+    fn word(&self, bytes: &[u8]) -> Result<u32, String> {
+        if bytes.len() < 4 {
+            return Err("Unexpected end of MAT-file.".to_string());
+        }
+        let b = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        Ok(if self.big_endian { u32::from_be_bytes(b) } else { u32::from_le_bytes(b) })
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        if self.data.len() - self.pos < n {
+            return Err("Unexpected end of MAT-file.".to_string());
+        }
+        let bytes = &self.data[self.pos..self.pos + n];
+        self.pos += n;
+        Ok(bytes)
+    }
+
+    /// Reads the type and the data of the next element, which may use the
+    /// small format that packs up to 4 bytes of data into the tag.
+    fn element(&mut self) -> Result<(u32, &'a [u8]), String> {
+        let tag = self.take(8)?;
+        let (first, second) = (self.word(&tag[..4])?, self.word(&tag[4..])?);
+        if first >> 16 != 0 {
+            let n = (first >> 16) as usize;
+            if n > 4 {
+                return Err("Malformed small data element in MAT-file.".to_string());
+            }
+            return Ok((first & 0xffff, &tag[4..4 + n]));
+        }
+        let data = self.take(second as usize)?;
+        // Compressed elements are not padded.
+        if first != MI_COMPRESSED {
+            self.pos = ((self.pos + 7) & !7).min(self.data.len());
+        }
+        Ok((first, data))
+    }
+}
@@ -0,0 +1,136 @@
+//! Read-only vectors backed by memory-mapped files
+//!
+//! An `MmapVector` exposes a binary file of raw `f32` or `f64` values
+//! through `VectorOps` without reading it into memory, so reductions and dot
+//! products over large files only touch the pages they need. The values are
+//! read in the native byte order of the machine; `slice_swapped` converts
+//! files written with the other byte order.
+//!
+//! The file must not be modified while it is mapped, since the mapped values
+//! would change underneath any borrowed slice.
+//!
+//! # Examples
+//! ```no_run
+//! use numrs::io::MmapVector;
+//! use numrs::vector::VectorOps;
+//!
+//! let v = MmapVector::<f64>::open("samples.bin").unwrap();
+//! println!("{} values with mean {}", v.len(), v.mean());
+//! let head = v.slice(0..10).unwrap();
+//! ```
+
+extern crate memmap2;
+
+use std::fs::File;
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::Range;
+use std::path::Path;
+use std::slice;
+use self::memmap2::Mmap;
+use common::Number;
+use vector;
+use vector::{Vector, VectorOps};
+
+/// Element types that can be read from a mapped file.
+pub trait MmapElement: Number + private::Sealed {
+    /// Reverses the byte order of the value.
+    fn swap_bytes(self) -> Self;
+}
+
+impl MmapElement for f32 {
+    fn swap_bytes(self) -> f32 {
+        f32::from_bits(self.to_bits().swap_bytes())
+    }
+}
+
+impl MmapElement for f64 {
+    fn swap_bytes(self) -> f64 {
+        f64::from_bits(self.to_bits().swap_bytes())
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+}
+
+/// A read-only vector whose elements live in a memory-mapped file.
+pub struct MmapVector<T: MmapElement> {
+    map: Option<Mmap>,
+    len: usize,
+    marker: PhantomData<T>,
+}
+
+impl<T: MmapElement> MmapVector<T> {
+    /// Maps the file at `path`. Fails if the file cannot be mapped or its size
+    /// is not a multiple of the element size.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<MmapVector<T>, String> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| format!("Could not open {}: {}", path.display(), e))?;
+        let bytes = file.metadata()
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))?
+            .len() as usize;
+        let size = mem::size_of::<T>();
+        if !bytes.is_multiple_of(size) {
+            return Err(format!("File size {} of {} is not a multiple of the element size {}.",
+                               bytes, path.display(), size));
+        }
+        if bytes == 0 {
+            return Ok(MmapVector { map: None, len: 0, marker: PhantomData });
+        }
+        // The mapping is only ever read and the caller is told not to modify
+        // the file while it is mapped.
+        let map = unsafe { Mmap::map(&file) }
+            .map_err(|e| format!("Could not map {}: {}", path.display(), e))?;
+        if !(map.as_ptr() as usize).is_multiple_of(mem::align_of::<T>()) {
+            return Err(format!("Mapping of {} is misaligned.", path.display()));
+        }
+        Ok(MmapVector { map: Some(map), len: bytes / size, marker: PhantomData })
+    }
+
+    /// Number of elements in the file.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the file holds no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Copies the elements in `range` into a new vector. Fails if the range
+    /// is out of bounds.
+    pub fn slice(&self, range: Range<usize>) -> Result<Vector<T>, String> {
+        self.check_range(&range)?;
+        Ok(vector::from_elems(&self.as_slice()[range]))
+    }
+
+    /// Copies the elements in `range` like `slice`, reversing the byte order
+    /// of each one. Use this for files written on a machine of the other
+    /// endianness.
+    pub fn slice_swapped(&self, range: Range<usize>) -> Result<Vector<T>, String> {
+        self.check_range(&range)?;
+        Ok(vector::from_vec(self.as_slice()[range].iter().map(|x| x.swap_bytes()).collect()))
+    }
+
+    fn check_range(&self, range: &Range<usize>) -> Result<(), String> {
+        if range.start > range.end || range.end > self.len {
+            return Err(format!("Range {}..{} out of bounds for vector of length {}.",
+                               range.start, range.end, self.len));
+        }
+        Ok(())
+    }
+}
+
+impl<T: MmapElement> VectorOps<T> for MmapVector<T> {
+    fn as_slice(&self) -> &[T] {
+        match self.map {
+            // The mapping is aligned for T, checked in open, holds exactly
+            // len elements and every bit pattern is a valid float.
+            Some(ref map) => unsafe { slice::from_raw_parts(map.as_ptr() as *const T, self.len) },
+            None => &[],
+        }
+    }
+}
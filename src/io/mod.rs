@@ -0,0 +1,56 @@
+//! Reading and writing vectors and matrices in other file formats
+//!
+//! # Examples
+//! ```
+//! use numrs::io;
+//! use numrs::vector;
+//!
+//! let mut bytes = Vec::new();
+//! io::write_f32_le(&mut bytes, &vector::from_elems(&[1.0, 2.5])).unwrap();
+//! let v = io::read_f32_le(&mut &bytes[..], 2).unwrap();
+//! assert_eq!(v.data, vec![1.0, 2.5]);
+//! ```
+
+use std::io::{self, Read, Write};
+use vector;
+use vector::Vector;
+
+/// Apache Arrow arrays and Parquet columns
+#[cfg(feature = "arrow")]
+pub mod arrow;
+
+/// MATLAB level 5 MAT-files
+#[cfg(feature = "mat")]
+pub mod mat;
+
+/// Read-only vectors backed by memory-mapped files
+#[cfg(feature = "mmap")]
+pub mod mmap;
+
+#[cfg(feature = "mmap")]
+pub use self::mmap::MmapVector;
+
+/// Size of the buffer used to convert between bytes and numbers.
+const BUFFER_BYTES: usize = 8192;
+
+/// Reads `count` little-endian `f32` values from `reader`, a buffer at a time.
+/// Fails with `UnexpectedEof` if the reader ends early.
+pub fn read_f32_le<R: Read>(reader: &mut R, count: usize) -> io::Result<Vector<f32>> {
+    let mut data = Vec::with_capacity(count.min(BUFFER_BYTES));
+    let mut buf = [0; BUFFER_BYTES];
+    while data.len() < count {
+        let n = (count - data.len()).min(BUFFER_BYTES / 4);
+        reader.read_exact(&mut buf[..4 * n])?;
+        data.extend(buf[..4 * n].chunks(4).map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])));
+    }
+    Ok(vector::from_vec(data))
+}
+
+/// Writes the elements of `v` to `writer` as little-endian `f32` values.
+pub fn write_f32_le<W: Write>(writer: &mut W, v: &Vector<f32>) -> io::Result<()> {
+    for chunk in v.data.chunks(BUFFER_BYTES / 4) {
+        let bytes: Vec<u8> = chunk.iter().flat_map(|x| x.to_le_bytes().to_vec()).collect();
+        writer.write_all(&bytes)?;
+    }
+    Ok(())
+}
@@ -1,8 +1,60 @@
 /// Common types and utilities
 pub mod common;
 
+/// A 1D Vector type of complex numbers
+pub mod complex;
+
+/// Matrix decompositions
+pub mod decomposition;
+
+/// C-compatible interface to `Vector<f64>`
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+/// Text formatting options for vectors and matrices
+pub mod format;
+
+/// Half precision storage for `f32` data
+pub mod half;
+
+/// Interpolation of sampled data
+pub mod interp;
+
+/// Conversions to and from the types of other numeric crates
+pub mod interop;
+
+/// Reading and writing vectors and matrices in other file formats
+pub mod io;
+
+/// Iterative solvers for linear systems
+pub mod linalg;
+
 /// A 2D Matrix type
 pub mod matrix;
 
+/// Distance metrics between the rows of matrices
+pub mod metrics;
+
+/// Polynomials
+pub mod poly;
+
+/// Scalar reference implementations of the vector operations
+pub mod reference;
+
+/// A copy-on-write shared Vector
+pub mod shared;
+
+/// Special functions
+pub mod special;
+
+/// Statistical helpers
+pub mod stats;
+
+/// Incremental computations over streaming data
+pub mod stream;
+
 /// A 1D Vector type
 pub mod vector;
+
+/// Borrowed views into vectors and slices
+pub mod view;
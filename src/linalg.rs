@@ -0,0 +1,130 @@
+//! Iterative solvers for linear systems
+//!
+//! The solvers only need to apply the matrix to vectors, through the
+//! `MatVec` trait, so they also work for operators that are never stored as
+//! a dense `Matrix`.
+//!
+//! # Examples
+//! ```
+//! use numrs::linalg::{self, CgOptions};
+//! use numrs::matrix;
+//! use numrs::vector;
+//!
+//! let a = matrix::from_elems(2, 2, &[4.0f64, 1.0, 1.0, 3.0]);
+//! let b = vector::from_elems(&[1.0, 2.0]);
+//! let result = linalg::conjugate_gradient(&a, &b, CgOptions::default()).unwrap();
+//! assert!((result.solution[0] - 1.0 / 11.0).abs() < 1e-12);
+//! assert!((result.solution[1] - 7.0 / 11.0).abs() < 1e-12);
+//! ```
+
+use common::Real;
+use matrix::Matrix;
+use vector::{Vector, VectorOps};
+
+/// A linear operator that can be applied to vectors.
+pub trait MatVec<T: Real> {
+    /// Number of rows and columns of the operator.
+    fn shape(&self) -> (usize, usize);
+
+    /// The product of the operator with `x`, which has as many elements as
+    /// the operator has columns.
+    fn matvec(&self, x: &Vector<T>) -> Vector<T>;
+}
+
+impl<T: Real> MatVec<T> for Matrix<T> {
+    fn shape(&self) -> (usize, usize) {
+        (self.num_rows(), self.num_cols())
+    }
+
+    fn matvec(&self, x: &Vector<T>) -> Vector<T> {
+        self.mul_vec(x).unwrap()
+    }
+}
+
+/// Options for `conjugate_gradient`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CgOptions<T: Real> {
+    /// The iteration stops once the residual norm `|b - Ax|` is at most
+    /// `tol * |b|`.
+    pub tol: T,
+    /// Iterations allowed before giving up.
+    pub max_iter: usize,
+    /// Diagonal of a Jacobi preconditioner, usually the diagonal of the
+    /// matrix. Every entry must be positive.
+    pub preconditioner: Option<Vector<T>>,
+}
+
+impl<T: Real> Default for CgOptions<T> {
+    /// A relative tolerance of `1e-10`, 1000 iterations and no
+    /// preconditioner.
+    fn default() -> CgOptions<T> {
+        CgOptions { tol: T::from(1e-10).unwrap(), max_iter: 1000, preconditioner: None }
+    }
+}
+
+/// The outcome of a converged `conjugate_gradient` solve.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CgResult<T: Real> {
+    pub solution: Vector<T>,
+    /// Number of iterations taken.
+    pub iterations: usize,
+    /// Norm of the final residual `b - Ax`.
+    pub residual: T,
+}
+
+/// Solves `a x = b` for a symmetric positive definite operator with the
+/// (preconditioned) conjugate gradient method, starting from `x = 0`. Fails
+/// if the shapes do not match, the preconditioner is invalid, the operator
+/// turns out not to be positive definite, or the tolerance is not reached
+/// within `opts.max_iter` iterations.
+pub fn conjugate_gradient<T: Real, A: MatVec<T>>(a: &A, b: &Vector<T>,
+                                                 opts: CgOptions<T>) -> Result<CgResult<T>, String> {
+    let (rows, cols) = a.shape();
+    if rows != cols {
+        return Err("The operator should be square.".to_string());
+    }
+    if rows != b.len() {
+        return Err("Operator and vector are not conformable for solving.".to_string());
+    }
+    if let Some(ref d) = opts.preconditioner {
+        if d.len() != b.len() {
+            return Err("Preconditioner and vector are not conformable.".to_string());
+        }
+        if d.data.iter().any(|&x| x <= T::zero() || x.is_nan()) {
+            return Err("Preconditioner entries should be positive.".to_string());
+        }
+    }
+    let precondition = |r: &Vector<T>| match opts.preconditioner {
+        Some(ref d) => r / d,
+        None => r.clone(),
+    };
+    let target = opts.tol * b.norm();
+    let mut x = Vector::new(b.len(), T::zero());
+    let mut r = b.clone();
+    let mut p = precondition(&r);
+    let mut rz = r.dot(&p)?;
+    for k in 0..opts.max_iter {
+        let residual = r.norm();
+        if residual <= target {
+            return Ok(CgResult { solution: x, iterations: k, residual });
+        }
+        let ap = a.matvec(&p);
+        let pap = p.dot(&ap)?;
+        if pap <= T::zero() {
+            return Err("The operator is not positive definite.".to_string());
+        }
+        let alpha = rz / pap;
+        x.axpy(alpha, &p)?;
+        r.axpy(-alpha, &ap)?;
+        let z = precondition(&r);
+        let next = r.dot(&z)?;
+        p = z + p * (next / rz);
+        rz = next;
+    }
+    let residual = r.norm();
+    if residual <= target {
+        return Ok(CgResult { solution: x, iterations: opts.max_iter, residual });
+    }
+    Err(format!("Conjugate gradient did not converge in {} iterations, residual norm {}.",
+                opts.max_iter, residual.to_f64().unwrap()))
+}
@@ -21,12 +21,31 @@
 //! res = m2.clone() * 5.0; // scalar product of a matrix
 //! ```
 
+extern crate num;
+#[cfg(feature = "parallel")]
+extern crate rayon;
+
+use std::fmt;
+use std::mem;
 use std::ops::{Index, Add, Sub, Mul, Neg};
-use common::Number;
+use std::str::FromStr;
+use self::num::traits::ToPrimitive;
+use common::{Number, Real};
+use decomposition::QrFactorization;
+use format::FormatOptions;
+use linalg::{self, CgOptions};
 use vector;
-use vector::Vector;
+use vector::{Vector, VectorOps};
+#[cfg(feature = "parallel")]
+use self::rayon::prelude::*;
+
+/// Products with fewer multiply-adds than this are computed on the calling
+/// thread even when the `parallel` feature is enabled.
+#[cfg(feature = "parallel")]
+const PARALLEL_THRESHOLD: usize = 64 * 64 * 64;
 
 /// 2D Matrix
+#[derive(Debug)]
 pub struct Matrix<T: Number> {
     rows: usize,
     cols: usize,
@@ -38,7 +57,7 @@ impl<T: Number> Index<usize> for Matrix<T> {
 
     #[inline]
     fn index<'a>(&'a self, index: usize) -> &'a [T] {
-        &self.data.data[self.rows * index..self.rows * index + self.cols]
+        &self.data.data[self.cols * index..self.cols * index + self.cols]
     }
 }
 
@@ -94,21 +113,7 @@ impl<T: Number> Mul<Matrix<T>> for Matrix<T> {
     type Output = Matrix<T>;
 
     fn mul(self, rhs: Matrix<T>) -> Matrix<T> {
-        if self.num_cols() == rhs.num_rows() {
-            let mut new_mat = Matrix::<T>::new(self.num_rows(), rhs.num_cols(), T::zero());
-            for i in 0..self.num_rows() {
-                for j in 0..rhs.num_cols() {
-                    let mut sum: T = T::zero();
-                    for k in 0..self.num_cols() {
-                        sum = sum + self.get(i, k) * rhs.get(k, j);
-                    }
-                    new_mat.set(i, j, sum);
-                }
-            }
-            new_mat
-        } else {
-            panic!("Matrices are not conformable for multiplication.")
-        }
+        self.matmul(&rhs)
     }
 }
 
@@ -147,6 +152,60 @@ impl<T: Number> PartialEq for Matrix<T> {
 
 impl<T: Number> Eq for Matrix<T> {}
 
+impl<T: Number + fmt::Display> fmt::Display for Matrix<T> {
+    /// Writes one row per line in the format of `Vector`, which `from_str`
+    /// reads back.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for i in 0..self.num_rows() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", vector::from_elems(&self[i]))?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses rows separated by `;` or newlines, each written like a `Vector`,
+/// e.g. `1 2; 3 4` or `[1, 2]\n[3, 4]`. A `[` before and a `]` after each row
+/// are optional, so `[1 2; 3 4]` works too, and blank rows are skipped. All
+/// rows must have the same length. Errors give the character offset of the
+/// offending token or row.
+impl<T: Real + FromStr> FromStr for Matrix<T> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Matrix<T>, String> {
+        let (mut rows, mut cols, mut data) = (0, 0, Vec::new());
+        let mut offset = 0;
+        for line in s.split(&[';', '\n'][..]) {
+            let indent = line.len() - line.trim_start().len();
+            let mut start = offset + line[..indent].chars().count();
+            offset += line.chars().count() + 1;
+            let mut piece = line.trim();
+            if piece.starts_with('[') {
+                piece = &piece[1..];
+                start += 1;
+            }
+            if piece.ends_with(']') {
+                piece = &piece[..piece.len() - 1];
+            }
+            if piece.trim().is_empty() {
+                continue;
+            }
+            let row = vector::parse_elems::<T>(piece, start)?;
+            if rows == 0 {
+                cols = row.len();
+            } else if row.len() != cols {
+                return Err(format!("Row {} at offset {} has {} elements instead of {}.",
+                                   rows, start, row.len(), cols));
+            }
+            data.extend(row);
+            rows += 1;
+        }
+        Ok(Matrix::<T> { rows, cols, data: vector::from_vec(data) })
+    }
+}
+
 impl<T: Number> Matrix<T> {
     /// Returns the number of rows in the matrix.
     #[inline]
@@ -209,15 +268,80 @@ impl<T: Number> Matrix<T> {
         self.data.data.clone()
     }
 
+    /// Returns the elements in row major order without copying them.
+    pub fn into_vec(self) -> Vec<T> {
+        self.data.data
+    }
+
     /// Transposes the matrix.
     pub fn transpose(&mut self) {
         let mut v = self.data.clone();
         for i in 0..self.num_rows() {
             for j in 0..self.num_cols() {
-                v[j * self.num_cols() + i] = self.get(i, j);
+                v[j * self.num_rows() + i] = self.get(i, j);
             }
         }
         self.data = v;
+        mem::swap(&mut self.rows, &mut self.cols);
+    }
+
+    /// Matrix product of `self` and `rhs`.
+    ///
+    /// With the `parallel` feature, large products are split into blocks of
+    /// output rows which are computed on the rayon thread pool. Every element
+    /// is accumulated in the same order either way, so the results are
+    /// identical to the serial ones.
+    pub fn matmul(&self, rhs: &Matrix<T>) -> Matrix<T> {
+        if self.num_cols() != rhs.num_rows() {
+            panic!("Matrices are not conformable for multiplication.")
+        }
+        let mut new_mat = Matrix::<T>::new(self.num_rows(), rhs.num_cols(), T::zero());
+        if rhs.num_cols() > 0 {
+            self.matmul_into(rhs, &mut new_mat.data.data);
+        }
+        new_mat
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    fn matmul_into(&self, rhs: &Matrix<T>, out: &mut [T]) {
+        self.matmul_rows(rhs, 0, out);
+    }
+
+    #[cfg(feature = "parallel")]
+    fn matmul_into(&self, rhs: &Matrix<T>, out: &mut [T]) {
+        let (rows, cols) = (self.num_rows(), rhs.num_cols());
+        if rows * self.num_cols() * cols < PARALLEL_THRESHOLD {
+            return self.matmul_rows(rhs, 0, out);
+        }
+        let block = (rows / (4 * rayon::current_num_threads())).max(1);
+        out.par_chunks_mut(block * cols)
+            .enumerate()
+            .for_each(|(b, chunk)| self.matmul_rows(rhs, b * block, chunk));
+    }
+
+    /// Computes the rows of `self * rhs` starting from row `first` into `out`.
+    fn matmul_rows(&self, rhs: &Matrix<T>, first: usize, out: &mut [T]) {
+        let (n, cols) = (self.num_cols(), rhs.num_cols());
+        for (r, row) in out.chunks_mut(cols).enumerate() {
+            let a = &self.data.data[(first + r) * n..(first + r + 1) * n];
+            for (k, &aik) in a.iter().enumerate() {
+                let b = &rhs.data.data[k * cols..(k + 1) * cols];
+                for (o, &bkj) in row.iter_mut().zip(b.iter()) {
+                    *o = *o + aik * bkj;
+                }
+            }
+        }
+    }
+
+    /// Product of the matrix and the column vector `v`.
+    pub fn mul_vec(&self, v: &Vector<T>) -> Result<Vector<T>, String> {
+        if self.num_cols() != v.len() {
+            return Err("Matrix and vector are not conformable for multiplication.".to_string());
+        }
+        let rows = (0..self.num_rows()).map(|i| {
+            self[i].iter().zip(v.data.iter()).fold(T::zero(), |s, (&a, &x)| s + a * x)
+        });
+        Ok(vector::from_vec(rows.collect()))
     }
 
     /// Trace of the matrix.
@@ -233,6 +357,210 @@ impl<T: Number> Matrix<T> {
     }
 }
 
+impl<T: Real> Matrix<T> {
+    /// Estimates the eigenvalue of largest magnitude and its unit eigenvector
+    /// by power iteration, starting from the normalized all-ones vector. Each
+    /// iteration applies the matrix and takes the Rayleigh quotient as the
+    /// eigenvalue estimate, stopping after `iters` iterations or once the
+    /// estimate changes by less than `tol`. Convergence is slow when the two
+    /// largest eigenvalues are close in magnitude. Panics if the matrix is
+    /// not square.
+    pub fn power_iteration(&self, iters: usize, tol: T) -> (Vector<T>, T) {
+        if self.num_rows() != self.num_cols() {
+            panic!("The matrix should be a square matrix.")
+        }
+        let mut x = Vector::new(self.num_rows(), T::one()).normalized();
+        let mut lambda = T::zero();
+        for _ in 0..iters {
+            let y = self.mul_vec(&x).unwrap();
+            let next = x.dot(&y).unwrap();
+            x = y.normalized();
+            let change = (next - lambda).abs();
+            lambda = next;
+            if change < tol {
+                break;
+            }
+        }
+        (x, lambda)
+    }
+
+    /// QR factorization of the matrix, built by appending its rows one at a
+    /// time to an empty `QrFactorization`.
+    pub fn qr_factorize(&self) -> QrFactorization<T> {
+        let mut qr = QrFactorization::new(self.num_cols());
+        for i in 0..self.num_rows() {
+            qr.update_append_row(&vector::from_elems(&self[i])).unwrap();
+        }
+        qr
+    }
+
+    /// Determinant of the matrix, by Gaussian elimination with partial
+    /// pivoting. The determinant of a `0 x 0` matrix is 1, and it is 0 only
+    /// if the elimination meets an exactly zero pivot. The product of the
+    /// pivots easily overflows or underflows for large matrices even when
+    /// its logarithm is moderate; use `slogdet` for those. Panics if the
+    /// matrix is not square.
+    pub fn determinant(&self) -> T {
+        if self.num_rows() != self.num_cols() {
+            panic!("The matrix should be a square matrix.")
+        }
+        match self.lu() {
+            Some((lu, _, sign)) => (0..lu.num_rows()).fold(sign, |d, k| d * lu.get(k, k)),
+            None => T::zero(),
+        }
+    }
+
+    /// Sign and natural logarithm of the absolute value of the determinant,
+    /// as `numpy.linalg.slogdet`, so that the determinant is
+    /// `sign * logdet.exp()`. The logarithm is a sum over the pivots of the
+    /// elimination, so it neither overflows nor underflows. An exactly zero
+    /// pivot gives `(0, -inf)`. Fails if the matrix is not square.
+    pub fn slogdet(&self) -> Result<(T, T), String> {
+        if self.num_rows() != self.num_cols() {
+            return Err("The matrix should be a square matrix.".to_string());
+        }
+        Ok(match self.lu() {
+            Some((lu, _, sign)) => (0..lu.num_rows()).fold((sign, T::zero()), |(s, l), k| {
+                let p = lu.get(k, k);
+                (if p < T::zero() { -s } else { s }, l + p.abs().ln())
+            }),
+            None => (T::zero(), T::neg_infinity()),
+        })
+    }
+
+    /// Solves `self * x = b` by Gaussian elimination with partial pivoting.
+    /// Fails if the matrix is not square, does not match `b` or is singular
+    /// to working precision, that is, if a pivot is at most `n * epsilon`
+    /// times the largest absolute entry of the row it came from. The test is
+    /// relative to each row, so scaling a row does not change the outcome.
+    pub fn solve(&self, b: &Vector<T>) -> Result<Vector<T>, String> {
+        let n = self.num_rows();
+        if n != self.num_cols() {
+            return Err("The matrix should be a square matrix.".to_string());
+        }
+        if n != b.len() {
+            return Err("Matrix and vector are not conformable for solving.".to_string());
+        }
+        let (lu, perm, _) = match self.lu() {
+            Some(f) => f,
+            None => return Err("The matrix is singular.".to_string()),
+        };
+        let tol = T::epsilon() * T::from(n).unwrap();
+        for (k, &row) in perm.iter().enumerate() {
+            let scale = self[row].iter().fold(T::zero(), |m, x| m.max(x.abs()));
+            if lu.get(k, k).abs() <= tol * scale {
+                return Err("The matrix is singular.".to_string());
+            }
+        }
+        let mut x = vector::from_vec(perm.iter().map(|&i| b[i]).collect());
+        for k in 0..n {
+            for i in k + 1..n {
+                x[i] = x[i] - lu.get(i, k) * x[k];
+            }
+        }
+        for k in (0..n).rev() {
+            let mut sum = x[k];
+            for j in k + 1..n {
+                sum = sum - lu.get(k, j) * x[j];
+            }
+            x[k] = sum / lu.get(k, k);
+        }
+        Ok(x)
+    }
+
+    /// LU factorization of a square matrix with partial pivoting, shared by
+    /// `determinant`, `slogdet` and `solve`. Returns the factors packed in
+    /// one matrix (`U` on and above the diagonal, the multipliers of the
+    /// unit lower triangular `L` below it), the row permutation, where
+    /// `perm[k]` is the row of `self` that ended up in row `k`, and its
+    /// sign. Returns `None` if a pivot is exactly zero.
+    fn lu(&self) -> Option<(Matrix<T>, Vec<usize>, T)> {
+        let n = self.num_rows();
+        let mut a = self.clone();
+        let mut perm: Vec<usize> = (0..n).collect();
+        let mut sign = T::one();
+        for k in 0..n {
+            let p = (k..n).fold(k, |p, i| if a.get(i, k).abs() > a.get(p, k).abs() { i } else { p });
+            if a.get(p, k) == T::zero() {
+                return None;
+            }
+            if p != k {
+                for j in 0..n {
+                    let (x, y) = (a.get(k, j), a.get(p, j));
+                    a.set(k, j, y);
+                    a.set(p, j, x);
+                }
+                perm.swap(k, p);
+                sign = -sign;
+            }
+            let pivot = a.get(k, k);
+            for i in k + 1..n {
+                let f = a.get(i, k) / pivot;
+                a.set(i, k, f);
+                for j in k + 1..n {
+                    let x = a.get(i, j) - f * a.get(k, j);
+                    a.set(i, j, x);
+                }
+            }
+        }
+        Some((a, perm, sign))
+    }
+
+    /// Solves `self * x = b` for a symmetric positive definite matrix with
+    /// the conjugate gradient method, starting from `x = 0`. In exact
+    /// arithmetic this takes at most `n` iterations for an `n x n` matrix;
+    /// it stops as soon as the norm of the residual `b - self * x` is at
+    /// most `tol`. Fails if that has not happened after `max_iter`
+    /// iterations, or if the matrix turns out not to be positive definite.
+    ///
+    /// This is a shorthand for `linalg::conjugate_gradient` without a
+    /// preconditioner, and its errors are passed through unchanged. Note
+    /// that `tol` here is absolute, whereas `CgOptions::tol` is relative to
+    /// the norm of `b`.
+    pub fn conjugate_gradient(&self, b: &Vector<T>, max_iter: usize, tol: T) -> Result<Vector<T>, String> {
+        if self.num_rows() != self.num_cols() {
+            return Err("The matrix should be a square matrix.".to_string());
+        }
+        if self.num_rows() != b.len() {
+            return Err("Matrix and vector are not conformable for solving.".to_string());
+        }
+        let norm = b.norm();
+        let opts = CgOptions {
+            tol: if norm == T::zero() { tol } else { tol / norm },
+            max_iter,
+            preconditioner: None,
+        };
+        linalg::conjugate_gradient(self, b, opts).map(|res| res.solution)
+    }
+}
+
+impl<T: Number + ToPrimitive> Matrix<T> {
+    /// Renders the matrix as text according to `opts`, one row per line with
+    /// the columns right-aligned, e.g. `[ 1.0, 2.0]` over `[10.0, 3.0]`.
+    pub fn to_string_with(&self, opts: FormatOptions) -> String {
+        let rows = self.num_rows();
+        let shown: Vec<usize> = match opts.elided(rows) {
+            Some((head, tail)) => (0..head).chain(rows - tail..rows).collect(),
+            None => (0..rows).collect(),
+        };
+        let width = shown.iter()
+            .flat_map(|&i| self[i].iter())
+            .map(|&x| opts.format_value(x).len())
+            .fold(if opts.elided(self.num_cols()).is_some() { 3 } else { 0 },
+                  |w, l| w.max(l));
+        let label = format!("{}", rows.saturating_sub(1)).len();
+        let line = |i: usize| {
+            let row = opts.render_row(&self[i], width, false);
+            if opts.show_indices { format!("{:>w$}: {}", i, row, w = label) } else { row }
+        };
+        let mut lines: Vec<String> = shown.iter().map(|&i| line(i)).collect();
+        if let Some((head, _)) = opts.elided(rows) {
+            lines.insert(head, "...".to_string());
+        }
+        lines.join("\n")
+    }
+}
+
 /// Creates a `Matrix` with dimensions `rows x cols` from the elements of the
 /// slice `elems`.
 pub fn from_elems<T: Number>(rows: usize, cols: usize, elems: &[T]) -> Matrix<T> {
@@ -243,6 +571,15 @@ pub fn from_elems<T: Number>(rows: usize, cols: usize, elems: &[T]) -> Matrix<T>
     }
 }
 
+/// Creates a `Matrix` with dimensions `rows x cols` which takes ownership of
+/// the row major elements in `data` without copying them.
+pub fn from_vec<T: Number>(rows: usize, cols: usize, data: Vec<T>) -> Matrix<T> {
+    if data.len() != rows * cols {
+        panic!("Total number of elements in matrix should be same.")
+    }
+    Matrix::<T> { rows, cols, data: vector::from_vec(data) }
+}
+
 /// Creates an identity matrix of dimension `n x n`.
 pub fn identity<T: Number>(n: usize) -> Matrix<T> {
     let mut m = Matrix::<T>::new(n, n, T::zero());
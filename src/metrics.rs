@@ -0,0 +1,128 @@
+//! Distance metrics between the rows of matrices
+//!
+//! # Examples
+//! ```
+//! use numrs::matrix;
+//! use numrs::metrics;
+//! use numrs::metrics::Metric;
+//!
+//! let a = matrix::from_elems(2, 2, &[0.0, 0.0, 1.0, 1.0]);
+//! let b = matrix::from_elems(1, 2, &[3.0, 4.0]);
+//!
+//! // 2x1 matrix of distances between every row of `a` and every row of `b`
+//! let d = metrics::cdist(&a, &b, Metric::Euclidean).unwrap();
+//! assert_eq!(d.get(0, 0), 5.0);
+//! ```
+
+use common::Real;
+use matrix::Matrix;
+use vector::{Vector, VectorOps};
+
+/// Distance metric used by `cdist` and `pdist`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Metric {
+    /// `sqrt(sum((a - b)^2))`
+    Euclidean,
+    /// `sum((a - b)^2)`
+    SquaredEuclidean,
+    /// `sum(|a - b|)`
+    Manhattan,
+    /// `1 - a.b / (|a| * |b|)`, which is NaN when either row is all zeros.
+    Cosine,
+}
+
+/// Computes the `m x n` matrix of distances between every row of the `m x k`
+/// matrix `a` and every row of the `n x k` matrix `b`.
+///
+/// The Euclidean distances are computed as `|a|^2 + |b|^2 - 2a.b` on top of
+/// the matrix product for speed. Rounding can make this slightly negative for
+/// (nearly) identical rows, so the squared distances are clamped to 0.
+pub fn cdist<T: Real>(a: &Matrix<T>, b: &Matrix<T>, metric: Metric) -> Result<Matrix<T>, String> {
+    if a.num_cols() != b.num_cols() {
+        return Err("Matrices are not conformable for distance computation.".to_string());
+    }
+    let (m, n) = (a.num_rows(), b.num_rows());
+    let mut d = Matrix::new(m, n, T::zero());
+    match metric {
+        Metric::Manhattan => {
+            for i in 0..m {
+                for j in 0..n {
+                    let mut sum = T::zero();
+                    for k in 0..a.num_cols() {
+                        sum = sum + (a.get(i, k) - b.get(j, k)).abs();
+                    }
+                    d.set(i, j, sum);
+                }
+            }
+        }
+        _ => {
+            let (na, nb) = (row_sq_norms(a), row_sq_norms(b));
+            let mut bt = b.clone();
+            bt.transpose();
+            let ab = a.clone() * bt;
+            for (i, &na_i) in na.iter().enumerate() {
+                for (j, &nb_j) in nb.iter().enumerate() {
+                    let dot = ab.get(i, j);
+                    let dist = match metric {
+                        Metric::Cosine => T::one() - dot / (na_i.sqrt() * nb_j.sqrt()),
+                        _ => {
+                            let sq = (na_i + nb_j - (dot + dot)).max(T::zero());
+                            if metric == Metric::Euclidean { sq.sqrt() } else { sq }
+                        }
+                    };
+                    d.set(i, j, dist);
+                }
+            }
+        }
+    }
+    Ok(d)
+}
+
+/// Computes the condensed distances between the rows of `a`, i.e. the upper
+/// triangle of `cdist(a, a)` in row major order, with `m * (m - 1) / 2`
+/// elements for `m` rows.
+pub fn pdist<T: Real>(a: &Matrix<T>, metric: Metric) -> Vector<T> {
+    let d = cdist(a, a, metric).unwrap();
+    let m = a.num_rows();
+    let mut v = Vector::<T> { data: Vec::with_capacity(m * m.saturating_sub(1) / 2) };
+    for i in 0..m {
+        for j in i + 1..m {
+            v.data.push(d.get(i, j));
+        }
+    }
+    v
+}
+
+/// Computes the symmetric `n x n` matrix of cosine similarities between the
+/// `n` vectors, `a.b / (|a| * |b|)`. Every norm is computed once and only the
+/// upper triangle is computed, then mirrored. Entries involving a zero
+/// vector are NaN. Fails unless all the vectors have the same length.
+pub fn cosine_similarity_matrix<T: Real>(vectors: &[Vector<T>]) -> Result<Matrix<T>, String> {
+    let n = vectors.len();
+    if vectors.iter().any(|v| v.len() != vectors[0].len()) {
+        return Err("Vectors should all have the same length.".to_string());
+    }
+    let norms: Vec<T> = vectors.iter().map(|v| v.norm()).collect();
+    let mut s = Matrix::new(n, n, T::zero());
+    for i in 0..n {
+        for j in i..n {
+            let sim = vectors[i].dot(&vectors[j])? / (norms[i] * norms[j]);
+            s.set(i, j, sim);
+            s.set(j, i, sim);
+        }
+    }
+    Ok(s)
+}
+
+/// Squared Euclidean norm of every row of the matrix.
+fn row_sq_norms<T: Real>(a: &Matrix<T>) -> Vec<T> {
+    let mut norms = Vec::with_capacity(a.num_rows());
+    for i in 0..a.num_rows() {
+        let mut sum = T::zero();
+        for k in 0..a.num_cols() {
+            sum = sum + a.get(i, k) * a.get(i, k);
+        }
+        norms.push(sum);
+    }
+    norms
+}
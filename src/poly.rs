@@ -0,0 +1,172 @@
+//! Polynomials
+//!
+//! Coefficients are given from the highest power down, as in NumPy, so
+//! `[1, -3, 2]` is `x^2 - 3x + 2`.
+//!
+//! # Examples
+//! ```
+//! use numrs::poly;
+//! use numrs::vector;
+//!
+//! let roots = poly::roots(&vector::from_elems(&[1.0f64, -3.0, 2.0])).unwrap();
+//! assert!((roots[0].0 - 1.0).abs() < 1e-12 && (roots[1].0 - 2.0).abs() < 1e-12);
+//!
+//! let x = vector::from_elems(&[0.0f64, 1.0, 2.0, 3.0]);
+//! let fit = poly::polyfit(&x, &x.polyval(&vector::from_elems(&[2.0, 1.0])), 1).unwrap();
+//! assert!((fit[0] - 2.0).abs() < 1e-12 && (fit[1] - 1.0).abs() < 1e-12);
+//! ```
+
+extern crate num;
+
+use std::cmp::Ordering;
+use self::num::complex::Complex;
+use self::num::traits::One;
+use common::Real;
+use matrix::Matrix;
+use vector::Vector;
+
+/// Maximum number of Durand-Kerner sweeps over all the roots.
+const MAX_SWEEPS: usize = 500;
+
+/// Complex roots of the polynomial with coefficients `coeffs`, as
+/// `(re, im)` pairs sorted by real and then imaginary part. Leading zero
+/// coefficients are ignored; a polynomial of degree 0 has no roots and is an
+/// error.
+///
+/// The roots are found by Durand-Kerner iteration. Simple roots are accurate
+/// to about machine precision relative to their size, but a root of
+/// multiplicity `m` only to about the `m`th root of it, and roots of
+/// high-degree polynomials can be very sensitive to the coefficients.
+///
+/// If the estimates still move after `MAX_SWEEPS` sweeps, which happens for
+/// multiple roots, they are accepted only if the polynomial is within
+/// rounding error of zero at every one of them; otherwise, or if the
+/// iteration overflows, root finding fails.
+pub fn roots<T: Real>(coeffs: &Vector<T>) -> Result<Vec<(T, T)>, String> {
+    if coeffs.data.iter().any(|c| !c.is_finite()) {
+        return Err("Polynomial coefficients should be finite.".to_string());
+    }
+    let lead = coeffs.data.iter().position(|&c| c != T::zero()).unwrap_or(coeffs.len());
+    let c = &coeffs.data[lead..];
+    if c.len() < 2 {
+        return Err("Polynomial should have degree at least 1.".to_string());
+    }
+    // Trailing zeros are exact roots at zero.
+    let zeros = c.iter().rev().take_while(|&&x| x == T::zero()).count();
+    let c = &c[..c.len() - zeros];
+    let monic: Vec<Complex<T>> = c[1..].iter().map(|&x| Complex::new(x / c[0], T::zero())).collect();
+
+    // Start on a circle enclosing every root, at angles offset from the real
+    // axis so that no estimate is real or the conjugate of another.
+    let n = monic.len();
+    let radius = T::one() + monic.iter().fold(T::zero(), |m, a| m.max(a.norm()));
+    let step = T::from(2.0 * ::std::f64::consts::PI / n.max(1) as f64).unwrap();
+    let offset = T::from(0.4).unwrap();
+    let mut z: Vec<Complex<T>> = (0..n)
+        .map(|k| Complex::from_polar(&radius, &(step * T::from(k).unwrap() + offset)))
+        .collect();
+    let mut converged = false;
+    for _ in 0..MAX_SWEEPS {
+        let mut change = T::zero();
+        for i in 0..n {
+            let mut den = Complex::one();
+            for j in (0..n).filter(|&j| j != i) {
+                den = den * (z[i] - z[j]);
+            }
+            let delta = horner(&monic, z[i]) / den;
+            z[i] = z[i] - delta;
+            change = change.max(delta.norm() / z[i].norm().max(T::one()));
+        }
+        if change <= T::epsilon() {
+            converged = true;
+            break;
+        }
+    }
+    let finite = z.iter().all(|r| r.re.is_finite() && r.im.is_finite());
+    if !finite || (!converged && !z.iter().all(|&r| negligible_residual(&monic, r))) {
+        return Err("Root finding did not converge.".to_string());
+    }
+
+    let mut out: Vec<(T, T)> = z.iter().map(|r| (r.re, r.im)).collect();
+    out.extend((0..zeros).map(|_| (T::zero(), T::zero())));
+    out.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    Ok(out)
+}
+
+/// Coefficients, highest power first as for `Vector::polyval`, of the
+/// polynomial of the given `degree` that best fits the points `(x, y)` in
+/// the least-squares sense. The points are first centered and scaled to
+/// `[-1, 1]`, the normal equations `V^T V c = V^T y` of the Vandermonde
+/// matrix `V` of the scaled points are solved, and the result is expanded
+/// back into powers of `x`. Their condition number still grows quickly with
+/// the degree, so for degrees beyond about 10 expect to lose accuracy.
+/// Fails if the lengths differ or there are fewer distinct points than
+/// coefficients.
+pub fn polyfit<T: Real>(x: &Vector<T>, y: &Vector<T>, degree: usize) -> Result<Vector<T>, String> {
+    if x.len() != y.len() {
+        return Err("Sample points and values should have the same length.".to_string());
+    }
+    let n = degree + 1;
+    if x.len() < n {
+        return Err(format!("At least {} points are needed for a degree {} fit.", n, degree));
+    }
+    let mut sorted = x.data.clone();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or_else(|| a.is_nan().cmp(&b.is_nan())));
+    sorted.dedup();
+    if sorted.len() < n {
+        return Err("Sample points do not determine the fit.".to_string());
+    }
+
+    let center = x.data.iter().fold(T::zero(), |s, &xi| s + xi) / T::from(x.len()).unwrap();
+    let mut scale = x.data.iter().fold(T::zero(), |m, &xi| m.max((xi - center).abs()));
+    if scale == T::zero() {
+        scale = T::one();
+    }
+    // Column j of V holds t^(degree - j) for t = (x - center) / scale, so
+    // sums of t^(2 degree - i - j) fill the normal matrix.
+    let mut powers = vec![T::zero(); 2 * degree + 1];
+    let mut rhs = Vector::new(n, T::zero());
+    for (&xi, &yi) in x.data.iter().zip(y.data.iter()) {
+        let t = (xi - center) / scale;
+        let mut p = T::one();
+        for k in 0..2 * degree + 1 {
+            powers[k] = powers[k] + p;
+            if k <= degree {
+                rhs[degree - k] = rhs[degree - k] + p * yi;
+            }
+            p = p * t;
+        }
+    }
+    let mut normal = Matrix::new(n, n, T::zero());
+    for i in 0..n {
+        for j in 0..n {
+            normal.set(i, j, powers[2 * degree - i - j]);
+        }
+    }
+    let scaled = normal.solve(&rhs)?;
+
+    // Horner's rule on polynomials: c <- c * (x - center) / scale + q_k.
+    let mut coeffs = Vector::new(n, T::zero());
+    for (k, &q) in scaled.data.iter().enumerate() {
+        for i in (1..k + 1).rev() {
+            coeffs[i] = (coeffs[i] - coeffs[i - 1] * center) / scale;
+        }
+        coeffs[0] = coeffs[0] / scale;
+        coeffs[k] = coeffs[k] + q;
+    }
+    Ok(coeffs)
+}
+
+/// Whether the monic polynomial is zero at `x` up to the rounding error of
+/// evaluating it there with Horner's rule.
+fn negligible_residual<T: Real>(monic: &[Complex<T>], x: Complex<T>) -> bool {
+    let bound = monic.iter().fold(T::one(), |b, a| b * x.norm() + a.norm());
+    let n = T::from(monic.len() + 1).unwrap();
+    horner(monic, x).norm() <= T::from(4.0).unwrap() * n * T::epsilon() * bound
+}
+
+/// Value at `x` of the monic polynomial whose coefficients after the
+/// leading 1 are `monic`.
+fn horner<T: Real>(monic: &[Complex<T>], x: Complex<T>) -> Complex<T> {
+    monic.iter().fold(Complex::one(), |p, &a| p * x + a)
+}
@@ -0,0 +1,114 @@
+//! Scalar reference implementations
+//!
+//! Each function here is the plainest possible loop over slices for one of
+//! the public vector operations. They are the ground truth that the optimised
+//! kernels are tested against and are not meant to be fast.
+//!
+//! Binary operations panic if the slices have different lengths.
+//!
+//! # Examples
+//! ```
+//! use numrs::reference;
+//!
+//! assert_eq!(reference::add(&[1.0, 2.0], &[3.0, 4.0]), vec![4.0, 6.0]);
+//! assert_eq!(reference::dot(&[1.0, 2.0], &[3.0, 4.0]), 11.0);
+//! ```
+
+use std::ops::Neg;
+use common::{Number, Real};
+
+fn zip_with<T: Number, F: Fn(T, T) -> T>(a: &[T], b: &[T], f: F) -> Vec<T> {
+    assert_eq!(a.len(), b.len(), "Slices should have the same length.");
+    let mut out = Vec::with_capacity(a.len());
+    for (&x, &y) in a.iter().zip(b.iter()) {
+        out.push(f(x, y));
+    }
+    out
+}
+
+/// Element-wise sum of `a` and `b`.
+pub fn add<T: Number>(a: &[T], b: &[T]) -> Vec<T> {
+    zip_with(a, b, |x, y| x + y)
+}
+
+/// Element-wise difference of `a` and `b`.
+pub fn sub<T: Number>(a: &[T], b: &[T]) -> Vec<T> {
+    zip_with(a, b, |x, y| x - y)
+}
+
+/// Element-wise product of `a` and `b`.
+pub fn mul<T: Number>(a: &[T], b: &[T]) -> Vec<T> {
+    zip_with(a, b, |x, y| x * y)
+}
+
+/// Element-wise quotient of `a` and `b`.
+pub fn div<T: Number>(a: &[T], b: &[T]) -> Vec<T> {
+    zip_with(a, b, |x, y| x / y)
+}
+
+/// Negation of every element of `a`. Unlike `0 - x` this flips the sign of
+/// zero too.
+pub fn neg<T: Number + Neg<Output = T>>(a: &[T]) -> Vec<T> {
+    let mut out = Vec::with_capacity(a.len());
+    for &x in a {
+        out.push(-x);
+    }
+    out
+}
+
+/// Every element of `a` multiplied by `k`.
+pub fn scale<T: Number>(a: &[T], k: T) -> Vec<T> {
+    let mut out = Vec::with_capacity(a.len());
+    for &x in a {
+        out.push(x * k);
+    }
+    out
+}
+
+/// Square of every element of `a`.
+pub fn square<T: Number>(a: &[T]) -> Vec<T> {
+    mul(a, a)
+}
+
+/// Running sums of `a`.
+pub fn cumsum<T: Number>(a: &[T]) -> Vec<T> {
+    let mut out = Vec::with_capacity(a.len());
+    let mut sum = T::zero();
+    for &x in a {
+        sum = sum + x;
+        out.push(sum);
+    }
+    out
+}
+
+/// Whether `a` and `b` have the same length and equal elements.
+pub fn eq<T: Number>(a: &[T], b: &[T]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    for (x, y) in a.iter().zip(b.iter()) {
+        if x != y {
+            return false;
+        }
+    }
+    true
+}
+
+/// Sum of the elements of `a`, accumulated from left to right.
+pub fn sum<T: Number>(a: &[T]) -> T {
+    let mut sum = T::zero();
+    for &x in a {
+        sum = sum + x;
+    }
+    sum
+}
+
+/// Dot product of `a` and `b`, accumulated from left to right.
+pub fn dot<T: Number>(a: &[T], b: &[T]) -> T {
+    sum(&mul(a, b))
+}
+
+/// Euclidean norm of `a`.
+pub fn norm<T: Real>(a: &[T]) -> T {
+    dot(a, a).sqrt()
+}
@@ -0,0 +1,93 @@
+//! A copy-on-write shared Vector
+//!
+//! `SharedVector` keeps its elements behind an `Arc`, so cloning it is O(1)
+//! and the elements are only copied the first time a clone is mutated while
+//! it is still shared. Read access to all the `Vector` methods goes through
+//! `Deref`.
+//!
+//! # Examples
+//! ```
+//! use numrs::vector;
+//! use numrs::shared::SharedVector;
+//!
+//! let base = SharedVector::from(vector::from_elems(&[1.0, 2.0, 3.0]));
+//! let mut copy = base.clone(); // no elements are copied here
+//!
+//! copy[0] = 10.0; // `copy` gets its own elements now
+//! assert_eq!(base[0], 1.0);
+//! assert_eq!(copy[0], 10.0);
+//! ```
+
+use std::ops::{Deref, Index, IndexMut};
+use std::sync::Arc;
+use common::Number;
+use vector::Vector;
+
+/// Copy-on-write Vector with O(1) clones
+pub struct SharedVector<T: Number> {
+    inner: Arc<Vector<T>>,
+}
+
+impl<T: Number> Clone for SharedVector<T> {
+    fn clone(&self) -> SharedVector<T> {
+        SharedVector::<T> { inner: self.inner.clone() }
+    }
+}
+
+impl<T: Number> From<Vector<T>> for SharedVector<T> {
+    fn from(v: Vector<T>) -> SharedVector<T> {
+        SharedVector::<T> { inner: Arc::new(v) }
+    }
+}
+
+impl<T: Number> Deref for SharedVector<T> {
+    type Target = Vector<T>;
+
+    #[inline]
+    fn deref(&self) -> &Vector<T> {
+        &self.inner
+    }
+}
+
+impl<T: Number> Index<usize> for SharedVector<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: usize) -> &T {
+        &self.inner[index]
+    }
+}
+
+impl<T: Number> IndexMut<usize> for SharedVector<T> {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.make_mut()[index]
+    }
+}
+
+impl<T: Number> PartialEq for SharedVector<T> {
+    fn eq(&self, other: &SharedVector<T>) -> bool {
+        *self.inner == *other.inner
+    }
+}
+
+impl<T: Number> SharedVector<T> {
+    /// Returns a mutable reference to the elements, copying them first if
+    /// they are shared with another `SharedVector`.
+    pub fn make_mut(&mut self) -> &mut Vector<T> {
+        Arc::make_mut(&mut self.inner)
+    }
+
+    /// Returns the number of `SharedVector`s sharing these elements.
+    pub fn strong_count(this: &SharedVector<T>) -> usize {
+        Arc::strong_count(&this.inner)
+    }
+
+    /// Unwraps the `Vector`, copying the elements only if they are shared.
+    pub fn into_vector(self) -> Vector<T> {
+        match Arc::try_unwrap(self.inner) {
+            Ok(v) => v,
+            Err(shared) => (*shared).clone(),
+        }
+    }
+}
@@ -0,0 +1,140 @@
+//! Special functions
+//!
+//! Scalar implementations of the error function and the logarithm of the
+//! gamma function, which `Vector::erf`, `Vector::erfc` and `Vector::lgamma`
+//! apply elementwise. They compute in `f64` whatever the element type and
+//! are accurate to about `1e-13` relative over the range where the result
+//! is a normal `f64`.
+//!
+//! # Examples
+//! ```
+//! use numrs::special;
+//!
+//! assert!((special::erf(1.0f64) - 0.8427007929497149).abs() < 1e-15);
+//! assert!((special::lgamma(5.0) - 24.0f64.ln()).abs() < 1e-13);
+//! ```
+
+use std::f64::consts::PI;
+use common::Real;
+
+/// Relative size of the last term kept in the series and continued fraction.
+const EPSILON: f64 = 1e-16;
+
+/// Below this `erf` is summed as a series, above it `1 - erfc`.
+const ERF_SERIES_LIMIT: f64 = 3.0;
+
+/// Below this `erfc` is `1 - erf`, above it a continued fraction, which
+/// converges more slowly the smaller the argument.
+const ERFC_SERIES_LIMIT: f64 = 1.0;
+
+/// Error function `2 / sqrt(pi) * integral of exp(-t^2) from 0 to x`.
+pub fn erf<T: Real>(x: T) -> T {
+    let x = x.to_f64().unwrap();
+    let y = if x.is_nan() {
+        x
+    } else if x.abs() < ERF_SERIES_LIMIT {
+        erf_series(x)
+    } else {
+        x.signum() * (1.0 - erfc_fraction(x.abs()))
+    };
+    T::from(y).unwrap()
+}
+
+/// Complementary error function `1 - erf(x)`, computed without cancellation
+/// for large `x` where it underflows gracefully to 0.
+pub fn erfc<T: Real>(x: T) -> T {
+    let x = x.to_f64().unwrap();
+    let y = if x.is_nan() {
+        x
+    } else if x.abs() < ERFC_SERIES_LIMIT {
+        1.0 - erf_series(x)
+    } else if x > 0.0 {
+        erfc_fraction(x)
+    } else {
+        2.0 - erfc_fraction(-x)
+    };
+    T::from(y).unwrap()
+}
+
+/// Natural logarithm of the absolute value of the gamma function, using the
+/// Lanczos approximation and the reflection formula for `x < 0.5`. Positive
+/// infinity at the poles `0, -1, -2, ...`. Near the zeros at 1 and 2 the
+/// error is absolute rather than relative.
+pub fn lgamma<T: Real>(x: T) -> T {
+    T::from(lgamma_f64(x.to_f64().unwrap())).unwrap()
+}
+
+/// `erf(x) = 2 / sqrt(pi) * exp(-x^2) * sum(2^n x^(2n+1) / (1 * 3 * ... * (2n+1)))`,
+/// whose terms are all positive.
+fn erf_series(x: f64) -> f64 {
+    let x2 = x * x;
+    let (mut term, mut sum, mut n) = (x, x, 0.0);
+    while term.abs() > EPSILON * sum.abs() {
+        n += 1.0;
+        term *= 2.0 * x2 / (2.0 * n + 1.0);
+        sum += term;
+    }
+    2.0 / PI.sqrt() * (-x2).exp() * sum
+}
+
+/// `erfc(x) = exp(-x^2) / sqrt(pi) / (x + (1/2) / (x + 1 / (x + (3/2) / (x + ...))))`
+/// for `x > 0`, evaluated with Lentz's method.
+fn erfc_fraction(x: f64) -> f64 {
+    if x.is_infinite() {
+        return 0.0;
+    }
+    let tiny = 1e-300;
+    let (mut f, mut c, mut d) = (x, x, 0.0);
+    let mut n = 0.0;
+    loop {
+        n += 1.0;
+        let a = n / 2.0;
+        d = x + a * d;
+        c = x + a / c;
+        if d == 0.0 {
+            d = tiny;
+        }
+        d = 1.0 / d;
+        let delta = c * d;
+        f *= delta;
+        if (delta - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+    (-x * x).exp() / (PI.sqrt() * f)
+}
+
+/// Lanczos coefficients for `g = 7`.
+const LANCZOS_G: f64 = 7.0;
+const LANCZOS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+];
+
+fn lgamma_f64(x: f64) -> f64 {
+    if x.is_nan() || x == f64::INFINITY {
+        return x;
+    }
+    if x < 0.5 {
+        let s = (PI * x).sin();
+        if x == x.floor() || s == 0.0 {
+            return f64::INFINITY;
+        }
+        // Reflection: gamma(x) * gamma(1 - x) = pi / sin(pi x).
+        return (PI / s.abs()).ln() - lgamma_f64(1.0 - x);
+    }
+    let x = x - 1.0;
+    let mut a = LANCZOS[0];
+    for (i, &c) in LANCZOS.iter().enumerate().skip(1) {
+        a += c / (x + i as f64);
+    }
+    let t = x + LANCZOS_G + 0.5;
+    0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + a.ln()
+}
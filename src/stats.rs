@@ -0,0 +1,382 @@
+//! Statistical helpers
+//!
+//! # Examples
+//! ```
+//! use numrs::vector;
+//! use numrs::stats::Ecdf;
+//!
+//! let v = vector::from_elems(&[3.0, 1.0, 4.0, 1.0, 5.0]);
+//! let ecdf = Ecdf::new(&v);
+//! assert_eq!(ecdf.eval(1.0), 0.4);
+//! assert_eq!(ecdf.percentile_rank(4.0), 80.0);
+//! ```
+//!
+//! `RunningStats` summarizes a sample one element at a time:
+//!
+//! ```
+//! use numrs::stats::RunningStats;
+//!
+//! let mut stats = RunningStats::new();
+//! stats.push_slice(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+//! assert_eq!(stats.mean(), 5.0);
+//! assert_eq!(stats.var(), 32.0 / 7.0);
+//! ```
+//!
+//! and `QuantileSketch` estimates its quantiles in bounded memory:
+//!
+//! ```
+//! use numrs::stats::QuantileSketch;
+//!
+//! let mut sketch = QuantileSketch::new(0.01);
+//! for i in 0..10000 {
+//!     sketch.push(i as f64);
+//! }
+//! assert!((sketch.quantile(0.5) - 5000.0).abs() <= 100.0);
+//! ```
+
+use common::Real;
+use vector;
+use vector::{Vector, VectorOps};
+use view::VectorView;
+
+/// Number of elements `RunningStats::push_vector` summarizes at a time.
+const PUSH_CHUNK: usize = 4096;
+
+/// Empirical cumulative distribution function of a sample
+///
+/// Building it sorts a copy of the sample in O(n log n); every query is then
+/// a binary search in O(log n).
+pub struct Ecdf<T: Real> {
+    sorted: Vec<T>,
+}
+
+impl<T: Real> Ecdf<T> {
+    /// Creates the ECDF of the elements of `v`.
+    pub fn new(v: &Vector<T>) -> Ecdf<T> {
+        Ecdf { sorted: v.take(&v.argsort()).unwrap().data }
+    }
+
+    /// Fraction of the elements which are less than or equal to `value`. NaN
+    /// elements are never counted, and an empty sample gives NaN.
+    pub fn eval(&self, value: T) -> T {
+        let count = self.sorted.partition_point(|&x| x <= value);
+        T::from(count).unwrap() / T::from(self.sorted.len()).unwrap()
+    }
+
+    /// `eval` as a percentage between 0 and 100.
+    pub fn percentile_rank(&self, value: T) -> T {
+        self.eval(value) * T::from(100).unwrap()
+    }
+}
+
+/// Welford's online mean and variance, which `RunningStats` implements along
+/// with the other running statistics.
+pub type WelfordAccumulator<T> = RunningStats<T>;
+
+/// Running count, sum, mean, variance, minimum and maximum of a sample
+///
+/// The mean and variance are updated with Welford's algorithm, which stays
+/// accurate over billions of updates and for samples whose mean is large
+/// compared to their spread. NaNs are ignored by `min` and `max` but
+/// propagate to the other statistics.
+#[derive(Clone, Debug)]
+pub struct RunningStats<T: Real> {
+    count: usize,
+    sum: T,
+    min: T,
+    max: T,
+    mean: T,
+    m2: T,
+}
+
+impl<T: Real> Default for RunningStats<T> {
+    fn default() -> RunningStats<T> {
+        RunningStats::new()
+    }
+}
+
+impl<T: Real> RunningStats<T> {
+    /// Creates the statistics of an empty sample.
+    pub fn new() -> RunningStats<T> {
+        RunningStats {
+            count: 0,
+            sum: T::zero(),
+            min: T::nan(),
+            max: T::nan(),
+            mean: T::zero(),
+            m2: T::zero(),
+        }
+    }
+
+    /// Adds a single element.
+    pub fn push(&mut self, x: T) {
+        self.count += 1;
+        self.sum = self.sum + x;
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+        let delta = x - self.mean;
+        self.mean = self.mean + delta / T::from(self.count).unwrap();
+        self.m2 = self.m2 + delta * (x - self.mean);
+    }
+
+    /// Adds every element of `xs` in turn.
+    pub fn push_slice(&mut self, xs: &[T]) {
+        for &x in xs {
+            self.push(x);
+        }
+    }
+
+    /// Adds every element of `v`. Rather than updating per element, this
+    /// summarizes a chunk at a time with the vector reductions and combines
+    /// the chunks, which is faster for long vectors.
+    pub fn push_vector(&mut self, v: &Vector<T>) {
+        for chunk in v.data.chunks(PUSH_CHUNK) {
+            let sum = VectorView::from_slice(chunk).sum();
+            let mean = sum / T::from(chunk.len()).unwrap();
+            let mut part = RunningStats {
+                count: chunk.len(),
+                sum,
+                min: T::nan(),
+                max: T::nan(),
+                mean,
+                m2: T::zero(),
+            };
+            for &x in chunk {
+                part.min = part.min.min(x);
+                part.max = part.max.max(x);
+                part.m2 = part.m2 + (x - mean) * (x - mean);
+            }
+            *self = self.combine(&part);
+        }
+    }
+
+    /// Statistics of the two samples together, using the parallel variance
+    /// formula of Chan et al. This lets parts of a sample be summarized
+    /// separately, e.g. on different threads.
+    pub fn combine(&self, other: &RunningStats<T>) -> RunningStats<T> {
+        if other.count == 0 {
+            return self.clone();
+        }
+        if self.count == 0 {
+            return other.clone();
+        }
+        let (na, nb) = (T::from(self.count).unwrap(), T::from(other.count).unwrap());
+        let n = na + nb;
+        let delta = other.mean - self.mean;
+        RunningStats {
+            count: self.count + other.count,
+            sum: self.sum + other.sum,
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+            mean: self.mean + delta * nb / n,
+            m2: self.m2 + other.m2 + delta * delta * na * nb / n,
+        }
+    }
+
+    /// Number of elements seen.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Sum of the elements.
+    pub fn sum(&self) -> T {
+        self.sum
+    }
+
+    /// Arithmetic mean, NaN for an empty sample.
+    pub fn mean(&self) -> T {
+        if self.count == 0 { T::nan() } else { self.mean }
+    }
+
+    /// Sample variance with `count - 1` degrees of freedom, NaN for fewer
+    /// than two elements.
+    pub fn var(&self) -> T {
+        if self.count < 2 { T::nan() } else { self.m2 / T::from(self.count - 1).unwrap() }
+    }
+
+    /// Same as `var`, under the name used by `stream::ChunkStats`.
+    pub fn variance(&self) -> T {
+        self.var()
+    }
+
+    /// Sample standard deviation, the square root of `var`.
+    pub fn std(&self) -> T {
+        self.var().sqrt()
+    }
+
+    /// Smallest element, or `None` for an empty sample.
+    pub fn min(&self) -> Option<T> {
+        if self.count == 0 { None } else { Some(self.min) }
+    }
+
+    /// Largest element, or `None` for an empty sample.
+    pub fn max(&self) -> Option<T> {
+        if self.count == 0 { None } else { Some(self.max) }
+    }
+}
+
+/// A sampled value of a `QuantileSketch` with the rank information of the
+/// Greenwald-Khanna summary: `g` is the difference between its smallest
+/// possible rank and that of the previous entry, and `delta` the spread
+/// between its smallest and largest possible rank.
+#[derive(Clone, Copy, Debug)]
+struct GkEntry<T> {
+    value: T,
+    g: usize,
+    delta: usize,
+}
+
+/// Approximate quantiles of a stream in bounded memory
+///
+/// This is the Greenwald-Khanna summary: a quantile query for `q` over `n`
+/// elements returns an element whose rank is within `epsilon * n` of
+/// `q * n`, while only keeping `O(log(epsilon * n) / epsilon)` of them.
+/// Until there are about `1 / epsilon` elements no information is discarded
+/// and `quantile` gives the same result as `Vector::quantile`. NaNs are
+/// ignored.
+#[derive(Clone, Debug)]
+pub struct QuantileSketch<T: Real> {
+    epsilon: f64,
+    count: usize,
+    entries: Vec<GkEntry<T>>,
+}
+
+impl<T: Real> QuantileSketch<T> {
+    /// Creates an empty sketch with rank error `epsilon`. Panics unless
+    /// `0 < epsilon < 1`.
+    pub fn new(epsilon: f64) -> QuantileSketch<T> {
+        if !(epsilon > 0.0 && epsilon < 1.0) {
+            panic!("Sketch error should be between 0 and 1.");
+        }
+        QuantileSketch { epsilon, count: 0, entries: Vec::new() }
+    }
+
+    /// The rank error the sketch was created with.
+    pub fn epsilon(&self) -> f64 {
+        self.epsilon
+    }
+
+    /// Number of elements seen, not counting NaNs.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Adds a single element.
+    pub fn push(&mut self, x: T) {
+        if x.is_nan() {
+            return;
+        }
+        let i = self.entries.partition_point(|e| e.value <= x);
+        let delta = if i == 0 || i == self.entries.len() { 0 } else { self.band() };
+        self.entries.insert(i, GkEntry { value: x, g: 1, delta });
+        self.count += 1;
+        if self.count.is_multiple_of(self.compress_interval()) {
+            self.compress();
+        }
+    }
+
+    /// Adds every element of `v`.
+    pub fn push_vector(&mut self, v: &Vector<T>) {
+        for &x in &v.data {
+            self.push(x);
+        }
+    }
+
+    /// Combines `other` into this sketch, as if its elements had been pushed
+    /// here. The result keeps the larger of the two error bounds.
+    pub fn merge(&mut self, other: &QuantileSketch<T>) {
+        let a = self.ranks();
+        let b = other.ranks();
+        let (na, nb) = (self.count, other.count);
+        // Entries of `self` go first among equal values. For an entry of one
+        // summary, the entries of the other before and after it bound how
+        // many of the other's elements can precede it.
+        let mut merged = Vec::with_capacity(a.len() + b.len());
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() || j < b.len() {
+            if j == b.len() || (i < a.len() && a[i].0 <= b[j].0) {
+                let (v, rmin, rmax) = a[i];
+                let before = if j == 0 { 0 } else { b[j - 1].1 };
+                let after = if j == b.len() { nb } else { b[j].2 - 1 };
+                merged.push((v, rmin + before, rmax + after));
+                i += 1;
+            } else {
+                let (v, rmin, rmax) = b[j];
+                let before = if i == 0 { 0 } else { a[i - 1].1 };
+                let after = if i == a.len() { na } else { a[i].2 - 1 };
+                merged.push((v, rmin + before, rmax + after));
+                j += 1;
+            }
+        }
+        let mut prev = 0;
+        self.entries = merged.into_iter().map(|(value, rmin, rmax)| {
+            let g = rmin - prev;
+            prev = rmin;
+            GkEntry { value, g, delta: rmax - rmin }
+        }).collect();
+        self.count = na + nb;
+        self.epsilon = self.epsilon.max(other.epsilon);
+        self.compress();
+    }
+
+    /// Estimates the `q`th quantile for `q` in `[0, 1]`. NaN for an empty
+    /// sketch. Panics if `q` is outside `[0, 1]`.
+    pub fn quantile(&self, q: f64) -> T {
+        if !(0.0..=1.0).contains(&q) {
+            panic!("Quantile should be between 0 and 1.");
+        }
+        if self.count == 0 {
+            return T::nan();
+        }
+        if self.entries.len() == self.count {
+            let exact: Vec<T> = self.entries.iter().map(|e| e.value).collect();
+            return vector::from_vec(exact).quantile(T::from(q).unwrap());
+        }
+        let target = (q * (self.count - 1) as f64).round() as usize + 1;
+        let mut best = (usize::MAX, T::nan());
+        for (v, rmin, rmax) in self.ranks() {
+            let err = target.saturating_sub(rmin).max(rmax.saturating_sub(target));
+            if err < best.0 {
+                best = (err, v);
+            }
+        }
+        best.1
+    }
+
+    /// The values of the entries with their smallest and largest possible
+    /// ranks.
+    fn ranks(&self) -> Vec<(T, usize, usize)> {
+        let mut rmin = 0;
+        self.entries.iter().map(|e| {
+            rmin += e.g;
+            (e.value, rmin, rmin + e.delta)
+        }).collect()
+    }
+
+    /// Largest rank uncertainty `2 * epsilon * n` an entry may have.
+    fn band(&self) -> usize {
+        (2.0 * self.epsilon * self.count as f64).floor() as usize
+    }
+
+    fn compress_interval(&self) -> usize {
+        ((1.0 / (2.0 * self.epsilon)).floor() as usize).max(1)
+    }
+
+    /// Folds every entry into its successor where the combined rank
+    /// uncertainty stays within `band`. The first and last entries, the
+    /// exact minimum and maximum, are always kept.
+    fn compress(&mut self) {
+        let band = self.band();
+        let mut kept: Vec<GkEntry<T>> = Vec::with_capacity(self.entries.len());
+        while let Some(e) = self.entries.pop() {
+            match kept.last_mut() {
+                Some(next) if !self.entries.is_empty() && e.g + next.g + next.delta <= band => {
+                    next.g += e.g;
+                }
+                _ => kept.push(e),
+            }
+        }
+        kept.reverse();
+        self.entries = kept;
+    }
+}
@@ -0,0 +1,193 @@
+//! Incremental computations over streaming data
+//!
+//! # Examples
+//! ```
+//! use numrs::stream::DotAccumulator;
+//!
+//! let mut acc = DotAccumulator::new();
+//! acc.push(1.0, 2.0);
+//! acc.push_chunk(&[3.0, 4.0], &[5.0, 6.0]).unwrap();
+//! assert_eq!(acc.value(), 41.0);
+//! ```
+//!
+//! Summary statistics of data that does not fit in memory can be gathered a
+//! chunk at a time:
+//!
+//! ```
+//! use numrs::stream::ChunkedReducer;
+//!
+//! let mut left = ChunkedReducer::new();
+//! left.push_chunk(&[1.0, 2.0]);
+//! let mut right = ChunkedReducer::new();
+//! right.push_chunk(&[3.0, 4.0]);
+//! left.merge(&right);
+//! let stats = left.finalize();
+//! assert_eq!((stats.count, stats.mean, stats.max), (4, 2.5, 4.0));
+//! ```
+
+use std::io::{self, Read};
+use common::{Number, Real};
+use stats::RunningStats;
+use vector;
+use vector::{Vector, VectorOps};
+use view::VectorView;
+
+/// Running inner product of two streams of numbers
+pub struct DotAccumulator<T: Number> {
+    sum: T,
+    count: usize,
+}
+
+impl<T: Number> Default for DotAccumulator<T> {
+    fn default() -> DotAccumulator<T> {
+        DotAccumulator::new()
+    }
+}
+
+impl<T: Number> DotAccumulator<T> {
+    /// Creates an accumulator with a value of 0.
+    pub fn new() -> DotAccumulator<T> {
+        DotAccumulator { sum: T::zero(), count: 0 }
+    }
+
+    /// Adds the product of a single pair of elements.
+    pub fn push(&mut self, a: T, b: T) {
+        self.sum = self.sum + a * b;
+        self.count += 1;
+    }
+
+    /// Adds the dot product of two equal length chunks. Fails, leaving the
+    /// accumulator unchanged, if the lengths differ.
+    pub fn push_chunk(&mut self, a: &[T], b: &[T]) -> Result<(), String> {
+        let dot = VectorView::from_slice(a).dot(&VectorView::from_slice(b))?;
+        self.sum = self.sum + dot;
+        self.count += a.len();
+        Ok(())
+    }
+
+    /// Returns the inner product of everything pushed so far.
+    pub fn value(&self) -> T {
+        self.sum
+    }
+
+    /// Returns the number of element pairs pushed so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+/// Summary statistics returned by `ChunkedReducer::finalize`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ChunkStats<T> {
+    /// Number of elements seen.
+    pub count: usize,
+    /// Sum of the elements.
+    pub sum: T,
+    /// Smallest element, NaN if there were none.
+    pub min: T,
+    /// Largest element, NaN if there were none.
+    pub max: T,
+    /// Arithmetic mean, NaN if there were no elements.
+    pub mean: T,
+    /// Sample variance with `count - 1` degrees of freedom, NaN for fewer
+    /// than two elements.
+    pub variance: T,
+}
+
+/// Running summary statistics of a stream of numbers fed in chunks, see
+/// `stats::RunningStats` for how they are computed.
+#[derive(Clone, Debug, Default)]
+pub struct ChunkedReducer<T: Real> {
+    stats: RunningStats<T>,
+}
+
+impl<T: Real> ChunkedReducer<T> {
+    /// Creates a reducer that has seen no elements.
+    pub fn new() -> ChunkedReducer<T> {
+        ChunkedReducer { stats: RunningStats::new() }
+    }
+
+    /// Adds a single element.
+    pub fn push(&mut self, x: T) {
+        self.stats.push(x);
+    }
+
+    /// Adds every element of `chunk`. Vectors can be passed with `as_slice()`.
+    pub fn push_chunk(&mut self, chunk: &[T]) {
+        self.stats.push_slice(chunk);
+    }
+
+    /// Combines the statistics of `other` into this reducer, as if its
+    /// elements had been pushed here. This lets chunks be reduced in
+    /// parallel and merged afterwards.
+    pub fn merge(&mut self, other: &ChunkedReducer<T>) {
+        self.stats = self.stats.combine(&other.stats);
+    }
+
+    /// Number of elements seen so far.
+    pub fn count(&self) -> usize {
+        self.stats.count()
+    }
+
+    /// Returns the statistics of everything seen so far.
+    pub fn finalize(&self) -> ChunkStats<T> {
+        let s = &self.stats;
+        ChunkStats {
+            count: s.count(),
+            sum: s.sum(),
+            min: s.min().unwrap_or_else(T::nan),
+            max: s.max().unwrap_or_else(T::nan),
+            mean: s.mean(),
+            variance: s.var(),
+        }
+    }
+}
+
+/// Reads little-endian `f64` values from `reader` in chunks of `chunk_len`
+/// and returns `f` applied to each chunk, in order. The last chunk is shorter
+/// if the number of values is not a multiple of `chunk_len`. Fails with
+/// `InvalidData` if the input ends partway through a value. Panics if
+/// `chunk_len` is 0.
+pub fn apply_chunked<R, F, U>(reader: &mut R, chunk_len: usize, mut f: F) -> io::Result<Vec<U>>
+    where R: Read,
+          F: FnMut(&Vector<f64>) -> U
+{
+    if chunk_len == 0 {
+        panic!("Chunk length should be greater than 0.");
+    }
+    let mut buf = vec![0; 8 * chunk_len];
+    let mut out = Vec::new();
+    loop {
+        let filled = fill(reader, &mut buf)?;
+        if filled % 8 != 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                      "Input ends partway through a value."));
+        }
+        if filled > 0 {
+            let data = buf[..filled].chunks(8).map(|b| {
+                let mut bytes = [0; 8];
+                bytes.copy_from_slice(b);
+                f64::from_le_bytes(bytes)
+            });
+            out.push(f(&vector::from_vec(data.collect())));
+        }
+        if filled < buf.len() {
+            return Ok(out);
+        }
+    }
+}
+
+/// Reads into `buf` until it is full or the reader ends, returning the number
+/// of bytes read.
+fn fill<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
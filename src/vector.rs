@@ -21,14 +21,201 @@
 //! res = v2.clone() * 5.0; // multiply a vector with a constant
 //! ```
 
-use std::ops::{Index, IndexMut, Add, Sub, Mul, Neg};
-use common::Number;
+extern crate num;
+#[cfg(feature = "parallel")]
+extern crate rayon;
+
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::iter::{Product, Sum};
+use std::mem::ManuallyDrop;
+use std::ops::{Index, IndexMut, Add, Sub, Mul, Div, Neg, Range};
+use std::str::FromStr;
+use std::f64::consts::PI;
+use self::num::traits::ToPrimitive;
+use common::{Number, Real};
+use format::FormatOptions;
+use interp;
+use matrix::Matrix;
+use special;
+use stats::Ecdf;
+use view::{VectorView, VectorViewMut, StridedView, StridedViewMut};
+#[cfg(feature = "parallel")]
+use self::rayon::prelude::*;
+
+/// Number of elements each rayon task of `par_apply` works on.
+#[cfg(feature = "parallel")]
+const PAR_CHUNK: usize = 4096;
 
 /// 1D Vector
+#[derive(Debug)]
 pub struct Vector<T: Number> {
     pub data: Vec<T>,
 }
 
+/// Errors returned by the checked vector operations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VectorError {
+    /// The operands have different lengths.
+    DimensionMismatch { left: usize, right: usize },
+    /// An operand has no elements.
+    EmptyInput,
+    /// Memory for the given number of elements could not be allocated.
+    AllocationFailed { requested: usize },
+    /// The element at the given index is NaN or infinite.
+    NonFinite { index: usize },
+    /// The length is not a multiple of the required one.
+    LengthNotMultiple { len: usize, multiple: usize },
+    /// The divisor at the given index is zero.
+    DivisionByZero { index: usize },
+}
+
+impl fmt::Display for VectorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            VectorError::DimensionMismatch { left, right } => {
+                write!(f, "Vectors are not conformable: lengths {} and {}.", left, right)
+            }
+            VectorError::EmptyInput => write!(f, "Vector is empty."),
+            VectorError::AllocationFailed { requested } => {
+                write!(f, "Could not allocate a vector of {} elements.", requested)
+            }
+            VectorError::NonFinite { index } => {
+                write!(f, "Vector element at index {} is not finite.", index)
+            }
+            VectorError::LengthNotMultiple { len, multiple } => {
+                write!(f, "Vector length {} is not a multiple of {}.", len, multiple)
+            }
+            VectorError::DivisionByZero { index } => {
+                write!(f, "Division by zero at index {}.", index)
+            }
+        }
+    }
+}
+
+impl Error for VectorError {}
+
+/// Invariants checked by `Vector::try_new`. The default checks nothing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ValidationPolicy {
+    /// Reject NaN and infinite elements.
+    pub finite: bool,
+    /// Reject empty input.
+    pub non_empty: bool,
+    /// Require the length to be a multiple of this, e.g. the number of
+    /// columns of the matrix the data will be reshaped into.
+    pub multiple_of: Option<usize>,
+}
+
+impl ValidationPolicy {
+    /// Only rejects NaN and infinite elements, as `TryFrom` does.
+    pub fn finite() -> ValidationPolicy {
+        ValidationPolicy { finite: true, ..ValidationPolicy::default() }
+    }
+}
+
+/// How `Vector::pad` fills the added elements, following `numpy.pad`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PadMode<T> {
+    /// Every added element is the given value.
+    Constant(T),
+    /// The first and last elements are repeated.
+    Edge,
+    /// The elements are mirrored about the first and last elements, which
+    /// are not repeated: `[1, 2, 3]` padded by 2 on each side is
+    /// `[3, 2, 1, 2, 3, 2, 1]`. Pads longer than the vector keep reflecting.
+    Reflect,
+}
+
+/// How `Vector::outlier_mask` measures the distance from the center.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutlierMethod {
+    /// Distance from the mean in (population) standard deviations.
+    StdDev,
+    /// Tukey's fences: distance below the first or above the third quartile
+    /// in interquartile ranges.
+    Iqr,
+}
+
+/// Operations shared by `Vector` and the borrowed views, which only need
+/// read access to a contiguous run of elements.
+pub trait VectorOps<T: Number> {
+    /// Returns the elements as a slice.
+    fn as_slice(&self) -> &[T];
+
+    /// Sum of all the elements.
+    fn sum(&self) -> T {
+        let mut sum = T::zero();
+        for &x in self.as_slice() {
+            sum = sum + x;
+        }
+        sum
+    }
+
+    /// Arithmetic mean of the elements, which is NaN for an empty vector.
+    fn mean(&self) -> T
+        where T: Real
+    {
+        self.sum() / T::from(self.as_slice().len()).unwrap()
+    }
+
+    /// Smallest element, or `None` for an empty vector.
+    fn min(&self) -> Option<T>
+        where T: PartialOrd
+    {
+        let mut it = self.as_slice().iter();
+        let first = it.next().cloned();
+        it.fold(first, |m, &x| m.map(|m| if x < m { x } else { m }))
+    }
+
+    /// Largest element, or `None` for an empty vector.
+    fn max(&self) -> Option<T>
+        where T: PartialOrd
+    {
+        let mut it = self.as_slice().iter();
+        let first = it.next().cloned();
+        it.fold(first, |m, &x| m.map(|m| if x > m { x } else { m }))
+    }
+
+    /// Dot product of two equal length vectors.
+    fn dot<V: VectorOps<T>>(&self, other: &V) -> Result<T, String> {
+        let (a, b) = (self.as_slice(), other.as_slice());
+        if a.len() != b.len() {
+            return Err("Vectors are not conformable for dot product.".to_string());
+        }
+        let mut sum = T::zero();
+        for (&x, &y) in a.iter().zip(b.iter()) {
+            sum = sum + x * y;
+        }
+        Ok(sum)
+    }
+
+    /// Euclidean norm of the vector.
+    fn norm(&self) -> T
+        where T: Real
+    {
+        let mut sum = T::zero();
+        for &x in self.as_slice() {
+            sum = sum + x * x;
+        }
+        sum.sqrt()
+    }
+
+    /// Copies the elements into a new `Vector`.
+    fn to_vector(&self) -> Vector<T> {
+        from_elems(self.as_slice())
+    }
+}
+
+impl<T: Number> VectorOps<T> for Vector<T> {
+    #[inline]
+    fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+}
+
 impl<T: Number> Index<usize> for Vector<T> {
     type Output = T;
 
@@ -55,54 +242,52 @@ impl<T: Number> Clone for Vector<T> {
     }
 }
 
-impl<T: Number> Add<Vector<T>> for Vector<T> {
-    type Output = Vector<T>;
+// Elementwise operators between vectors, for every combination of owned and
+// borrowed operands. An owned operand's buffer is reused for the result, so
+// only `&a op &b` allocates. Panics if the lengths differ.
+macro_rules! vector_op {
+    ($Op:ident, $method:ident, $op:tt, $name:expr) => {
+        impl<T: Number> $Op<Vector<T>> for Vector<T> {
+            type Output = Vector<T>;
 
-    fn add(self, rhs: Vector<T>) -> Vector<T> {
-        if self.len() == rhs.len() {
-            let mut new_vec = Vector::<T>::new(self.len(), T::zero());
-            for i in 0..self.len() {
-                new_vec.data[i] = self.data[i] + rhs.data[i];
+            fn $method(self, rhs: Vector<T>) -> Vector<T> {
+                self $op &rhs
             }
-            new_vec
-        } else {
-            panic!("Vectors are not conformable for addition.");
         }
-    }
-}
 
-impl<T: Number> Sub<Vector<T>> for Vector<T> {
-    type Output = Vector<T>;
+        impl<'a, T: Number> $Op<&'a Vector<T>> for Vector<T> {
+            type Output = Vector<T>;
 
-    fn sub(self, rhs: Vector<T>) -> Vector<T> {
-        if self.len() == rhs.len() {
-            let mut new_vec = Vector::<T>::new(self.len(), T::zero());
-            for i in 0..self.len() {
-                new_vec.data[i] = self.data[i] - rhs.data[i];
+            fn $method(mut self, rhs: &'a Vector<T>) -> Vector<T> {
+                self.zip_assign(rhs, |x, y| x $op y, $name);
+                self
             }
-            new_vec
-        } else {
-            panic!("Vectors are not conformable for subtraction.");
         }
-    }
-}
 
-impl<T: Number> Mul<Vector<T>> for Vector<T> {
-    type Output = Vector<T>;
+        impl<'a, T: Number> $Op<Vector<T>> for &'a Vector<T> {
+            type Output = Vector<T>;
 
-    fn mul(self, rhs: Vector<T>) -> Vector<T> {
-        if self.len() == rhs.len() {
-            let mut new_vec = Vector::<T>::new(self.len(), T::zero());
-            for i in 0..self.len() {
-                new_vec.data[i] = self.data[i] * rhs.data[i];
+            fn $method(self, mut rhs: Vector<T>) -> Vector<T> {
+                rhs.zip_assign(self, |y, x| x $op y, $name);
+                rhs
+            }
+        }
+
+        impl<'a, 'b, T: Number> $Op<&'b Vector<T>> for &'a Vector<T> {
+            type Output = Vector<T>;
+
+            fn $method(self, rhs: &'b Vector<T>) -> Vector<T> {
+                self.clone() $op rhs
             }
-            new_vec
-        } else {
-            panic!("Vectors are not conformable for multiplication.")
         }
     }
 }
 
+vector_op!(Add, add, +, "addition");
+vector_op!(Sub, sub, -, "subtraction");
+vector_op!(Mul, mul, *, "multiplication");
+vector_op!(Div, div, /, "division");
+
 impl<T: Number + Neg<Output = T>> Neg for Vector<T> {
     type Output = Vector<T>;
 
@@ -127,21 +312,146 @@ impl<T: Number> Mul<T> for Vector<T> {
     }
 }
 
+// Operators with the scalar on the left, e.g. `2.0 * v` or `1.0 - v`. These
+// have to be implemented on the scalar type, so they exist for `f32` and
+// `f64` only. Each computes `s op v[i]` for every element.
+macro_rules! scalar_lhs_op {
+    ($T:ty, $Op:ident, $method:ident, $op:tt) => {
+        impl $Op<Vector<$T>> for $T {
+            type Output = Vector<$T>;
+
+            fn $method(self, rhs: Vector<$T>) -> Vector<$T> {
+                self $op &rhs
+            }
+        }
+
+        impl<'a> $Op<&'a Vector<$T>> for $T {
+            type Output = Vector<$T>;
+
+            fn $method(self, rhs: &'a Vector<$T>) -> Vector<$T> {
+                Vector { data: rhs.data.iter().map(|&x| self $op x).collect() }
+            }
+        }
+    }
+}
+
+scalar_lhs_op!(f32, Add, add, +);
+scalar_lhs_op!(f32, Sub, sub, -);
+scalar_lhs_op!(f32, Mul, mul, *);
+scalar_lhs_op!(f32, Div, div, /);
+scalar_lhs_op!(f64, Add, add, +);
+scalar_lhs_op!(f64, Sub, sub, -);
+scalar_lhs_op!(f64, Mul, mul, *);
+scalar_lhs_op!(f64, Div, div, /);
+
+/// Numerical equality of every element, stopping at the first difference.
+/// As for the elements, NaN is not equal to itself and `-0.0 == 0.0`; see
+/// `bitwise_eq` for an exact comparison of floats.
 impl<T: Number> PartialEq for Vector<T> {
     fn eq(&self, other: &Vector<T>) -> bool {
-        if self.len() != other.len() {
-            return false;
+        self.data == other.data
+    }
+}
+
+impl<T: Number> Eq for Vector<T> {}
+
+/// Element-wise sum of the vectors. An empty iterator gives an empty vector,
+/// so start from `Vector::new(n, T::zero())` with `fold` when the length must
+/// be known. Panics if the vectors differ in length.
+impl<T: Number> Sum for Vector<T> {
+    fn sum<I: Iterator<Item = Vector<T>>>(iter: I) -> Vector<T> {
+        iter.fold(None, |acc: Option<Vector<T>>, v| match acc {
+            None => Some(v),
+            Some(mut acc) => {
+                acc.zip_assign(&v, |x, y| x + y, "addition");
+                Some(acc)
+            }
+        }).unwrap_or_else(|| from_vec(Vec::new()))
+    }
+}
+
+impl<'a, T: Number> Sum<&'a Vector<T>> for Vector<T> {
+    fn sum<I: Iterator<Item = &'a Vector<T>>>(iter: I) -> Vector<T> {
+        iter.cloned().sum()
+    }
+}
+
+/// Element-wise product of the vectors, with the same conventions as `Sum`.
+impl<T: Number> Product for Vector<T> {
+    fn product<I: Iterator<Item = Vector<T>>>(iter: I) -> Vector<T> {
+        iter.fold(None, |acc: Option<Vector<T>>, v| match acc {
+            None => Some(v),
+            Some(mut acc) => {
+                acc.zip_assign(&v, |x, y| x * y, "multiplication");
+                Some(acc)
+            }
+        }).unwrap_or_else(|| from_vec(Vec::new()))
+    }
+}
+
+impl<'a, T: Number> Product<&'a Vector<T>> for Vector<T> {
+    fn product<I: Iterator<Item = &'a Vector<T>>>(iter: I) -> Vector<T> {
+        iter.cloned().product()
+    }
+}
+
+/// Appends the elements of the iterator to the vector.
+impl<T: Number> Extend<T> for Vector<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.data.extend(iter);
+    }
+}
+
+/// Fallible conversion that only accepts finite elements, for validating data
+/// that comes from outside the program, see `ValidationPolicy::finite`. Takes
+/// ownership of the buffer without copying it. Use `from_vec` to skip the
+/// check.
+impl<T: Real> TryFrom<Vec<T>> for Vector<T> {
+    type Error = VectorError;
+
+    fn try_from(data: Vec<T>) -> Result<Vector<T>, VectorError> {
+        match data.iter().position(|x| !x.is_finite()) {
+            Some(index) => Err(VectorError::NonFinite { index }),
+            None => Ok(Vector::<T> { data }),
         }
-        for i in 0..self.len() {
-            if self.data[i] != other.data[i] {
-                return false;
+    }
+}
+
+/// Fallible conversion that only accepts finite elements, see
+/// `ValidationPolicy::finite`.
+impl<'a, T: Real> TryFrom<&'a [T]> for Vector<T> {
+    type Error = VectorError;
+
+    fn try_from(elems: &'a [T]) -> Result<Vector<T>, VectorError> {
+        Vector::try_new(elems, ValidationPolicy::finite())
+    }
+}
+
+impl<T: Number + fmt::Display> fmt::Display for Vector<T> {
+    /// Writes the elements as `[1, 2.5, -300]`, which `from_str` reads back.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[")?;
+        for (i, x) in self.data.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
             }
+            write!(f, "{}", x)?;
         }
-        true
+        write!(f, "]")
     }
 }
 
-impl<T: Number> Eq for Vector<T> {}
+/// Parses lists such as `[1.0, 2.5, -3e2]` or `1 2.5 -3e2`. Elements are
+/// separated by commas and/or whitespace, the brackets are optional and a
+/// trailing separator is allowed. `inf` and `NaN` are accepted. Errors give
+/// the character offset of the offending token.
+impl<T: Real + FromStr> FromStr for Vector<T> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Vector<T>, String> {
+        parse_elems(s, 0).map(|data| Vector::<T> { data })
+    }
+}
 
 impl<T: Number> Vector<T> {
     #[inline]
@@ -153,10 +463,1444 @@ impl<T: Number> Vector<T> {
         let d = vec![default; length];
         Vector::<T> { data: d }
     }
+
+    /// Applies `f` to every element, splitting the vector into chunks which
+    /// are processed on the rayon thread pool. The result is the same as
+    /// mapping `f` over the elements in order.
+    #[cfg(feature = "parallel")]
+    pub fn par_apply<F: Fn(T) -> T + Sync>(&self, f: F) -> Vector<T> {
+        let mut out = self.clone();
+        out.data.par_chunks_mut(PAR_CHUNK).for_each(|chunk| {
+            for x in chunk.iter_mut() {
+                *x = f(*x);
+            }
+        });
+        out
+    }
+
+    /// Returns a raw pointer to the first element. The pointer is valid for
+    /// `len()` reads as long as the vector is neither dropped nor resized.
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        self.data.as_ptr()
+    }
+
+    /// Returns a raw mutable pointer to the first element. The pointer is
+    /// valid for `len()` reads and writes as long as the vector is neither
+    /// dropped nor resized.
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.data.as_mut_ptr()
+    }
+
+    /// Decomposes the vector into a pointer to its buffer, its length and its
+    /// capacity, without dropping the elements. The caller becomes
+    /// responsible for the memory, which was allocated by the Rust global
+    /// allocator; the only way to free it is to rebuild the vector with
+    /// `from_raw_parts` and drop it. It must not be freed from C.
+    pub fn into_raw_parts(self) -> (*mut T, usize, usize) {
+        let mut data = ManuallyDrop::new(self.data);
+        (data.as_mut_ptr(), data.len(), data.capacity())
+    }
+
+    /// Rebuilds a vector from the parts returned by `into_raw_parts`.
+    ///
+    /// # Safety
+    ///
+    /// The same contract as `Vec::from_raw_parts` applies: `ptr` must have
+    /// been allocated by the Rust global allocator for `capacity` elements of
+    /// `T` (normally it comes from `into_raw_parts`), the first `length`
+    /// elements must be initialized, `length <= capacity`, and ownership of
+    /// the buffer moves into the returned vector, so the parts must not be
+    /// used to build a second one.
+    pub unsafe fn from_raw_parts(ptr: *mut T, length: usize, capacity: usize) -> Vector<T> {
+        Vector::<T> { data: Vec::from_raw_parts(ptr, length, capacity) }
+    }
+
+    /// Returns a read-only view of the elements in `range`.
+    pub fn view(&self, range: Range<usize>) -> VectorView<'_, T> {
+        VectorView::from_slice(&self.data[range])
+    }
+
+    /// Returns a mutable view of the elements in `range`.
+    pub fn view_mut(&mut self, range: Range<usize>) -> VectorViewMut<'_, T> {
+        VectorViewMut::from_slice(&mut self.data[range])
+    }
+
+    /// Returns a read-only view of every `stride`th element starting from
+    /// `offset`, e.g. one channel of interleaved data.
+    pub fn strided_view(&self, offset: usize, stride: usize) -> Result<StridedView<'_, T>, String> {
+        check_stride(self.len(), offset, stride)?;
+        Ok(StridedView::from_slice(&self.data[offset..], stride))
+    }
+
+    /// Returns a mutable view of every `stride`th element starting from
+    /// `offset`.
+    pub fn strided_view_mut(&mut self, offset: usize, stride: usize) -> Result<StridedViewMut<'_, T>, String> {
+        check_stride(self.len(), offset, stride)?;
+        Ok(StridedViewMut::from_slice(&mut self.data[offset..], stride))
+    }
+
+    /// Returns a new vector with every element multiplied by itself.
+    pub fn square(&self) -> Vector<T> {
+        let mut v = self.clone();
+        v.square_mut();
+        v
+    }
+
+    /// Multiplies every element of the vector by itself in place.
+    pub fn square_mut(&mut self) {
+        for x in self.data.iter_mut() {
+            *x = *x * *x;
+        }
+    }
+
+    /// Returns a new vector with the elements at `indices`, in that order.
+    pub fn take(&self, indices: &[usize]) -> Result<Vector<T>, String> {
+        self.check_indices(indices)?;
+        Ok(Vector::<T> { data: indices.iter().map(|&i| self.data[i]).collect() })
+    }
+
+    /// Writes `values[k]` to the position `indices[k]`, the inverse of
+    /// `take`. Nothing is written if any index is out of bounds.
+    pub fn put(&mut self, indices: &[usize], values: &[T]) -> Result<(), String> {
+        if indices.len() != values.len() {
+            return Err("Indices and values are not conformable for put.".to_string());
+        }
+        self.check_indices(indices)?;
+        for (&i, &x) in indices.iter().zip(values.iter()) {
+            self.data[i] = x;
+        }
+        Ok(())
+    }
+
+    /// Dot product of the elements at `indices` with `other`,
+    /// `sum(self[indices[k]] * other[k])`, without gathering them into a new
+    /// vector first. Indices may repeat. Fails before computing anything if
+    /// an index is out of bounds.
+    pub fn dot_indexed(&self, other: &Vector<T>, indices: &[usize]) -> Result<T, String> {
+        if indices.len() != other.len() {
+            return Err("Indices and vector are not conformable for dot product.".to_string());
+        }
+        self.check_indices(indices)?;
+        let mut sum = T::zero();
+        for (&i, &y) in indices.iter().zip(other.data.iter()) {
+            sum = sum + self.data[i] * y;
+        }
+        Ok(sum)
+    }
+
+    fn check_indices(&self, indices: &[usize]) -> Result<(), String> {
+        match indices.iter().position(|&i| i >= self.len()) {
+            Some(k) => Err(format!("Index {} at position {} out of bounds for vector of length {}.",
+                                   indices[k], k, self.len())),
+            None => Ok(()),
+        }
+    }
+
+    /// Replaces every element `x` with `f(x, y)` for the matching element `y`
+    /// of `other`, panicking with a message naming `op` if the lengths differ.
+    fn zip_assign<F: Fn(T, T) -> T>(&mut self, other: &Vector<T>, f: F, op: &str) {
+        if self.len() != other.len() {
+            panic!("Vectors are not conformable for {}.", op);
+        }
+        for (x, &y) in self.data.iter_mut().zip(other.data.iter()) {
+            *x = f(*x, y);
+        }
+    }
+
+    /// Creates an empty vector with room for `capacity` elements, failing
+    /// instead of aborting if the memory cannot be allocated.
+    pub fn try_with_capacity(capacity: usize) -> Result<Vector<T>, VectorError> {
+        let mut data = Vec::new();
+        data.try_reserve_exact(capacity)
+            .map_err(|_| VectorError::AllocationFailed { requested: capacity })?;
+        Ok(Vector::<T> { data })
+    }
+
+    /// Creates a vector of `length` copies of `value`, failing instead of
+    /// aborting if the memory cannot be allocated.
+    pub fn try_filled(length: usize, value: T) -> Result<Vector<T>, VectorError> {
+        let mut v = Vector::try_with_capacity(length)?;
+        v.data.resize(length, value);
+        Ok(v)
+    }
+
+    /// Creates a vector of `length` zeros, see `try_filled`.
+    pub fn try_zeros(length: usize) -> Result<Vector<T>, VectorError> {
+        Vector::try_filled(length, T::zero())
+    }
+
+    /// Elementwise sum which reports a length mismatch or a failed
+    /// allocation of the result as an error instead of panicking or aborting.
+    pub fn checked_add(&self, other: &Vector<T>) -> Result<Vector<T>, VectorError> {
+        self.try_zip_map(other, |x, y| x + y)
+    }
+
+    /// Elementwise difference, see `checked_add`.
+    pub fn checked_sub(&self, other: &Vector<T>) -> Result<Vector<T>, VectorError> {
+        self.try_zip_map(other, |x, y| x - y)
+    }
+
+    /// Elementwise product, see `checked_add`.
+    pub fn checked_mul(&self, other: &Vector<T>) -> Result<Vector<T>, VectorError> {
+        self.try_zip_map(other, |x, y| x * y)
+    }
+
+    /// Elementwise quotient, see `checked_add`, which also reports the index
+    /// of the first zero divisor (including `-0.0`) instead of producing an
+    /// infinity or NaN, or panicking for integers.
+    pub fn checked_div_elem(&self, other: &Vector<T>) -> Result<Vector<T>, VectorError> {
+        if self.len() == other.len() {
+            if let Some(index) = other.data.iter().position(|&y| y == T::zero()) {
+                return Err(VectorError::DivisionByZero { index });
+            }
+        }
+        self.try_zip_map(other, |x, y| x / y)
+    }
+
+    /// Elementwise quotient with `default` wherever the divisor is zero.
+    /// Errors only if the lengths differ or the allocation fails.
+    pub fn div_or(&self, other: &Vector<T>, default: T) -> Result<Vector<T>, VectorError> {
+        self.try_zip_map(other, |x, y| if y == T::zero() { default } else { x / y })
+    }
+
+    fn try_zip_map<F: Fn(T, T) -> T>(&self, other: &Vector<T>, f: F) -> Result<Vector<T>, VectorError> {
+        if self.len() != other.len() {
+            return Err(VectorError::DimensionMismatch { left: self.len(), right: other.len() });
+        }
+        let mut v = Vector::try_with_capacity(self.len())?;
+        v.data.extend(self.data.iter().zip(other.data.iter()).map(|(&x, &y)| f(x, y)));
+        Ok(v)
+    }
+
+    /// Adds `a` times `x` to the vector in place, `self += a * x`.
+    pub fn axpy(&mut self, a: T, x: &Vector<T>) -> Result<(), String> {
+        if self.len() != x.len() {
+            return Err("Vectors are not conformable for axpy.".to_string());
+        }
+        for (y, &x) in self.data.iter_mut().zip(x.data.iter()) {
+            *y = *y + a * x;
+        }
+        Ok(())
+    }
+
+    /// Bilinear form `self^T * matrix * other`. Fails unless `self` has as
+    /// many elements as the matrix has rows and `other` as many as it has
+    /// columns.
+    pub fn bilinear(&self, matrix: &Matrix<T>, other: &Vector<T>) -> Result<T, String> {
+        if self.len() != matrix.num_rows() {
+            return Err("Matrix and vector are not conformable for multiplication.".to_string());
+        }
+        self.dot(&matrix.mul_vec(other)?)
+    }
+
+    /// Quadratic form `self^T * matrix * self`, see `bilinear`. Fails unless
+    /// the matrix is square with as many rows as the vector has elements.
+    pub fn quadratic_form(&self, matrix: &Matrix<T>) -> Result<T, String> {
+        if matrix.num_rows() != matrix.num_cols() {
+            return Err("The matrix should be a square matrix.".to_string());
+        }
+        self.bilinear(matrix, self)
+    }
+
+    /// Dot product which, unlike `dot`, tells apart a mismatch in length and
+    /// empty operands, whose dot product would silently be zero.
+    pub fn dot_checked(&self, other: &Vector<T>) -> Result<T, VectorError> {
+        if self.len() != other.len() {
+            return Err(VectorError::DimensionMismatch { left: self.len(), right: other.len() });
+        }
+        if self.data.is_empty() {
+            return Err(VectorError::EmptyInput);
+        }
+        Ok(self.dot(other).unwrap())
+    }
+
+    /// Multiplies the signal elementwise by `window`, e.g. one made by `hann`,
+    /// before spectral analysis.
+    pub fn apply_window(&self, window: &Vector<T>) -> Result<Vector<T>, String> {
+        if self.len() != window.len() {
+            return Err("Signal and window should have the same length.".to_string());
+        }
+        let data = self.data.iter().zip(window.data.iter()).map(|(&x, &w)| x * w);
+        Ok(Vector::<T> { data: data.collect() })
+    }
+
+    /// Returns a copy with `before` elements added at the start and `after`
+    /// at the end, filled according to `mode`. Panics if the vector is empty
+    /// and `mode` is `Edge` or `Reflect` with a non-zero pad, since there is
+    /// nothing to repeat.
+    pub fn pad(&self, before: usize, after: usize, mode: PadMode<T>) -> Vector<T> {
+        let n = self.len();
+        if n == 0 && before + after > 0 && !matches!(mode, PadMode::Constant(_)) {
+            panic!("Cannot pad an empty vector in edge or reflect mode.");
+        }
+        let source = |j: isize| -> T {
+            match mode {
+                PadMode::Constant(c) => c,
+                PadMode::Edge => self.data[j.max(0).min(n as isize - 1) as usize],
+                PadMode::Reflect if n == 1 => self.data[0],
+                PadMode::Reflect => {
+                    let period = 2 * (n as isize - 1);
+                    let m = j.rem_euclid(period);
+                    self.data[if m < n as isize { m } else { period - m } as usize]
+                }
+            }
+        };
+        let mut data = Vec::with_capacity(before + n + after);
+        data.extend((0..before).map(|i| source(i as isize - before as isize)));
+        data.extend_from_slice(&self.data);
+        data.extend((0..after).map(|i| source((n + i) as isize)));
+        Vector::<T> { data }
+    }
+
+    /// Rotates the elements `n` places to the left in place, so the element
+    /// at `n % len` becomes the first. Does nothing to an empty vector.
+    pub fn rotate_left(&mut self, n: usize) {
+        if !self.data.is_empty() {
+            let k = n % self.len();
+            self.data.rotate_left(k);
+        }
+    }
+
+    /// Rotates the elements `n` places to the right in place, see
+    /// `rotate_left`.
+    pub fn rotate_right(&mut self, n: usize) {
+        if !self.data.is_empty() {
+            let k = n % self.len();
+            self.data.rotate_right(k);
+        }
+    }
+
+    /// Returns a copy with the elements moved `n` places towards the end, or
+    /// towards the start for negative `n`, filling the vacated places with
+    /// `fill`: `shift(1, f)` of `[a, b, c]` is `[f, a, b]`. Shifting by the
+    /// length or more gives a vector of `fill`.
+    pub fn shift(&self, n: isize, fill: T) -> Vector<T> {
+        let len = self.len();
+        let k = n.unsigned_abs().min(len);
+        let mut v = Vector::new(len, fill);
+        if n >= 0 {
+            v.data[k..].copy_from_slice(&self.data[..len - k]);
+        } else {
+            v.data[..len - k].copy_from_slice(&self.data[k..]);
+        }
+        v
+    }
+
+    /// Whether any element is nonzero. As in NumPy, `-0.0` counts as zero
+    /// and NaN as nonzero. False for an empty vector.
+    pub fn any(&self) -> bool {
+        self.data.iter().any(|&x| x != T::zero())
+    }
+
+    /// Whether every element is nonzero, see `any`. True for an empty vector.
+    pub fn all(&self) -> bool {
+        self.data.iter().all(|&x| x != T::zero())
+    }
+
+    /// Number of nonzero elements, see `any`.
+    pub fn count_nonzero(&self) -> usize {
+        self.data.iter().filter(|&&x| x != T::zero()).count()
+    }
+
+    /// Evaluates the polynomial with coefficients `coeffs`, highest power
+    /// first as in `numpy.polyval`, at every element by Horner's method.
+    /// No coefficients give the zero polynomial.
+    pub fn polyval(&self, coeffs: &Vector<T>) -> Vector<T> {
+        let data = self.data.iter().map(|&x| coeffs.data.iter().fold(T::zero(), |acc, &c| acc * x + c));
+        Vector::<T> { data: data.collect() }
+    }
+
+    /// Returns the running sum of the vector, where the `i`th element is the
+    /// sum of the first `i + 1` elements.
+    pub fn cumsum(&self) -> Vector<T> {
+        let mut v = self.clone();
+        let mut sum = T::zero();
+        for x in v.data.iter_mut() {
+            sum = sum + *x;
+            *x = sum;
+        }
+        v
+    }
 }
 
-pub fn from_elems<T: Number>(elems: &[T]) -> Vector<T> {
-    let mut v = Vector::<T> { data: Vec::with_capacity(elems.len()) };
-    v.data.extend_from_slice(elems);
-    v
+impl<T: Number + ToPrimitive> Vector<T> {
+    /// Renders the vector as text according to `opts`, e.g. `[1.00, 2.50]`.
+    pub fn to_string_with(&self, opts: FormatOptions) -> String {
+        opts.render_row(&self.data, 0, opts.show_indices)
+    }
+}
+
+impl<T: Number + PartialOrd> Vector<T> {
+    /// Returns the indices that would sort the vector in ascending order.
+    /// The sort is stable, so equal elements keep their original order, and
+    /// NaNs are placed last.
+    pub fn argsort(&self) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..self.len()).collect();
+        indices.sort_by(|&a, &b| nan_last_cmp(&self.data[a], &self.data[b]));
+        indices
+    }
+
+    /// Returns a new vector with every element limited to `[lo, hi]`. NaNs
+    /// are left as they are. Panics if `lo > hi`.
+    pub fn clamp(&self, lo: T, hi: T) -> Vector<T> {
+        self.clamp_counted(lo, hi).0
+    }
+
+    /// Clamps like `clamp` and also returns how many elements were changed.
+    /// Elements exactly equal to a bound are not counted as clipped.
+    pub fn clamp_counted(&self, lo: T, hi: T) -> (Vector<T>, usize) {
+        if lo > hi {
+            panic!("Lower bound should not exceed upper bound.");
+        }
+        let mut clipped = 0;
+        let mut v = self.clone();
+        for x in v.data.iter_mut() {
+            if *x < lo {
+                *x = lo;
+                clipped += 1;
+            } else if *x > hi {
+                *x = hi;
+                clipped += 1;
+            }
+        }
+        (v, clipped)
+    }
+
+    /// Number of sign changes between consecutive elements. Zeros (and NaNs)
+    /// carry no sign and are skipped, so `[1, 0, -1]` has one crossing and
+    /// `[1, 0, 1]` none; a run of zeros counts as at most one crossing.
+    pub fn zero_crossings(&self) -> usize {
+        let zero = T::zero();
+        let mut last = None;
+        let mut count = 0;
+        for &x in &self.data {
+            let positive = if x > zero {
+                true
+            } else if x < zero {
+                false
+            } else {
+                continue;
+            };
+            if last == Some(!positive) {
+                count += 1;
+            }
+            last = Some(positive);
+        }
+        count
+    }
+}
+
+impl<T: Real> Vector<T> {
+    /// Fraction of the elements less than or equal to `value`, i.e. the
+    /// empirical CDF. This sorts the vector on every call; use
+    /// `stats::Ecdf` for repeated queries.
+    pub fn ecdf(&self, value: T) -> T {
+        Ecdf::new(self).eval(value)
+    }
+
+    /// `ecdf` as a percentage between 0 and 100.
+    pub fn percentile_rank(&self, value: T) -> T {
+        Ecdf::new(self).percentile_rank(value)
+    }
+
+    /// Returns the `q`th quantile for `q` in `[0, 1]`, interpolating linearly
+    /// between the closest elements. The result is NaN for an empty vector or
+    /// one that contains NaNs. Panics if `q` is outside `[0, 1]`.
+    pub fn quantile(&self, q: T) -> T {
+        if !(q >= T::zero() && q <= T::one()) {
+            panic!("Quantile should be between 0 and 1.");
+        }
+        if self.data.is_empty() || self.data.iter().any(|x| x.is_nan()) {
+            return T::nan();
+        }
+        let sorted = self.take(&self.argsort()).unwrap();
+        let pos = q * T::from(self.len() - 1).unwrap();
+        let (lo, hi) = (pos.floor().to_usize().unwrap(), pos.ceil().to_usize().unwrap());
+        sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - pos.floor())
+    }
+
+    /// Returns the `p`th percentile for `p` in `[0, 100]`, see `quantile`.
+    pub fn percentile(&self, p: T) -> T {
+        self.quantile(p / T::from(100).unwrap())
+    }
+
+    /// Clamps the elements to the range between the `lower` and `upper`
+    /// quantiles. Panics unless `0 <= lower < upper <= 1`.
+    pub fn clip_to_quantiles(&self, lower: T, upper: T) -> Vector<T> {
+        if !(T::zero() <= lower && lower < upper && upper <= T::one()) {
+            panic!("Quantiles should satisfy 0 <= lower < upper <= 1.");
+        }
+        self.clamp(self.quantile(lower), self.quantile(upper))
+    }
+
+    /// Returns the running maximum, where the `i`th element is the largest of
+    /// the first `i + 1` elements. A NaN propagates to every later element;
+    /// see `nancummax` to skip NaNs.
+    pub fn cummax(&self) -> Vector<T> {
+        self.running(|acc, x| if x.is_nan() || x > acc { x } else { acc }, false)
+    }
+
+    /// Returns the running minimum, see `cummax`.
+    pub fn cummin(&self) -> Vector<T> {
+        self.running(|acc, x| if x.is_nan() || x < acc { x } else { acc }, false)
+    }
+
+    /// Running maximum that ignores NaNs. Each NaN is replaced by the maximum
+    /// so far, so it stays NaN only before the first number.
+    pub fn nancummax(&self) -> Vector<T> {
+        self.running(|acc, x| if x > acc { x } else { acc }, true)
+    }
+
+    /// Running minimum that ignores NaNs, see `nancummax`.
+    pub fn nancummin(&self) -> Vector<T> {
+        self.running(|acc, x| if x < acc { x } else { acc }, true)
+    }
+
+    /// Scans the vector with `f`, seeding the accumulator with the first
+    /// element, or with the first non-NaN element if `skip_nan` is set. Once
+    /// the accumulator is NaN it stays NaN.
+    fn running<F: Fn(T, T) -> T>(&self, f: F, skip_nan: bool) -> Vector<T> {
+        let mut acc = T::nan();
+        let mut seeded = false;
+        let data = self.data.iter().map(|&x| {
+            if !seeded {
+                if !(skip_nan && x.is_nan()) {
+                    acc = x;
+                    seeded = true;
+                }
+            } else if !acc.is_nan() {
+                acc = f(acc, x);
+            }
+            acc
+        }).collect();
+        Vector::<T> { data }
+    }
+
+    /// Smooth Hermite step from 0 at `edge0` to 1 at `edge1`, as in GLSL:
+    /// `t * t * (3 - 2t)` with `t = (x - edge0) / (edge1 - edge0)` clamped to
+    /// `[0, 1]`. If the edges are equal this is a hard step, 0 below the edge
+    /// and 1 from it on. NaN stays NaN.
+    pub fn smoothstep(&self, edge0: T, edge1: T) -> Vector<T> {
+        let (zero, one) = (T::zero(), T::one());
+        let three = one + one + one;
+        let data = self.data.iter().map(|&x| {
+            if x.is_nan() {
+                x
+            } else if edge0 == edge1 {
+                if x < edge0 { zero } else { one }
+            } else {
+                let t = ((x - edge0) / (edge1 - edge0)).max(zero).min(one);
+                t * t * (three - (t + t))
+            }
+        });
+        Vector::<T> { data: data.collect() }
+    }
+
+    /// Clamps the elements to the values of the `lower` and `upper`
+    /// quantiles, see `quantile`. Unlike `clip_to_quantiles` this reports bad
+    /// quantiles as an error and accepts `lower == upper`.
+    pub fn winsorize(&self, lower: T, upper: T) -> Result<Vector<T>, String> {
+        if !(T::zero() <= lower && lower <= upper && upper <= T::one()) {
+            return Err("Quantiles should satisfy 0 <= lower <= upper <= 1.".to_string());
+        }
+        if self.data.is_empty() {
+            return Ok(self.clone());
+        }
+        Ok(self.clamp(self.quantile(lower), self.quantile(upper)))
+    }
+
+    /// Flags the elements more than `k` times the spread of `method` away
+    /// from the center. Nothing is flagged in a constant vector or in one
+    /// that contains NaNs. Panics if `k` is negative or NaN.
+    pub fn outlier_mask(&self, k: T, method: OutlierMethod) -> Vec<bool> {
+        if k < T::zero() || k.is_nan() {
+            panic!("Outlier threshold should be non-negative.");
+        }
+        if self.data.is_empty() {
+            return Vec::new();
+        }
+        let (lo, hi) = match method {
+            OutlierMethod::StdDev => {
+                let n = T::from(self.len()).unwrap();
+                let mean = self.data.iter().fold(T::zero(), |s, &x| s + x) / n;
+                let var = self.data.iter().fold(T::zero(), |s, &x| s + (x - mean) * (x - mean)) / n;
+                let d = k * var.sqrt();
+                (mean - d, mean + d)
+            }
+            OutlierMethod::Iqr => {
+                let (q1, q3) = (self.quantile(T::from(0.25).unwrap()), self.quantile(T::from(0.75).unwrap()));
+                let d = k * (q3 - q1);
+                (q1 - d, q3 + d)
+            }
+        };
+        self.data.iter().map(|&x| x < lo || x > hi).collect()
+    }
+
+    /// Copies `elems` into a new vector after checking the invariants of
+    /// `policy`, reporting the first one that fails: emptiness, then the
+    /// length, then the index of the first non-finite element. Panics if
+    /// `policy.multiple_of` is `Some(0)`.
+    pub fn try_new(elems: &[T], policy: ValidationPolicy) -> Result<Vector<T>, VectorError> {
+        if policy.non_empty && elems.is_empty() {
+            return Err(VectorError::EmptyInput);
+        }
+        if let Some(multiple) = policy.multiple_of {
+            if multiple == 0 {
+                panic!("Length multiple should be positive.");
+            }
+            if !elems.len().is_multiple_of(multiple) {
+                return Err(VectorError::LengthNotMultiple { len: elems.len(), multiple });
+            }
+        }
+        if policy.finite {
+            if let Some(index) = elems.iter().position(|x| !x.is_finite()) {
+                return Err(VectorError::NonFinite { index });
+            }
+        }
+        Ok(Vector::<T> { data: elems.to_vec() })
+    }
+
+    /// Returns the vector scaled to unit length. A zero vector is returned
+    /// unchanged.
+    pub fn normalized(&self) -> Vector<T> {
+        let norm = self.norm();
+        if norm == T::zero() {
+            return self.clone();
+        }
+        Vector::<T> { data: self.data.iter().map(|&x| x / norm).collect() }
+    }
+
+    /// Sum of the absolute values of the elements.
+    pub fn l1_norm(&self) -> T {
+        self.data.iter().fold(T::zero(), |s, &x| s + x.abs())
+    }
+
+    /// Returns the vector divided by its L1 norm, so that non-negative
+    /// weights become a probability distribution summing to 1. A zero vector
+    /// is returned unchanged.
+    pub fn normalize_l1(&self) -> Vector<T> {
+        let norm = self.l1_norm();
+        if norm == T::zero() {
+            return self.clone();
+        }
+        Vector::<T> { data: self.data.iter().map(|&x| x / norm).collect() }
+    }
+
+    /// Orthogonal projection of the vector onto the direction of `other`,
+    /// `(self.other / other.other) * other`. Projecting onto a zero vector
+    /// gives a zero vector.
+    pub fn project_onto(&self, other: &Vector<T>) -> Result<Vector<T>, String> {
+        let num = self.dot(other)?;
+        let den = other.dot(other)?;
+        if den == T::zero() {
+            return Ok(Vector::new(self.len(), T::zero()));
+        }
+        Ok(other.clone() * (num / den))
+    }
+
+    /// Orthogonal projection of the vector onto the hyperplane through the
+    /// origin with the given `normal`, `self - project_onto(normal)`, which
+    /// removes the component along the normal. The normal need not be unit
+    /// length. A zero normal leaves the vector unchanged.
+    pub fn project_onto_plane(&self, normal: &Vector<T>) -> Result<Vector<T>, String> {
+        Ok(self - &self.project_onto(normal)?)
+    }
+
+    /// Reflection of the vector about the hyperplane with the given unit
+    /// `normal`, `self - 2 (self.normal) normal`, e.g. the direction of a ray
+    /// bouncing off a surface. The normal is not normalized here; for one
+    /// that is not unit length, subtract twice `project_onto(normal)`
+    /// instead.
+    pub fn reflect(&self, normal: &Vector<T>) -> Result<Vector<T>, String> {
+        let d = self.dot(normal)?;
+        let two = T::one() + T::one();
+        let data = self.data.iter().zip(normal.data.iter()).map(|(&x, &n)| x - two * d * n);
+        Ok(Vector::<T> { data: data.collect() })
+    }
+
+    /// Direction of the vector after refraction through a surface with the
+    /// given unit `normal`, by Snell's law, or `None` on total internal
+    /// reflection. As in GLSL, the vector is a unit incident direction, the
+    /// normal points back against it and `eta` is the ratio `n1 / n2` of the
+    /// refractive index being left to the one being entered.
+    pub fn refract(&self, normal: &Vector<T>, eta: T) -> Result<Option<Vector<T>>, String> {
+        let d = self.dot(normal)?;
+        let k = T::one() - eta * eta * (T::one() - d * d);
+        if k < T::zero() {
+            return Ok(None);
+        }
+        let c = eta * d + k.sqrt();
+        let data = self.data.iter().zip(normal.data.iter()).map(|(&x, &n)| eta * x - c * n);
+        Ok(Some(Vector::<T> { data: data.collect() }))
+    }
+
+    /// Linear interpolation `(1 - t) * self + t * other`, which is `self` at
+    /// `t = 0` and `other` at `t = 1`.
+    pub fn lerp(&self, other: &Vector<T>, t: T) -> Result<Vector<T>, String> {
+        if self.len() != other.len() {
+            return Err("Vectors are not conformable for interpolation.".to_string());
+        }
+        let data = self.data.iter().zip(other.data.iter()).map(|(&a, &b)| a + (b - a) * t);
+        Ok(Vector::<T> { data: data.collect() })
+    }
+
+    /// Spherical linear interpolation, which moves from `self` to `other` at
+    /// constant angular speed along the great circle through them. Intended
+    /// for unit vectors. When the vectors are nearly parallel or
+    /// antiparallel, or one of them is zero, the sine of the angle between
+    /// them is too small to divide by and this falls back to `lerp`.
+    pub fn slerp(&self, other: &Vector<T>, t: T) -> Result<Vector<T>, String> {
+        let den = self.norm() * other.norm();
+        let cos = self.dot(other)? / den;
+        let theta = cos.max(-T::one()).min(T::one()).acos();
+        let sin = theta.sin();
+        if den == T::zero() || sin < T::epsilon().sqrt() {
+            return self.lerp(other, t);
+        }
+        let wa = ((T::one() - t) * theta).sin() / sin;
+        let wb = (t * theta).sin() / sin;
+        let data = self.data.iter().zip(other.data.iter()).map(|(&a, &b)| a * wa + b * wb);
+        Ok(Vector::<T> { data: data.collect() })
+    }
+
+    /// Resamples the vector to `new_len` points by linear interpolation,
+    /// treating both the old and the new points as evenly spaced over
+    /// `[0, 1]`. A single point takes the first element. Panics if the vector
+    /// is empty and `new_len` is not 0.
+    pub fn resample(&self, new_len: usize) -> Vector<T> {
+        if new_len == 0 {
+            return Vector::<T> { data: Vec::new() };
+        }
+        if self.data.is_empty() {
+            panic!("Cannot resample an empty vector.");
+        }
+        let xp = unit_grid(self.len());
+        interp::interp(&unit_grid(new_len), &xp, self).unwrap()
+    }
+
+    /// Cosine of the angle between the two vectors, `self.other / (|self| |other|)`.
+    /// The result is NaN if either vector is zero.
+    pub fn cosine_similarity(&self, other: &Vector<T>) -> Result<T, String> {
+        Ok(self.dot(other)? / (self.norm() * other.norm()))
+    }
+
+    /// `1 - cosine_similarity`, which is 0 for vectors pointing the same way,
+    /// 1 for orthogonal ones and 2 for opposite ones. Note that this is not a
+    /// true metric since the triangle inequality does not hold, so it should
+    /// not be used where an algorithm relies on one.
+    pub fn cosine_distance(&self, other: &Vector<T>) -> Result<T, String> {
+        Ok(T::one() - self.cosine_similarity(other)?)
+    }
+
+    /// Shannon entropy `-sum(p * ln(p))` in nats, treating the vector as a
+    /// probability distribution. Zero probabilities contribute 0. The elements
+    /// are assumed to be non-negative and to sum to 1; divide by `sum()` first
+    /// to normalize weights.
+    pub fn entropy(&self) -> T {
+        let mut sum = T::zero();
+        for &p in &self.data {
+            if p != T::zero() {
+                sum = sum - p * p.ln();
+            }
+        }
+        sum
+    }
+
+    /// Kullback-Leibler divergence `sum(p * ln(p / q))` of `other` (q) from
+    /// `self` (p) in nats, with the same assumptions as `entropy`. Terms with
+    /// `p == 0` contribute 0, while `q == 0` where `p > 0` gives infinity.
+    pub fn kl_divergence(&self, other: &Vector<T>) -> Result<T, String> {
+        if self.len() != other.len() {
+            return Err("Vectors are not conformable for KL divergence.".to_string());
+        }
+        let mut sum = T::zero();
+        for (&p, &q) in self.data.iter().zip(other.data.iter()) {
+            if p != T::zero() {
+                sum = sum + p * (p / q).ln();
+            }
+        }
+        Ok(sum)
+    }
+
+    /// Scales every element by its standard deviation, `x[i] / sqrt(variances[i])`,
+    /// which is Mahalanobis whitening for a diagonal covariance. Fails if a
+    /// variance is not positive, since such a dimension cannot be scaled.
+    pub fn whiten_diag(&self, variances: &Vector<T>) -> Result<Vector<T>, String> {
+        if self.len() != variances.len() {
+            return Err("Vectors are not conformable for whitening.".to_string());
+        }
+        if let Some(i) = variances.data.iter().position(|&v| v <= T::zero() || v.is_nan()) {
+            return Err(format!("Variance at index {} is not positive.", i));
+        }
+        let data = self.data.iter().zip(variances.data.iter()).map(|(&x, &v)| x / v.sqrt());
+        Ok(Vector::<T> { data: data.collect() })
+    }
+
+    /// Dot product which avoids overflow in the intermediate products by first
+    /// scaling each vector by a power of two that brings its largest magnitude
+    /// near 1, and undoing the scaling afterwards. Powers of two scale exactly,
+    /// so this helps when the products would overflow (e.g. `f32` values above
+    /// about `1e19`) but the result itself is representable. It costs an extra
+    /// pass over both vectors and can lose precision in elements far smaller
+    /// than the largest one.
+    pub fn dot_scaled(&self, other: &Vector<T>) -> Result<T, String> {
+        if self.len() != other.len() {
+            return Err("Vectors are not conformable for dot product.".to_string());
+        }
+        let (ea, eb) = (max_exponent(&self.data), max_exponent(&other.data));
+        let two = T::one() + T::one();
+        let (sa, sb) = (two.powi(-ea), two.powi(-eb));
+        let mut sum = T::zero();
+        for (&x, &y) in self.data.iter().zip(other.data.iter()) {
+            sum = sum + (x * sa) * (y * sb);
+        }
+        Ok(sum * two.powi(ea + eb))
+    }
+
+    /// Dot product over the positions where neither vector is NaN, treating
+    /// NaN as a missing value. Returns the sum together with the number of
+    /// pairs that contributed to it.
+    pub fn masked_dot(&self, other: &Vector<T>) -> Result<(T, usize), String> {
+        if self.len() != other.len() {
+            return Err("Vectors are not conformable for dot product.".to_string());
+        }
+        let (mut sum, mut count) = (T::zero(), 0);
+        for (&x, &y) in self.data.iter().zip(other.data.iter()) {
+            if !x.is_nan() && !y.is_nan() {
+                sum = sum + x * y;
+                count += 1;
+            }
+        }
+        Ok((sum, count))
+    }
+
+    /// Solves `diag(self) * x = rhs`, treating the vector as the diagonal of
+    /// a diagonal matrix, i.e. `x[i] = rhs[i] / self[i]`. Fails on a zero
+    /// diagonal entry, since the system is then singular.
+    pub fn solve_diagonal(&self, rhs: &Vector<T>) -> Result<Vector<T>, String> {
+        if self.len() != rhs.len() {
+            return Err("Vectors are not conformable for solving.".to_string());
+        }
+        if let Some(i) = self.data.iter().position(|&d| d == T::zero()) {
+            return Err(format!("Diagonal entry at index {} is zero.", i));
+        }
+        let data = rhs.data.iter().zip(self.data.iter()).map(|(&b, &d)| b / d);
+        Ok(Vector::<T> { data: data.collect() })
+    }
+
+    /// Householder reflection mapping the vector onto a multiple of the first
+    /// basis vector. Returns `(v, beta)` with `v[0] == 1` such that
+    /// `(I - beta * v * v^T) * self == [-sign(self[0]) * |self|, 0, ..., 0]`.
+    /// As in LAPACK, the sign is chosen opposite to `self[0]` so that
+    /// computing `v` involves no cancellation. If the elements after the
+    /// first are all zero, `beta` is 0 and the reflection is the identity.
+    pub fn householder(&self) -> (Vector<T>, T) {
+        let mut v = self.clone();
+        if v.data.is_empty() {
+            return (v, T::zero());
+        }
+        let alpha = self[0];
+        let tail = VectorView::from_slice(&self.data[1..]).norm();
+        v[0] = T::one();
+        if tail == T::zero() {
+            return (v, T::zero());
+        }
+        let norm = alpha.hypot(tail);
+        let beta = if alpha >= T::zero() { -norm } else { norm };
+        let scale = T::one() / (alpha - beta);
+        for x in v.data[1..].iter_mut() {
+            *x = *x * scale;
+        }
+        (v, (beta - alpha) / beta)
+    }
+
+    /// Largest absolute value of the elements, i.e. the infinity norm,
+    /// without allocating. 0 for an empty vector. NaNs are ignored unless
+    /// every element is NaN, which also gives 0.
+    pub fn max_abs(&self) -> T {
+        self.data.iter().fold(T::zero(), |m, &x| m.max(x.abs()))
+    }
+
+    /// Compares the vectors elementwise like `numpy.isclose`: `a` is close to
+    /// `b` if `|a - b| <= atol + rtol * |b|`. The test is not symmetric since
+    /// the relative tolerance scales with `other`. Infinities are only close
+    /// to an infinity of the same sign, and NaNs are close to each other only
+    /// if `equal_nan` is set.
+    pub fn isclose(&self, other: &Vector<T>, rtol: T, atol: T, equal_nan: bool) -> Result<Vec<bool>, String> {
+        if self.len() != other.len() {
+            return Err("Vectors are not conformable for comparison.".to_string());
+        }
+        Ok(self.data.iter().zip(other.data.iter())
+            .map(|(&a, &b)| is_close(a, b, rtol, atol, equal_nan))
+            .collect())
+    }
+
+    /// Whether every element is close to the matching element of `other`, as
+    /// in `isclose` with NaNs never close. Stops at the first element that is
+    /// not, and is false if the lengths differ.
+    pub fn allclose(&self, other: &Vector<T>, rtol: T, atol: T) -> bool {
+        self.len() == other.len() &&
+            self.data.iter().zip(other.data.iter()).all(|(&a, &b)| is_close(a, b, rtol, atol, false))
+    }
+
+    /// Converts every element from degrees to radians.
+    pub fn to_radians(&self) -> Vector<T> {
+        Vector::<T> { data: self.data.iter().map(|&x| x.to_radians()).collect() }
+    }
+
+    /// Converts every element from radians to degrees.
+    pub fn to_degrees(&self) -> Vector<T> {
+        Vector::<T> { data: self.data.iter().map(|&x| x.to_degrees()).collect() }
+    }
+
+    /// Maps every angle in radians to the equivalent one in `(-pi, pi]`.
+    pub fn wrap_to_pi(&self) -> Vector<T> {
+        let (pi, two_pi) = pi_and_two_pi::<T>();
+        let wrap = |x: T| {
+            let r = x - two_pi * ((x + pi) / two_pi).floor();
+            if r <= -pi { r + two_pi } else { r }
+        };
+        Vector::<T> { data: self.data.iter().map(|&x| wrap(x)).collect() }
+    }
+
+    /// Maps every angle in radians to the equivalent one in `[0, 2 pi)`.
+    pub fn wrap_to_2pi(&self) -> Vector<T> {
+        let (_, two_pi) = pi_and_two_pi::<T>();
+        let wrap = |x: T| {
+            let r = x - two_pi * (x / two_pi).floor();
+            // Tiny negative angles round up to 2 pi itself.
+            if r >= two_pi { T::zero() } else { r }
+        };
+        Vector::<T> { data: self.data.iter().map(|&x| wrap(x)).collect() }
+    }
+
+    /// Removes the jumps of more than `pi` between consecutive phases by
+    /// adding multiples of `2 pi`, like `numpy.unwrap`.
+    pub fn unwrap_phase(&self) -> Vector<T> {
+        let (pi, two_pi) = pi_and_two_pi::<T>();
+        let mut v = self.clone();
+        let mut correction = T::zero();
+        for i in 1..self.len() {
+            let d = self[i] - self[i - 1];
+            if d.abs() >= pi {
+                let mut wrapped = (d + pi) - two_pi * ((d + pi) / two_pi).floor() - pi;
+                if wrapped == -pi && d > T::zero() {
+                    wrapped = pi;
+                }
+                correction = correction + wrapped - d;
+            }
+            v[i] = self[i] + correction;
+        }
+        v
+    }
+
+    /// Hyperbolic sine of every element. Overflows to infinity for large magnitudes.
+    pub fn sinh(&self) -> Vector<T> {
+        let mut v = self.clone();
+        v.sinh_mut();
+        v
+    }
+
+    /// In-place variant of `sinh`.
+    pub fn sinh_mut(&mut self) {
+        self.map_in_place(|x| x.sinh());
+    }
+
+    /// Hyperbolic cosine of every element. Overflows to infinity for large magnitudes.
+    pub fn cosh(&self) -> Vector<T> {
+        let mut v = self.clone();
+        v.cosh_mut();
+        v
+    }
+
+    /// In-place variant of `cosh`.
+    pub fn cosh_mut(&mut self) {
+        self.map_in_place(|x| x.cosh());
+    }
+
+    /// Hyperbolic tangent of every element, which saturates at -1 and 1.
+    pub fn tanh_v(&self) -> Vector<T> {
+        let mut v = self.clone();
+        v.tanh_v_mut();
+        v
+    }
+
+    /// In-place variant of `tanh_v`.
+    pub fn tanh_v_mut(&mut self) {
+        self.map_in_place(|x| x.tanh());
+    }
+
+    /// Inverse hyperbolic sine of every element.
+    pub fn asinh(&self) -> Vector<T> {
+        let mut v = self.clone();
+        v.asinh_mut();
+        v
+    }
+
+    /// In-place variant of `asinh`.
+    pub fn asinh_mut(&mut self) {
+        self.map_in_place(|x| x.asinh());
+    }
+
+    /// Inverse hyperbolic cosine of every element. NaN below 1, where it is
+    /// undefined.
+    pub fn acosh(&self) -> Vector<T> {
+        let mut v = self.clone();
+        v.acosh_mut();
+        v
+    }
+
+    /// In-place variant of `acosh`.
+    pub fn acosh_mut(&mut self) {
+        self.map_in_place(|x| x.acosh());
+    }
+
+    /// Inverse hyperbolic tangent of every element. Infinite at -1 and 1 and
+    /// NaN beyond them.
+    pub fn atanh(&self) -> Vector<T> {
+        let mut v = self.clone();
+        v.atanh_mut();
+        v
+    }
+
+    /// In-place variant of `atanh`.
+    pub fn atanh_mut(&mut self) {
+        self.map_in_place(|x| x.atanh());
+    }
+
+    /// Error function of every element, see `special::erf`.
+    pub fn erf(&self) -> Vector<T> {
+        let mut v = self.clone();
+        v.map_in_place(special::erf);
+        v
+    }
+
+    /// Complementary error function of every element, see `special::erfc`.
+    pub fn erfc(&self) -> Vector<T> {
+        let mut v = self.clone();
+        v.map_in_place(special::erfc);
+        v
+    }
+
+    /// Logarithm of the absolute value of the gamma function of every
+    /// element, see `special::lgamma`.
+    pub fn lgamma(&self) -> Vector<T> {
+        let mut v = self.clone();
+        v.map_in_place(special::lgamma);
+        v
+    }
+
+    /// Applies `f` to every element in place. The elementwise functions all
+    /// go through here, so a vectorized loop only needs adding once.
+    fn map_in_place<F: Fn(T) -> T>(&mut self, f: F) {
+        for x in self.data.iter_mut() {
+            *x = f(*x);
+        }
+    }
+
+    /// Energy of the signal, the sum of the squared elements. 0 for an empty
+    /// vector.
+    pub fn energy(&self) -> T {
+        self.dot(self).unwrap()
+    }
+
+    /// Average power of the signal, `energy() / len()`. NaN for an empty
+    /// vector.
+    pub fn power(&self) -> T {
+        self.energy() / T::from(self.len()).unwrap()
+    }
+
+    /// Root mean square of the signal, `sqrt(power())`. NaN for an empty
+    /// vector.
+    pub fn rms(&self) -> T {
+        self.power().sqrt()
+    }
+
+    /// Correlation of the signal with itself shifted by `lag`, normalized by
+    /// the zero lag value: `sum(x[i] * x[i + lag]) / sum(x[i]^2)`. The result
+    /// is 1 at lag 0 and 0 for lags beyond the signal. The mean is not
+    /// removed; subtract it first for the statistical autocorrelation. An
+    /// empty or zero signal gives NaN.
+    pub fn autocorrelation(&self, lag: usize) -> T {
+        let n = self.len();
+        let overlap = if lag < n {
+            let head = VectorView::from_slice(&self.data[..n - lag]);
+            head.dot(&VectorView::from_slice(&self.data[lag..])).unwrap()
+        } else {
+            T::zero()
+        };
+        overlap / self.energy()
+    }
+
+    /// `autocorrelation` at every lag from 0 to `len() - 1`.
+    pub fn autocorrelation_all(&self) -> Vector<T> {
+        Vector::<T> { data: (0..self.len()).map(|lag| self.autocorrelation(lag)).collect() }
+    }
+
+    /// Pearson correlation of the two series over every sliding window of
+    /// `window` elements, giving `len - window + 1` values. Running sums are
+    /// updated as the window slides, so the cost does not depend on the
+    /// window size. A window over which either series is constant gives NaN.
+    /// Fails unless `2 <= window <= len`.
+    pub fn rolling_correlation(&self, other: &Vector<T>, window: usize) -> Result<Vector<T>, String> {
+        if self.len() != other.len() {
+            return Err("Vectors are not conformable for correlation.".to_string());
+        }
+        if window < 2 || window > self.len() {
+            return Err(format!("Window size {} should be between 2 and the vector length {}.",
+                               window, self.len()));
+        }
+        let (x, y) = (&self.data, &other.data);
+        let n = T::from(window).unwrap();
+        let (mut sx, mut sy, mut sxx, mut syy, mut sxy) =
+            (T::zero(), T::zero(), T::zero(), T::zero(), T::zero());
+        let mut out = Vec::with_capacity(x.len() - window + 1);
+        for i in 0..x.len() {
+            sx = sx + x[i];
+            sy = sy + y[i];
+            sxx = sxx + x[i] * x[i];
+            syy = syy + y[i] * y[i];
+            sxy = sxy + x[i] * y[i];
+            if i >= window {
+                let j = i - window;
+                sx = sx - x[j];
+                sy = sy - y[j];
+                sxx = sxx - x[j] * x[j];
+                syy = syy - y[j] * y[j];
+                sxy = sxy - x[j] * y[j];
+            }
+            if i + 1 >= window {
+                let cov = n * sxy - sx * sy;
+                let (vx, vy) = (n * sxx - sx * sx, n * syy - sy * sy);
+                out.push(cov / (vx * vy).sqrt());
+            }
+        }
+        Ok(Vector::<T> { data: out })
+    }
+
+    /// Huber loss of every element: `x^2 / 2` where `|x| <= delta` and
+    /// `delta * (|x| - delta / 2)` beyond, so the two pieces meet smoothly.
+    /// NaNs stay NaN. Panics unless `delta` is positive, since the loss
+    /// degenerates to 0 everywhere for `delta == 0`.
+    pub fn huber(&self, delta: T) -> Vector<T> {
+        if delta <= T::zero() || delta.is_nan() {
+            panic!("Huber delta should be positive.");
+        }
+        let half = T::from(0.5).unwrap();
+        let data = self.data.iter().map(|&x| {
+            let a = x.abs();
+            if a <= delta { half * x * x } else { delta * (a - half * delta) }
+        });
+        Vector::<T> { data: data.collect() }
+    }
+}
+
+impl Vector<f64> {
+    /// Whether the vectors have the same length and bit-identical elements,
+    /// so that NaNs with the same payload are equal and `-0.0` differs from
+    /// `0.0`. Useful to check that serialization round-trips exactly.
+    pub fn bitwise_eq(&self, other: &Vector<f64>) -> bool {
+        self.len() == other.len()
+            && self.data.iter().zip(other.data.iter()).all(|(a, b)| a.to_bits() == b.to_bits())
+    }
+
+    /// Narrows every element to `f32` with an `as` cast, rounding to the
+    /// nearest `f32`. Finite values beyond the `f32` range become infinite.
+    pub fn to_f32(&self) -> Vector<f32> {
+        Vector::<f32> { data: self.data.iter().map(|&x| x as f32).collect() }
+    }
+
+    /// Narrows like `to_f32`, but finite values beyond the `f32` range are
+    /// clamped to `f32::MIN` or `f32::MAX` rather than becoming infinite.
+    /// Infinities and NaNs are kept.
+    pub fn to_f32_clamped(&self) -> Vector<f32> {
+        let narrow = |x: f64| {
+            let y = x as f32;
+            if y.is_infinite() && x.is_finite() { y.signum() * f32::MAX } else { y }
+        };
+        Vector::<f32> { data: self.data.iter().map(|&x| narrow(x)).collect() }
+    }
+}
+
+impl Vector<f32> {
+    /// Whether the vectors have the same length and bit-identical elements,
+    /// so that NaNs with the same payload are equal and `-0.0` differs from
+    /// `0.0`. Useful to check that serialization round-trips exactly.
+    pub fn bitwise_eq(&self, other: &Vector<f32>) -> bool {
+        self.len() == other.len()
+            && self.data.iter().zip(other.data.iter()).all(|(a, b)| a.to_bits() == b.to_bits())
+    }
+
+    /// Sum of the elements accumulated in `f64`. An `f32` accumulator loses
+    /// about one digit per factor of ten in length once it grows large
+    /// relative to the elements, so prefer this to `sum` for long vectors.
+    pub fn sum_f64(&self) -> f64 {
+        let (mut a, mut b) = (0.0f64, 0.0f64);
+        let mut pairs = self.data.chunks_exact(2);
+        for p in &mut pairs {
+            a += p[0] as f64;
+            b += p[1] as f64;
+        }
+        pairs.remainder().iter().fold(a + b, |s, &x| s + x as f64)
+    }
+
+    /// Dot product accumulated in `f64`, see `sum_f64`. Each product is exact
+    /// in `f64`, so only the accumulation rounds.
+    pub fn dot_f64(&self, other: &Vector<f32>) -> Result<f64, String> {
+        if self.len() != other.len() {
+            return Err("Vectors are not conformable for dot product.".to_string());
+        }
+        let (mut a, mut b) = (0.0f64, 0.0f64);
+        let mut pairs = self.data.chunks_exact(2).zip(other.data.chunks_exact(2));
+        for (x, y) in &mut pairs {
+            a += x[0] as f64 * y[0] as f64;
+            b += x[1] as f64 * y[1] as f64;
+        }
+        let tail = self.len() - self.len() % 2;
+        Ok(self.data[tail..].iter().zip(other.data[tail..].iter())
+            .fold(a + b, |s, (&x, &y)| s + x as f64 * y as f64))
+    }
+}
+
+fn pi_and_two_pi<T: Real>() -> (T, T) {
+    let pi = T::from(PI).unwrap();
+    (pi, pi + pi)
+}
+
+fn is_close<T: Real>(a: T, b: T, rtol: T, atol: T, equal_nan: bool) -> bool {
+    if a == b {
+        return true;
+    }
+    if a.is_nan() || b.is_nan() {
+        return equal_nan && a.is_nan() && b.is_nan();
+    }
+    a.is_finite() && b.is_finite() && (a - b).abs() <= atol + rtol * b.abs()
+}
+
+/// Binary exponent of the largest finite magnitude in `data`, or 0 if there
+/// is none.
+fn max_exponent<T: Real>(data: &[T]) -> i32 {
+    let max = data.iter()
+        .filter(|x| x.is_finite())
+        .fold(T::zero(), |m, &x| m.max(x.abs()));
+    if max == T::zero() {
+        return 0;
+    }
+    // For subnormals 2^-e would overflow, so stop at the largest power of
+    // two that is finite.
+    let limit = T::max_value().log2().ceil().to_i32().unwrap() - 1;
+    max.log2().floor().to_i32().unwrap().max(-limit)
+}
+
+/// `n` evenly spaced points from 0 to 1, or just 0 if `n` is 1.
+fn unit_grid<T: Real>(n: usize) -> Vector<T> {
+    let last = T::from(n.max(2) - 1).unwrap();
+    Vector::<T> { data: (0..n).map(|i| T::from(i).unwrap() / last).collect() }
+}
+
+/// Total order on partially ordered numbers which sorts NaNs last.
+fn nan_last_cmp<T: PartialOrd>(a: &T, b: &T) -> Ordering {
+    match a.partial_cmp(b) {
+        Some(ord) => ord,
+        None => is_nan(a).cmp(&is_nan(b)),
+    }
+}
+
+#[inline]
+fn is_nan<T: PartialOrd>(x: &T) -> bool {
+    x.partial_cmp(x).is_none()
+}
+
+fn check_stride(len: usize, offset: usize, stride: usize) -> Result<(), String> {
+    if stride == 0 {
+        return Err("Stride should be greater than 0.".to_string());
+    }
+    if offset >= len {
+        return Err(format!("Offset {} out of bounds for vector of length {}.", offset, len));
+    }
+    Ok(())
+}
+
+/// Parses the elements of a vector literal which starts `offset` characters
+/// into the whole input, so that errors point at the right place.
+pub(crate) fn parse_elems<T: FromStr>(s: &str, offset: usize) -> Result<Vec<T>, String> {
+    let mut start = offset + s[..s.len() - s.trim_start().len()].chars().count();
+    let mut body = s.trim();
+    if body.starts_with('[') {
+        if !body.ends_with(']') {
+            return Err(format!("Missing closing ']' at offset {}.", start + body.chars().count()));
+        }
+        body = &body[1..body.len() - 1];
+        start += 1;
+    }
+    let mut tokens = Vec::new();
+    let mut begin = None;
+    for (n, (i, c)) in body.char_indices().enumerate() {
+        if c == ',' || c.is_whitespace() {
+            if let Some((n0, i0)) = begin.take() {
+                tokens.push((n0, &body[i0..i]));
+            }
+        } else if begin.is_none() {
+            begin = Some((n, i));
+        }
+    }
+    if let Some((n0, i0)) = begin {
+        tokens.push((n0, &body[i0..]));
+    }
+    tokens.into_iter()
+        .map(|(n, tok)| {
+            tok.parse().map_err(|_| format!("Invalid number '{}' at offset {}.", tok, start + n))
+        })
+        .collect()
+}
+
+/// Creates a `Vector` which takes ownership of `data` without copying it.
+pub fn from_vec<T: Number>(data: Vec<T>) -> Vector<T> {
+    Vector::<T> { data }
+}
+
+pub fn from_elems<T: Number>(elems: &[T]) -> Vector<T> {
+    let mut v = Vector::<T> { data: Vec::with_capacity(elems.len()) };
+    v.data.extend_from_slice(elems);
+    v
+}
+
+/// Product of a `1 x n` row and an `n x 1` column, which is their dot product.
+pub fn row_times_col<T: Number>(row: &Vector<T>, col: &Vector<T>) -> Result<T, String> {
+    row.dot(col)
+}
+
+/// Product of an `m x 1` column and a `1 x n` row, i.e. the outer product,
+/// returned as its `m` rows of length `n`.
+pub fn col_times_row<T: Number>(col: &Vector<T>, row: &Vector<T>) -> Vec<Vector<T>> {
+    col.data.iter().map(|&x| row.clone() * x).collect()
+}
+
+/// Barycentric coordinates `(u, v, w)` of `p` with respect to the triangle
+/// `abc`, so that `p = u a + v b + w c` and `u + v + w = 1`. The point is
+/// inside the triangle when all three are non-negative. In more than two
+/// dimensions `p` is first projected onto the plane of the triangle. Errors
+/// if the lengths differ or the triangle is degenerate, i.e. its vertices
+/// are collinear to within rounding.
+pub fn barycentric<T: Real>(p: &Vector<T>, a: &Vector<T>, b: &Vector<T>,
+                            c: &Vector<T>) -> Result<(T, T, T), String> {
+    if [b, c, p].iter().any(|v| v.len() != a.len()) {
+        return Err("Vectors are not conformable for barycentric coordinates.".to_string());
+    }
+    let (e0, e1, e2) = (b.clone() - a.clone(), c.clone() - a.clone(), p.clone() - a.clone());
+    let (d00, d01, d11) = (e0.dot(&e0)?, e0.dot(&e1)?, e1.dot(&e1)?);
+    let (d20, d21) = (e2.dot(&e0)?, e2.dot(&e1)?);
+    let den = d00 * d11 - d01 * d01;
+    if den <= T::epsilon() * d00 * d11 {
+        return Err("Triangle is degenerate.".to_string());
+    }
+    let v = (d11 * d20 - d01 * d21) / den;
+    let w = (d00 * d21 - d01 * d20) / den;
+    Ok((T::one() - v - w, v, w))
+}
+
+/// Creates a Hann window of length `n`. The symmetric form is returned unless
+/// `periodic` is set, in which case the window suits FFT use.
+pub fn hann<T: Real>(n: usize, periodic: bool) -> Vector<T> {
+    cosine_window(n, periodic, &[0.5, 0.5])
+}
+
+/// Creates a Hamming window of length `n`. The symmetric form is returned
+/// unless `periodic` is set, in which case the window suits FFT use.
+pub fn hamming<T: Real>(n: usize, periodic: bool) -> Vector<T> {
+    cosine_window(n, periodic, &[0.54, 0.46])
+}
+
+/// Creates a Blackman window of length `n`. The symmetric form is returned
+/// unless `periodic` is set, in which case the window suits FFT use.
+pub fn blackman<T: Real>(n: usize, periodic: bool) -> Vector<T> {
+    cosine_window(n, periodic, &[0.42, 0.5, 0.08])
+}
+
+/// Creates a cosine annealing schedule of `steps` learning rates,
+/// `lr_min + (lr_max - lr_min) * (1 + cos(pi * k / (steps - 1))) / 2` for
+/// step `k`. The first step is exactly `lr_max` and the last exactly
+/// `lr_min`; a single step is `lr_max`.
+pub fn cosine_schedule<T: Real>(steps: usize, lr_max: T, lr_min: T) -> Vector<T> {
+    let mut v = Vector::<T> { data: Vec::with_capacity(steps) };
+    for k in 0..steps {
+        v.data.push(if k == 0 {
+            lr_max
+        } else if k == steps - 1 {
+            lr_min
+        } else {
+            let c = (PI * k as f64 / (steps - 1) as f64).cos();
+            lr_min + (lr_max - lr_min) * T::from((1.0 + c) / 2.0).unwrap()
+        });
+    }
+    v
+}
+
+/// Generalized cosine window `sum((-1)^j * a[j] * cos(2 * pi * j * k / m))`
+/// where `m` is `n` for the periodic form and `n - 1` for the symmetric one.
+fn cosine_window<T: Real>(n: usize, periodic: bool, coeffs: &[f64]) -> Vector<T> {
+    if n <= 1 {
+        return Vector::new(n, T::one());
+    }
+    let m = if periodic { n } else { n - 1 } as f64;
+    let mut v = Vector::<T> { data: Vec::with_capacity(n) };
+    for k in 0..n {
+        let mut w = 0.0;
+        for (j, a) in coeffs.iter().enumerate() {
+            let term = a * (2.0 * PI * (j * k) as f64 / m).cos();
+            w += if j % 2 == 0 { term } else { -term };
+        }
+        v.data.push(T::from(w).unwrap());
+    }
+    v
+}
+
+/// Orthonormalizes `vectors` with the modified Gram-Schmidt process.
+///
+/// Vectors which are linearly dependent on the ones before them leave a
+/// residual of (nearly) zero norm, relative to their own norm, after the
+/// projections are removed. Those are skipped, so the result spans the same
+/// space as the input but may contain fewer vectors.
+pub fn gram_schmidt<T: Real>(vectors: &[Vector<T>]) -> Result<Vec<Vector<T>>, String> {
+    let tol = T::epsilon().sqrt();
+    let mut basis: Vec<Vector<T>> = Vec::with_capacity(vectors.len());
+    for v in vectors {
+        let mut w = v.clone();
+        for q in basis.iter() {
+            w = w.clone() - w.project_onto(q)?;
+        }
+        let norm = w.norm();
+        if norm > tol * v.norm() && norm > T::zero() {
+            basis.push(w.normalized());
+        }
+    }
+    Ok(basis)
+}
+
+/// The symmetric `n x n` matrix of dot products between the `n` vectors.
+/// Fails unless all the vectors have the same length.
+pub fn gram_matrix<T: Number>(vectors: &[Vector<T>]) -> Result<Matrix<T>, String> {
+    let n = vectors.len();
+    if vectors.iter().any(|v| v.len() != vectors[0].len()) {
+        return Err("Vectors should all have the same length.".to_string());
+    }
+    let mut g = Matrix::new(n, n, T::zero());
+    for i in 0..n {
+        for j in i..n {
+            let d = vectors[i].dot(&vectors[j])?;
+            g.set(i, j, d);
+            g.set(j, i, d);
+        }
+    }
+    Ok(g)
+}
+
+/// Determinant of the `gram_matrix` of the vectors. Its square root is the
+/// volume of the parallelepiped they span, and it is 1 for no vectors. It is
+/// zero when the vectors are linearly dependent, so a value that is tiny
+/// relative to the product of their squared norms means they are nearly so.
+pub fn gram_determinant<T: Real>(vectors: &[Vector<T>]) -> Result<T, String> {
+    Ok(gram_matrix(vectors)?.determinant())
 }
@@ -0,0 +1,325 @@
+//! Borrowed views into vectors and slices
+//!
+//! `VectorView` and `VectorViewMut` wrap a borrowed slice so the vector
+//! operations can be run on part of a larger buffer without copying it. Views
+//! and vectors can be mixed freely in arithmetic through the `VectorOps` trait.
+//! `StridedView` and `StridedViewMut` do the same for every `n`th element of
+//! a buffer, such as one channel of interleaved audio.
+//!
+//! # Examples
+//! ```
+//! use numrs::vector;
+//! use numrs::vector::VectorOps;
+//!
+//! let v = vector::from_elems(&[1.0, 2.0, 3.0, 4.0]);
+//! let head = v.view(0..2);
+//! let tail = v.view(2..4);
+//!
+//! let res = head + &tail; // owned Vector with [4.0, 6.0]
+//! assert_eq!(res.sum(), 10.0);
+//! assert_eq!(head.dot(&tail).unwrap(), 11.0);
+//! ```
+
+use std::ops::{Index, IndexMut, Add, Sub, Mul, AddAssign, SubAssign, MulAssign};
+use std::iter::StepBy;
+use std::slice::Iter;
+use common::{Number, Real};
+use vector::{Vector, VectorOps};
+
+/// Read-only view of a contiguous run of elements
+#[derive(Clone, Copy)]
+pub struct VectorView<'a, T: Number + 'a> {
+    data: &'a [T],
+}
+
+/// Mutable view of a contiguous run of elements
+pub struct VectorViewMut<'a, T: Number + 'a> {
+    data: &'a mut [T],
+}
+
+impl<'a, T: Number> VectorView<'a, T> {
+    /// Creates a view over the elements of `slice`.
+    pub fn from_slice(slice: &'a [T]) -> VectorView<'a, T> {
+        VectorView { data: slice }
+    }
+}
+
+impl<'a, T: Number> VectorViewMut<'a, T> {
+    /// Creates a mutable view over the elements of `slice`.
+    pub fn from_slice(slice: &'a mut [T]) -> VectorViewMut<'a, T> {
+        VectorViewMut { data: slice }
+    }
+
+    /// Returns the elements of the view as a mutable slice.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.data
+    }
+
+    /// Returns a read-only view of the same elements.
+    pub fn as_view(&self) -> VectorView<'_, T> {
+        VectorView { data: self.data }
+    }
+}
+
+impl<'a, T: Number> VectorOps<T> for VectorView<'a, T> {
+    #[inline]
+    fn as_slice(&self) -> &[T] {
+        self.data
+    }
+}
+
+impl<'a, T: Number> VectorOps<T> for VectorViewMut<'a, T> {
+    #[inline]
+    fn as_slice(&self) -> &[T] {
+        self.data
+    }
+}
+
+impl<'a, T: Number> Index<usize> for VectorView<'a, T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: usize) -> &T {
+        &self.data[index]
+    }
+}
+
+impl<'a, T: Number> Index<usize> for VectorViewMut<'a, T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: usize) -> &T {
+        &self.data[index]
+    }
+}
+
+impl<'a, T: Number> IndexMut<usize> for VectorViewMut<'a, T> {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.data[index]
+    }
+}
+
+/// Applies `f` to every pair of elements of `lhs` and `rhs`.
+fn elementwise<T: Number, F: Fn(T, T) -> T>(lhs: &[T], rhs: &[T], f: F, op: &str) -> Vector<T> {
+    if lhs.len() != rhs.len() {
+        panic!("Vectors are not conformable for {}.", op);
+    }
+    Vector { data: lhs.iter().zip(rhs.iter()).map(|(&a, &b)| f(a, b)).collect() }
+}
+
+macro_rules! elementwise_op {
+    ($Op:ident, $method:ident, $op:tt, $name:expr) => {
+        impl<'a, 'b, T: Number> $Op<&'b VectorView<'a, T>> for Vector<T> {
+            type Output = Vector<T>;
+
+            fn $method(self, rhs: &'b VectorView<'a, T>) -> Vector<T> {
+                elementwise(&self.data, rhs.data, |a, b| a $op b, $name)
+            }
+        }
+
+        impl<'a, 'b, T: Number> $Op<&'b VectorViewMut<'a, T>> for Vector<T> {
+            type Output = Vector<T>;
+
+            fn $method(self, rhs: &'b VectorViewMut<'a, T>) -> Vector<T> {
+                elementwise(&self.data, rhs.data, |a, b| a $op b, $name)
+            }
+        }
+
+        impl<'a, 'b, T: Number, V: VectorOps<T>> $Op<&'b V> for VectorView<'a, T> {
+            type Output = Vector<T>;
+
+            fn $method(self, rhs: &'b V) -> Vector<T> {
+                elementwise(self.data, rhs.as_slice(), |a, b| a $op b, $name)
+            }
+        }
+
+        impl<'a, 'b, T: Number, V: VectorOps<T>> $Op<&'b V> for VectorViewMut<'a, T> {
+            type Output = Vector<T>;
+
+            fn $method(self, rhs: &'b V) -> Vector<T> {
+                elementwise(self.data, rhs.as_slice(), |a, b| a $op b, $name)
+            }
+        }
+    }
+}
+
+elementwise_op!(Add, add, +, "addition");
+elementwise_op!(Sub, sub, -, "subtraction");
+elementwise_op!(Mul, mul, *, "multiplication");
+
+macro_rules! elementwise_assign_op {
+    ($Op:ident, $method:ident, $op:tt, $name:expr) => {
+        impl<'a, 'b, T: Number, V: VectorOps<T>> $Op<&'b V> for VectorViewMut<'a, T> {
+            fn $method(&mut self, rhs: &'b V) {
+                let rhs = rhs.as_slice();
+                if self.data.len() != rhs.len() {
+                    panic!("Vectors are not conformable for {}.", $name);
+                }
+                for (a, &b) in self.data.iter_mut().zip(rhs.iter()) {
+                    *a = *a $op b;
+                }
+            }
+        }
+    }
+}
+
+elementwise_assign_op!(AddAssign, add_assign, +, "addition");
+elementwise_assign_op!(SubAssign, sub_assign, -, "subtraction");
+elementwise_assign_op!(MulAssign, mul_assign, *, "multiplication");
+
+/// Read-only view of every `stride`th element of a slice
+#[derive(Clone, Copy)]
+pub struct StridedView<'a, T: Number + 'a> {
+    data: &'a [T],
+    stride: usize,
+}
+
+/// Mutable view of every `stride`th element of a slice
+pub struct StridedViewMut<'a, T: Number + 'a> {
+    data: &'a mut [T],
+    stride: usize,
+}
+
+impl<'a, T: Number> From<VectorView<'a, T>> for StridedView<'a, T> {
+    fn from(view: VectorView<'a, T>) -> StridedView<'a, T> {
+        StridedView::from_slice(view.data, 1)
+    }
+}
+
+fn strided_len(len: usize, stride: usize) -> usize {
+    len.div_ceil(stride)
+}
+
+impl<'a, T: Number> StridedView<'a, T> {
+    /// Creates a view over the elements of `slice` at indices `0, stride,
+    /// 2 * stride, ...`. Panics if `stride` is 0.
+    pub fn from_slice(slice: &'a [T], stride: usize) -> StridedView<'a, T> {
+        if stride == 0 {
+            panic!("Stride should be greater than 0.");
+        }
+        StridedView { data: slice, stride }
+    }
+
+    /// Returns the number of elements in the view.
+    #[inline]
+    pub fn len(&self) -> usize {
+        strided_len(self.data.len(), self.stride)
+    }
+
+    /// Returns `true` if the view has no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Iterates over the elements of the view.
+    pub fn iter(&self) -> StepBy<Iter<'a, T>> {
+        self.data.iter().step_by(self.stride)
+    }
+
+    /// Sum of all the elements.
+    pub fn sum(&self) -> T {
+        self.iter().fold(T::zero(), |sum, &x| sum + x)
+    }
+
+    /// Arithmetic mean of the elements, which is NaN for an empty view.
+    pub fn mean(&self) -> T
+        where T: Real
+    {
+        self.sum() / T::from(self.len()).unwrap()
+    }
+
+    /// Smallest element, or `None` for an empty view.
+    pub fn min(&self) -> Option<T>
+        where T: PartialOrd
+    {
+        self.to_vector().min()
+    }
+
+    /// Largest element, or `None` for an empty view.
+    pub fn max(&self) -> Option<T>
+        where T: PartialOrd
+    {
+        self.to_vector().max()
+    }
+
+    /// Dot product with another view of the same length. Contiguous views can
+    /// be passed in through `StridedView::from`.
+    pub fn dot(&self, other: &StridedView<T>) -> Result<T, String> {
+        if self.len() != other.len() {
+            return Err("Vectors are not conformable for dot product.".to_string());
+        }
+        Ok(self.iter().zip(other.iter()).fold(T::zero(), |sum, (&a, &b)| sum + a * b))
+    }
+
+    /// Copies the elements of the view into a new `Vector`.
+    pub fn to_vector(&self) -> Vector<T> {
+        Vector { data: self.iter().cloned().collect() }
+    }
+}
+
+impl<'a, T: Number> Index<usize> for StridedView<'a, T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: usize) -> &T {
+        &self.data[index * self.stride]
+    }
+}
+
+impl<'a, T: Number> StridedViewMut<'a, T> {
+    /// Creates a mutable view over the elements of `slice` at indices `0,
+    /// stride, 2 * stride, ...`. Panics if `stride` is 0.
+    pub fn from_slice(slice: &'a mut [T], stride: usize) -> StridedViewMut<'a, T> {
+        if stride == 0 {
+            panic!("Stride should be greater than 0.");
+        }
+        StridedViewMut { data: slice, stride }
+    }
+
+    /// Returns the number of elements in the view.
+    #[inline]
+    pub fn len(&self) -> usize {
+        strided_len(self.data.len(), self.stride)
+    }
+
+    /// Returns `true` if the view has no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns a read-only view of the same elements.
+    pub fn as_view(&self) -> StridedView<'_, T> {
+        StridedView { data: self.data, stride: self.stride }
+    }
+
+    /// Writes the elements of `src` into the strided positions.
+    pub fn copy_from<V: VectorOps<T>>(&mut self, src: &V) -> Result<(), String> {
+        let src = src.as_slice();
+        if self.len() != src.len() {
+            return Err("Vectors are not conformable for copying.".to_string());
+        }
+        for (dst, &x) in self.data.iter_mut().step_by(self.stride).zip(src.iter()) {
+            *dst = x;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, T: Number> Index<usize> for StridedViewMut<'a, T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: usize) -> &T {
+        &self.data[index * self.stride]
+    }
+}
+
+impl<'a, T: Number> IndexMut<usize> for StridedViewMut<'a, T> {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.data[index * self.stride]
+    }
+}
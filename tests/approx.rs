@@ -0,0 +1,39 @@
+#![cfg(feature = "approx")]
+#[macro_use]
+extern crate approx;
+extern crate numrs;
+
+use numrs::matrix;
+use numrs::vector;
+
+#[test]
+fn test_approx_vector() {
+    let a = vector::from_elems(&[1.0f64, 2.0, 3.0]);
+    let nearly = vector::from_elems(&[1.0 + 1e-12, 2.0, 3.0 - 1e-12]);
+    let different = vector::from_elems(&[1.0, 2.1, 3.0]);
+
+    assert_abs_diff_eq!(a, a.clone());
+    assert_relative_eq!(a, nearly, epsilon = 1e-10);
+    assert_ulps_ne!(a, nearly);
+    assert_ulps_eq!(a, nearly, max_ulps = 10000);
+    assert_relative_ne!(a, different);
+    assert_abs_diff_eq!(a, different, epsilon = 0.2);
+    assert_abs_diff_ne!(a, vector::from_elems(&[1.0, 2.0]), epsilon = 100.0);
+
+    let b = vector::from_elems(&[0.1f32 + 0.2, 0.7]);
+    assert_relative_eq!(b, vector::from_elems(&[0.3f32, 0.7]));
+    assert_relative_ne!(b, vector::from_elems(&[0.3f32, 0.7, 0.0]));
+}
+
+#[test]
+fn test_approx_matrix() {
+    let m = matrix::from_elems(2, 2, &[1.0f64, 2.0, 3.0, 4.0]);
+    let nearly = matrix::from_elems(2, 2, &[1.0 + f64::EPSILON, 2.0, 3.0, 4.0]);
+    assert_relative_eq!(m, nearly);
+    assert_ulps_eq!(m, nearly);
+    assert_relative_ne!(m, matrix::from_elems(2, 2, &[1.0, 2.0, 3.0, 4.5]));
+
+    let mut reshaped = m.clone();
+    reshaped.reshape(1, 4);
+    assert_abs_diff_ne!(m, reshaped, epsilon = 100.0);
+}
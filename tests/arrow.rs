@@ -0,0 +1,80 @@
+#![cfg(feature = "arrow")]
+extern crate arrow;
+extern crate numrs;
+extern crate parquet;
+
+use std::env;
+use std::fs::{self, File};
+use std::sync::Arc;
+use arrow::array::{ArrayRef, Float32Array, Float64Array, Int32Array, StringArray};
+use arrow::record_batch::RecordBatch;
+use numrs::io::arrow::{self as numrs_arrow, NullHandling};
+use numrs::matrix;
+use numrs::matrix::Matrix;
+use numrs::vector;
+use numrs::vector::Vector;
+use parquet::arrow::ArrowWriter;
+
+fn batch() -> RecordBatch {
+    RecordBatch::try_from_iter(vec![
+        ("x", Arc::new(Float64Array::from(vec![1.0, 2.0, 3.0])) as ArrayRef),
+        ("n", Arc::new(Int32Array::from(vec![4, 5, 6])) as ArrayRef),
+        ("label", Arc::new(StringArray::from(vec!["a", "b", "c"])) as ArrayRef),
+        ("y", Arc::new(Float32Array::from(vec![Some(0.5), None, Some(1.5)])) as ArrayRef),
+    ]).unwrap()
+}
+
+#[test]
+fn test_arrow_vector_round_trip() {
+    let v = vector::from_elems(&[1.0, -2.5, 3.0]);
+    let array = v.to_arrow();
+    assert_eq!(array.values().to_vec(), v.data);
+    assert!(Vector::from_arrow(&array, NullHandling::Error).unwrap() == v);
+
+    let ptr = v.as_ptr();
+    let moved = v.into_arrow();
+    assert_eq!(moved.values().as_ptr(), ptr);
+}
+
+#[test]
+fn test_arrow_vector_nulls() {
+    let array = Float64Array::from(vec![Some(1.0), None, Some(3.0)]);
+    let v = Vector::from_arrow(&array, NullHandling::Nan).unwrap();
+    assert_eq!((v[0], v[2]), (1.0, 3.0));
+    assert!(v[1].is_nan());
+    assert_eq!(Vector::from_arrow(&array, NullHandling::Error).err().unwrap(),
+               "Arrow array has a null at index 1.");
+}
+
+#[test]
+fn test_arrow_matrix_from_record_batch() {
+    let m = Matrix::from_record_batch(&batch(), &["n", "x"], NullHandling::Error).unwrap();
+    assert!(m == matrix::from_elems(3, 2, &[4.0, 1.0, 5.0, 2.0, 6.0, 3.0]));
+
+    let m = Matrix::from_record_batch(&batch(), &["y"], NullHandling::Nan).unwrap();
+    assert!(m.get(1, 0).is_nan());
+    assert_eq!(Matrix::from_record_batch(&batch(), &["x", "y"], NullHandling::Error).err().unwrap(),
+               "Column 'y' has a null at row 1.");
+    assert_eq!(Matrix::from_record_batch(&batch(), &["x", "label"], NullHandling::Nan).err().unwrap(),
+               "Column 'label' has non-numeric type Utf8.");
+    assert_eq!(Matrix::from_record_batch(&batch(), &["z"], NullHandling::Nan).err().unwrap(),
+               "No column named 'z'.");
+}
+
+#[test]
+fn test_arrow_read_parquet_column() {
+    let path = env::temp_dir().join(format!("numrs_arrow_{}.parquet", std::process::id()));
+    let b = batch();
+    let mut writer = ArrowWriter::try_new(File::create(&path).unwrap(), b.schema(), None).unwrap();
+    writer.write(&b).unwrap();
+    writer.write(&b).unwrap();
+    writer.close().unwrap();
+
+    let x = numrs_arrow::read_parquet_column(&path, "x", NullHandling::Error).unwrap();
+    assert_eq!(x.data, vec![1.0, 2.0, 3.0, 1.0, 2.0, 3.0]);
+    let n = numrs_arrow::read_parquet_column(&path, "n", NullHandling::Error).unwrap();
+    assert_eq!(n.data, vec![4.0, 5.0, 6.0, 4.0, 5.0, 6.0]);
+    assert!(numrs_arrow::read_parquet_column(&path, "label", NullHandling::Nan).is_err());
+    assert!(numrs_arrow::read_parquet_column(&path, "y", NullHandling::Error).is_err());
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,16 @@
+extern crate numrs;
+use numrs::complex;
+use numrs::complex::Complex;
+
+#[test]
+fn test_hermitian_dot() {
+    let a = complex::from_elems(&[Complex::new(1.0, 1.0), Complex::new(0.0, 2.0)]);
+    let b = complex::from_elems(&[Complex::new(2.0, -1.0), Complex::new(1.0, 1.0)]);
+    // (1+i)(2+i) + (2i)(1-i) = (1+3i) + (2+2i)
+    assert_eq!(a.hermitian_dot(&b).unwrap(), Complex::new(3.0, 5.0));
+    // The inner product of a vector with itself is its real squared norm
+    assert_eq!(a.hermitian_dot(&a).unwrap(), Complex::new(6.0, 0.0));
+
+    let c = complex::from_elems(&[Complex::new(1.0, 0.0)]);
+    assert!(a.hermitian_dot(&c).is_err());
+}
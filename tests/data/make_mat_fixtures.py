@@ -0,0 +1,59 @@
+#!/usr/bin/env python3
+"""Writes the MAT-file fixtures used by tests/mat.rs.
+
+The files follow the level 5 MAT-file layout written by Octave's `save -v6`
+and `save -v7` (compressed) and by MATLAB, including small data elements for
+short names and doubles stored with a narrower integer type.
+"""
+import struct
+import zlib
+
+MI_INT8, MI_UINT8, MI_INT32, MI_UINT32 = 1, 2, 5, 6
+MI_SINGLE, MI_DOUBLE, MI_UINT16 = 7, 9, 4
+MI_MATRIX, MI_COMPRESSED = 14, 15
+MX_CHAR, MX_DOUBLE, MX_SINGLE = 4, 6, 7
+
+
+def header(e):
+    text = b'MATLAB 5.0 MAT-file, Platform: GNU Octave, Created on: Thu Jan 01 00:00:00 2020'
+    return text.ljust(116, b' ') + b'\0' * 8 + struct.pack(e + 'H', 0x0100) + \
+        (b'IM' if e == '<' else b'MI')
+
+
+def element(e, ty, data):
+    if len(data) <= 4:
+        return struct.pack(e + 'I', (len(data) << 16) | ty) + data.ljust(4, b'\0')
+    pad = (8 - len(data) % 8) % 8
+    return struct.pack(e + 'II', ty, len(data)) + data + b'\0' * pad
+
+
+def matrix(e, name, cls, rows, cols, ty, fmt, values):
+    body = element(e, MI_UINT32, struct.pack(e + 'II', cls, 0))
+    body += element(e, MI_INT32, struct.pack(e + 'ii', rows, cols))
+    body += element(e, MI_INT8, name.encode())
+    body += element(e, ty, struct.pack(e + fmt * len(values), *values))
+    return struct.pack(e + 'II', MI_MATRIX, len(body)) + body
+
+
+def compressed(e, elem):
+    data = zlib.compress(elem)
+    return struct.pack(e + 'II', MI_COMPRESSED, len(data)) + data
+
+
+def variables(e):
+    # Column major data.
+    return [
+        matrix(e, 'A', MX_DOUBLE, 2, 3, MI_DOUBLE, 'd', [1, 4, 2, 5, 3, 6.5]),
+        matrix(e, 'v', MX_SINGLE, 1, 3, MI_SINGLE, 'f', [1.5, -2, 3]),
+        matrix(e, 'counts', MX_DOUBLE, 3, 1, MI_UINT8, 'B', [1, 2, 250]),
+    ]
+
+
+with open('octave_v6.mat', 'wb') as f:
+    f.write(header('<') + b''.join(variables('<')))
+with open('octave_v7.mat', 'wb') as f:
+    f.write(header('<') + b''.join(compressed('<', v) for v in variables('<')))
+with open('big_endian.mat', 'wb') as f:
+    f.write(header('>') + b''.join(variables('>')))
+with open('char.mat', 'wb') as f:
+    f.write(header('<') + matrix('<', 'label', MX_CHAR, 1, 2, MI_UINT16, 'H', [104, 105]))
@@ -0,0 +1,187 @@
+extern crate numrs;
+use numrs::decomposition;
+use numrs::matrix;
+use numrs::matrix::Matrix;
+use numrs::vector;
+
+fn sample_data() -> Matrix<f64> {
+    // Points spread along the direction (3, 4) with a little orthogonal noise
+    let mut elems = Vec::new();
+    let noise = [0.1, -0.1, -0.1, 0.1, 0.1, -0.1, -0.1, 0.1];
+    for i in 0..8 {
+        let t = i as f64 - 3.5;
+        elems.push(3.0 * t - 4.0 * noise[i] + 1.0);
+        elems.push(4.0 * t + 3.0 * noise[i] - 2.0);
+        elems.push(0.5 * noise[(i + 3) % 8]);
+    }
+    matrix::from_elems(8, 3, &elems)
+}
+
+fn reconstruction_error(data: &Matrix<f64>, n_components: usize) -> f64 {
+    let pca = decomposition::pca(data, n_components).unwrap();
+    let approx = pca.transformed.clone() * pca.components.clone();
+    let mut err = 0.0;
+    for i in 0..data.num_rows() {
+        for j in 0..data.num_cols() {
+            let d = data.get(i, j) - (approx.get(i, j) + pca.mean[j]);
+            err += d * d;
+        }
+    }
+    err
+}
+
+#[test]
+fn test_pca_dominant_direction() {
+    let pca = decomposition::pca(&sample_data(), 1).unwrap();
+    assert!((pca.components.get(0, 0) - 0.6).abs() < 1e-2);
+    assert!((pca.components.get(0, 1) - 0.8).abs() < 1e-2);
+    assert!(pca.components.get(0, 2).abs() < 1e-2);
+}
+
+#[test]
+fn test_pca_explained_variance() {
+    let data = sample_data();
+    let pca = decomposition::pca(&data, 3).unwrap();
+    let n = data.num_rows() as f64;
+    let mut total = 0.0;
+    for j in 0..data.num_cols() {
+        let mean = (0..data.num_rows()).map(|i| data.get(i, j)).sum::<f64>() / n;
+        total += (0..data.num_rows()).map(|i| (data.get(i, j) - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    }
+    let explained: f64 = pca.explained_variance.data.iter().sum();
+    assert!((explained - total).abs() < 1e-9);
+    assert!(pca.explained_variance[0] >= pca.explained_variance[1]);
+    assert!(pca.explained_variance[1] >= pca.explained_variance[2]);
+}
+
+#[test]
+fn test_pca_orthonormal_components() {
+    let pca = decomposition::pca(&sample_data(), 3).unwrap();
+    for a in 0..3 {
+        for b in 0..3 {
+            let dot: f64 = pca.components[a].iter().zip(pca.components[b].iter()).map(|(x, y)| x * y).sum();
+            let expected = if a == b { 1.0 } else { 0.0 };
+            assert!((dot - expected).abs() < 1e-9);
+        }
+    }
+}
+
+#[test]
+fn test_pca_reconstruction_and_transform() {
+    let data = sample_data();
+    let errors: Vec<f64> = (1..4).map(|k| reconstruction_error(&data, k)).collect();
+    assert!(errors[0] > errors[1]);
+    assert!(errors[1] > errors[2]);
+    assert!(errors[2] < 1e-12);
+
+    let pca = decomposition::pca(&data, 2).unwrap();
+    let projected = pca.transform(&data).unwrap();
+    for i in 0..data.num_rows() {
+        for j in 0..2 {
+            assert!((projected.get(i, j) - pca.transformed.get(i, j)).abs() < 1e-12);
+        }
+    }
+    assert!(pca.transform(&matrix::from_elems(1, 2, &[1.0, 2.0])).is_err());
+}
+
+#[test]
+fn test_pca_too_many_components() {
+    assert!(decomposition::pca(&sample_data(), 4).is_err());
+}
+
+#[test]
+fn test_pca_non_finite() {
+    let mut data = sample_data();
+    data.set(2, 1, f64::NAN);
+    assert_eq!(decomposition::pca(&data, 1).err().unwrap(), "The data should be finite.");
+    data.set(2, 1, f64::INFINITY);
+    assert!(decomposition::pca(&data, 1).is_err());
+    let huge = matrix::from_elems(2, 1, &[-1e300f64, 1e300]);
+    assert_eq!(decomposition::pca(&huge, 1).err().unwrap(), "The covariance of the data overflows.");
+}
+
+fn stacked(rows: &[[f64; 3]]) -> Matrix<f64> {
+    let elems: Vec<f64> = rows.iter().flat_map(|r| r.iter().cloned()).collect();
+    matrix::from_elems(rows.len(), 3, &elems)
+}
+
+const QR_ROWS: [[f64; 3]; 6] = [
+    [2.0, -1.0, 0.5],
+    [1.0, 3.0, -2.0],
+    [0.0, 1.0, 4.0],
+    [-1.5, 2.0, 1.0],
+    [3.0, 0.0, -1.0],
+    [1.0, 1.0, 1.0],
+];
+
+#[test]
+fn test_qr_factorize() {
+    let a = stacked(&QR_ROWS);
+    let qr = a.qr_factorize();
+    let (q, r) = (qr.q().clone(), qr.r().clone());
+    let product = q.clone() * r.clone();
+    let mut qt = q.clone();
+    qt.transpose();
+    let gram = qt * q;
+    for i in 0..6 {
+        for j in 0..3 {
+            assert!((product.get(i, j) - a.get(i, j)).abs() < 1e-12);
+            if i > j {
+                assert!(r.get(i, j).abs() < 1e-12);
+            }
+        }
+        for j in 0..6 {
+            let expected = if i == j { 1.0 } else { 0.0 };
+            assert!((gram.get(i, j) - expected).abs() < 1e-12);
+        }
+    }
+
+    // A consistent system is solved exactly.
+    let x = vector::from_elems(&[1.0, -2.0, 0.5]);
+    let b = a.mul_vec(&x).unwrap();
+    let solved = qr.solve(&b).unwrap();
+    for j in 0..3 {
+        assert!((solved[j] - x[j]).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_qr_update_append_row() {
+    let b_all = [1.0, -0.5, 2.0, 0.25, 3.0, -1.0];
+    let mut qr = decomposition::QrFactorization::new(3);
+    for k in 1..7 {
+        qr.update_append_row(&vector::from_elems(&QR_ROWS[k - 1])).unwrap();
+        let fresh = stacked(&QR_ROWS[..k]).qr_factorize();
+        let b = vector::from_elems(&b_all[..k]);
+        if k < 3 {
+            // Still underdetermined: neither factorization can solve.
+            assert!(qr.solve(&b).is_err() && fresh.solve(&b).is_err());
+            continue;
+        }
+        let (x, y) = (qr.solve(&b).unwrap(), fresh.solve(&b).unwrap());
+        for j in 0..3 {
+            assert!((x[j] - y[j]).abs() < 1e-12);
+        }
+    }
+
+    // The 6-row least-squares solution satisfies the normal equations.
+    let a = stacked(&QR_ROWS);
+    let x = qr.solve(&vector::from_elems(&b_all)).unwrap();
+    let residual = a.mul_vec(&x).unwrap() - vector::from_elems(&b_all);
+    for j in 0..3 {
+        let g: f64 = (0..6).map(|i| a.get(i, j) * residual[i]).sum();
+        assert!(g.abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_qr_errors() {
+    let mut qr = stacked(&QR_ROWS[..4]).qr_factorize();
+    assert!(qr.update_append_row(&vector::from_elems(&[1.0, 2.0])).is_err());
+    assert_eq!(qr.r().num_rows(), 4);
+    assert!(qr.solve(&vector::from_elems(&[1.0, 2.0, 3.0])).is_err());
+
+    let dependent = stacked(&[[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [0.0, 1.0, 1.0], [1.0, 3.0, 4.0]]);
+    let err = dependent.qr_factorize().solve(&vector::from_elems(&[1.0; 4])).unwrap_err();
+    assert_eq!(err, "The matrix is rank deficient.");
+}
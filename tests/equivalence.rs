@@ -0,0 +1,61 @@
+//! Checks the vector operations against straightforward scalar loops for
+//! every length from 0 to 9 and for a larger pseudo-random input, so that any
+//! platform-specific code path can be validated on the target it runs on.
+extern crate numrs;
+use numrs::vector;
+use numrs::vector::{Vector, VectorOps};
+
+/// Deterministic pseudo-random values in [-50, 50).
+fn values(n: usize, seed: u64) -> Vec<f64> {
+    let mut state = seed;
+    (0..n)
+        .map(|_| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 53) as f64 * 100.0 - 50.0
+        })
+        .collect()
+}
+
+fn check(n: usize, seed: u64) {
+    let (a, b) = (values(n, seed), values(n, seed + 1));
+    let (va, vb) = (vector::from_elems(&a), vector::from_elems(&b));
+
+    let sum: Vec<f64> = a.iter().zip(&b).map(|(x, y)| x + y).collect();
+    let diff: Vec<f64> = a.iter().zip(&b).map(|(x, y)| x - y).collect();
+    let prod: Vec<f64> = a.iter().zip(&b).map(|(x, y)| x * y).collect();
+    let neg: Vec<f64> = a.iter().map(|x| -x).collect();
+    let scaled: Vec<f64> = a.iter().map(|x| 3.5 * x).collect();
+    assert_eq!((va.clone() + vb.clone()).data, sum);
+    assert_eq!((va.clone() - vb.clone()).data, diff);
+    assert_eq!((va.clone() * vb.clone()).data, prod);
+    assert_eq!((-va.clone()).data, neg);
+    assert_eq!((va.clone() * 3.5).data, scaled);
+    assert_eq!(va.square().data, a.iter().map(|x| x * x).collect::<Vec<f64>>());
+
+    let dot: f64 = prod.iter().sum();
+    let total: f64 = a.iter().sum();
+    assert!((va.dot(&vb).unwrap() - dot).abs() <= 1e-9 * (1.0 + dot.abs()));
+    assert!((va.sum() - total).abs() <= 1e-9 * (1.0 + total.abs()));
+
+    assert!(va == vector::from_elems(&a));
+    if n > 0 {
+        let mut changed = a.clone();
+        changed[n - 1] += 1.0;
+        assert!(va != vector::from_elems(&changed));
+    }
+}
+
+#[test]
+fn test_equivalence_short_lengths() {
+    for n in 0..10 {
+        check(n, n as u64);
+    }
+}
+
+#[test]
+fn test_equivalence_random() {
+    for seed in 0..8 {
+        check(1000 + 37 * seed as usize, 100 + seed);
+    }
+    assert_eq!(Vector::new(0, 0.0).sum(), 0.0);
+}
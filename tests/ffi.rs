@@ -0,0 +1,59 @@
+#![cfg(feature = "ffi")]
+extern crate numrs;
+use std::ptr;
+use numrs::ffi::*;
+
+#[test]
+fn test_ffi_vector_ops() {
+    let (x, y) = ([1.0, 2.0, 3.0], [4.0, 5.0, 6.0]);
+    unsafe {
+        let a = numrs_vector_new(x.as_ptr(), 3);
+        let b = numrs_vector_new(y.as_ptr(), 3);
+        assert_eq!(numrs_vector_len(a), 3);
+
+        let mut status = NumrsStatus::NullPointer;
+        let sum = numrs_vector_add(a, b, &mut status);
+        assert_eq!(status, NumrsStatus::Ok);
+        let mut out = 0.0;
+        assert_eq!(numrs_vector_get(sum, 2, &mut out), NumrsStatus::Ok);
+        assert_eq!(out, 9.0);
+        assert_eq!(*numrs_vector_data(sum), 5.0);
+
+        let diff = numrs_vector_sub(b, a, ptr::null_mut());
+        assert_eq!(numrs_vector_get(diff, 0, &mut out), NumrsStatus::Ok);
+        assert_eq!(out, 3.0);
+
+        assert_eq!(numrs_vector_dot(a, b, &mut out), NumrsStatus::Ok);
+        assert_eq!(out, 32.0);
+
+        for v in [a, b, sum, diff].iter() {
+            numrs_vector_free(*v);
+        }
+    }
+}
+
+#[test]
+fn test_ffi_errors() {
+    let x = [1.0, 2.0, 3.0];
+    unsafe {
+        let a = numrs_vector_new(x.as_ptr(), 3);
+        let b = numrs_vector_new(x.as_ptr(), 2);
+        assert!(numrs_vector_new(ptr::null(), 3).is_null());
+
+        let mut status = NumrsStatus::Ok;
+        assert!(numrs_vector_add(a, b, &mut status).is_null());
+        assert_eq!(status, NumrsStatus::LengthMismatch);
+        assert!(numrs_vector_add(a, ptr::null(), &mut status).is_null());
+        assert_eq!(status, NumrsStatus::NullPointer);
+
+        let mut out = 0.0;
+        assert_eq!(numrs_vector_dot(a, b, &mut out), NumrsStatus::LengthMismatch);
+        assert_eq!(numrs_vector_dot(a, a, ptr::null_mut()), NumrsStatus::NullPointer);
+        assert_eq!(numrs_vector_get(a, 3, &mut out), NumrsStatus::IndexOutOfBounds);
+        assert_eq!(numrs_vector_len(ptr::null()), 0);
+
+        numrs_vector_free(a);
+        numrs_vector_free(b);
+        numrs_vector_free(ptr::null_mut());
+    }
+}
@@ -0,0 +1,77 @@
+extern crate numrs;
+
+use numrs::format::{FormatOptions, Notation};
+use numrs::matrix;
+use numrs::vector;
+
+fn opts(precision: usize, notation: Notation) -> FormatOptions {
+    FormatOptions { precision, notation, ..FormatOptions::default() }
+}
+
+#[test]
+fn test_format_vector_notations() {
+    let v = vector::from_elems(&[1.5, -25000.0, 0.00123]);
+    assert_eq!(v.to_string_with(opts(2, Notation::Fixed)), "[1.50, -25000.00, 0.00]");
+    assert_eq!(v.to_string_with(opts(2, Notation::Scientific)), "[1.50e0, -2.50e4, 1.23e-3]");
+    assert_eq!(v.to_string_with(opts(2, Notation::Engineering)), "[1.50e0, -25.00e3, 1.23e-3]");
+    assert_eq!(vector::from_elems(&[1, 2]).to_string_with(opts(0, Notation::Fixed)), "[1, 2]");
+    assert_eq!(vector::from_elems::<f64>(&[]).to_string_with(FormatOptions::default()), "[]");
+}
+
+#[test]
+fn test_format_value_engineering() {
+    let o = opts(1, Notation::Engineering);
+    assert_eq!(o.format_value(0.0), "0.0e0");
+    assert_eq!(o.format_value(999.96), "1.0e3");
+    assert_eq!(o.format_value(0.0000123), "12.3e-6");
+    assert_eq!(o.format_value(f64::INFINITY), "inf");
+    assert_eq!(o.format_value(f64::NEG_INFINITY), "-inf");
+    assert_eq!(o.format_value(f64::NAN), "NaN");
+}
+
+#[test]
+fn test_format_vector_separator_and_indices() {
+    let v = vector::from_elems(&[1.0, 2.0]);
+    let o = FormatOptions { precision: 1, separator: " ".to_string(), show_indices: true,
+                            ..FormatOptions::default() };
+    assert_eq!(v.to_string_with(o), "[0: 1.0 1: 2.0]");
+}
+
+#[test]
+fn test_format_vector_elides() {
+    let v = vector::from_vec((0..10000).collect::<Vec<i32>>());
+    let o = FormatOptions { precision: 0, max_elements: Some(5), ..FormatOptions::default() };
+    assert_eq!(v.to_string_with(o.clone()), "[0, 1, 2, ..., 9998, 9999]");
+    let o = FormatOptions { show_indices: true, ..o };
+    assert_eq!(v.to_string_with(o), "[0: 0, 1: 1, 2: 2, ..., 9998: 9998, 9999: 9999]");
+    let o = FormatOptions { max_elements: None, ..FormatOptions::default() };
+    assert_eq!(v.to_string_with(o).matches(", ").count(), 9999);
+}
+
+#[test]
+fn test_format_extreme_precision() {
+    let v = vector::from_elems(&[1.0 / 3.0, 2.0e300, -1.0e-300]);
+    for &n in &[Notation::Fixed, Notation::Scientific, Notation::Engineering] {
+        assert!(!v.to_string_with(opts(0, n)).is_empty());
+        assert!(v.to_string_with(opts(200, n)).len() > 600);
+    }
+}
+
+#[test]
+fn test_format_matrix_aligned() {
+    let m = matrix::from_elems(2, 2, &[1.0, 2.0, 30.0, -4.0]);
+    assert_eq!(m.to_string_with(opts(1, Notation::Fixed)), "[ 1.0,  2.0]\n[30.0, -4.0]");
+
+    let o = FormatOptions { precision: 0, show_indices: true, ..FormatOptions::default() };
+    let m = matrix::from_elems(11, 1, &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]);
+    let text = m.to_string_with(o);
+    assert!(text.starts_with(" 0: [ 1]\n"));
+    assert!(text.ends_with("10: [11]"));
+}
+
+#[test]
+fn test_format_matrix_elides() {
+    let m = matrix::from_elems(4, 4, &(0..16).collect::<Vec<i32>>());
+    let o = FormatOptions { precision: 0, max_elements: Some(2), ..FormatOptions::default() };
+    assert_eq!(m.to_string_with(o), "[  0, ...,   3]\n...\n[ 12, ...,  15]");
+}
@@ -0,0 +1,133 @@
+extern crate numrs;
+use numrs::half::{self, Bf16Vector, F16Vector};
+use numrs::vector;
+use numrs::vector::VectorOps;
+
+#[test]
+fn test_f16_round_trip() {
+    let values = [0.0f32, -0.0, 1.0, -2.5, 1365.0 / 4096.0, 65504.0, -65504.0, 1.0 / 16384.0];
+    let h = F16Vector::from_vector(&vector::from_elems(&values));
+    assert_eq!(h.bits[..4], [0x0000, 0x8000, 0x3c00, 0xc100]);
+    assert_eq!(h.bits[5], 0x7bff);
+    assert_eq!(h.to_vector().data, values.to_vec());
+    assert_eq!(h.get(2), 1.0);
+    assert_eq!(h.len(), 8);
+
+    // Every half float survives widening and narrowing again.
+    for bits in 0..=u16::MAX {
+        let x = half::f16_to_f32(bits);
+        if x.is_nan() {
+            assert!(half::f16_to_f32(half::f32_to_f16(x)).is_nan());
+        } else {
+            assert_eq!(half::f32_to_f16(x), bits);
+        }
+    }
+}
+
+#[test]
+fn test_f16_subnormals() {
+    let smallest = 2.0f32.powi(-24);
+    assert_eq!(half::f16_to_f32(0x0001), smallest);
+    assert_eq!(half::f16_to_f32(0x03ff), 1023.0 * smallest);
+    assert_eq!(half::f16_to_f32(0x8001), -smallest);
+    assert_eq!(half::f32_to_f16(smallest), 0x0001);
+    // Halfway between 0 and the smallest subnormal rounds to even, i.e. 0,
+    // while anything above it rounds up.
+    assert_eq!(half::f32_to_f16(smallest / 2.0), 0x0000);
+    assert_eq!(half::f32_to_f16(smallest * 0.75), 0x0001);
+    assert_eq!(half::f32_to_f16(smallest * 1.5), 0x0002);
+    assert_eq!(half::f32_to_f16(1e-10), 0x0000);
+    assert_eq!(half::f32_to_f16(-1e-10), 0x8000);
+}
+
+#[test]
+fn test_f16_rounding_and_overflow() {
+    // 1 + 2^-11 is halfway between 1 and the next half float.
+    assert_eq!(half::f32_to_f16(1.0 + 2.0f32.powi(-11)), 0x3c00);
+    assert_eq!(half::f32_to_f16(1.0 + 3.0 * 2.0f32.powi(-11)), 0x3c02);
+    assert_eq!(half::f32_to_f16(65519.0), 0x7bff);
+    assert_eq!(half::f32_to_f16(65520.0), 0x7c00);
+    assert_eq!(half::f32_to_f16(1e10), 0x7c00);
+    assert_eq!(half::f32_to_f16(-1e10), 0xfc00);
+}
+
+#[test]
+fn test_f16_special_values() {
+    assert_eq!(half::f32_to_f16(f32::INFINITY), 0x7c00);
+    assert_eq!(half::f32_to_f16(f32::NEG_INFINITY), 0xfc00);
+    assert_eq!(half::f16_to_f32(0x7c00), f32::INFINITY);
+    assert_eq!(half::f16_to_f32(0xfc00), f32::NEG_INFINITY);
+    let nan = half::f32_to_f16(f32::NAN);
+    assert_eq!(nan & 0x7c00, 0x7c00);
+    assert!(nan & 0x3ff != 0);
+    assert!(half::f16_to_f32(nan).is_nan());
+    // A NaN whose payload lies only in the dropped bits stays a NaN.
+    assert!(half::f16_to_f32(half::f32_to_f16(f32::from_bits(0x7f80_0001))).is_nan());
+}
+
+#[test]
+fn test_f16_dot() {
+    let mut h = F16Vector::from_vector(&vector::from_elems(&[1.0, 2.0, 3.0]));
+    h.set(2, 0.5);
+    let x = vector::from_elems(&[4.0f32, 5.0, 6.0]);
+    assert_eq!(h.dot_f32(&x).unwrap(), 17.0);
+    assert!(h.dot_f32(&vector::from_elems(&[1.0])).is_err());
+    assert!(F16Vector::from_bits(vec![]).is_empty());
+}
+
+#[test]
+fn test_bf16_round_trip() {
+    let values = [0.0f32, -0.0, 1.0, -2.5, 3.0e38, 1.0e-39, f32::INFINITY, f32::NEG_INFINITY];
+    let b = Bf16Vector::from_vector(&vector::from_elems(&values));
+    assert_eq!(b.bits[..4], [0x0000, 0x8000, 0x3f80, 0xc020]);
+    assert_eq!(b.to_vector().data, values.iter().map(|&x| half::bf16_to_f32(half::f32_to_bf16(x))).collect::<Vec<_>>());
+    assert_eq!(b.get(3), -2.5);
+    for bits in 0..=u16::MAX {
+        let x = half::bf16_to_f32(bits);
+        if !x.is_nan() {
+            assert_eq!(half::f32_to_bf16(x), bits);
+        }
+    }
+}
+
+#[test]
+fn test_bf16_rounding() {
+    // The bfloat16 values next to 1 are 1 - 2^-8 and 1 + 2^-7.
+    let ulp = 2.0f32.powi(-7);
+    assert_eq!(half::f32_to_bf16(1.0 + ulp * 0.25), 0x3f80);
+    assert_eq!(half::f32_to_bf16(1.0 + ulp * 0.75), 0x3f81);
+    // Ties go to the even mantissa.
+    assert_eq!(half::f32_to_bf16(1.0 + ulp * 0.5), 0x3f80);
+    assert_eq!(half::f32_to_bf16(1.0 + ulp * 1.5), 0x3f82);
+    assert_eq!(half::f32_to_bf16(-(1.0 + ulp * 1.5)), 0xbf82);
+    // Rounding up past the largest finite value gives infinity.
+    assert_eq!(half::f32_to_bf16(f32::MAX), 0x7f80);
+}
+
+#[test]
+fn test_bf16_nan() {
+    assert!(half::bf16_to_f32(half::f32_to_bf16(f32::NAN)).is_nan());
+    // A signalling NaN with only low payload bits would truncate to infinity.
+    let snan = f32::from_bits(0x7f80_0001);
+    assert!(half::bf16_to_f32(half::f32_to_bf16(snan)).is_nan());
+    assert_eq!(half::f32_to_bf16(-f32::NAN) & 0x8000, 0x8000);
+}
+
+#[test]
+fn test_bf16_compute() {
+    let xs: Vec<f32> = (0..100).map(|i| (i as f32 * 0.37).sin() * 10.0).collect();
+    let b = Bf16Vector::from_vector(&vector::from_elems(&xs));
+    let widened = b.to_vector();
+    let y = vector::from_vec((0..100).map(|i| i as f32 * 0.01).collect());
+
+    assert_eq!(b.dot_f32(&y).unwrap(), widened.dot(&y).unwrap());
+    assert_eq!(b.sum(), widened.data.iter().sum::<f32>());
+
+    let mut acc = y.clone();
+    b.axpy(2.0, &mut acc).unwrap();
+    for i in 0..100 {
+        assert_eq!(acc[i], y[i] + 2.0 * widened[i]);
+    }
+    assert!(b.axpy(1.0, &mut vector::from_elems(&[1.0])).is_err());
+    assert!(b.dot_f32(&vector::from_elems(&[1.0])).is_err());
+}
@@ -0,0 +1,129 @@
+extern crate numrs;
+
+use numrs::interp;
+use numrs::interp::{BoundaryCondition, CubicSpline};
+use numrs::vector;
+use numrs::vector::Vector;
+
+#[test]
+fn test_interp_linear() {
+    let xp = vector::from_elems(&[0.0, 1.0, 3.0]);
+    let fp = vector::from_elems(&[1.0, 3.0, -1.0]);
+    let x = vector::from_elems(&[-1.0, 0.0, 0.25, 1.0, 2.0, 3.0, 5.0]);
+    assert_eq!(interp::interp(&x, &xp, &fp).unwrap().data,
+               vec![1.0, 1.0, 1.5, 3.0, 1.0, -1.0, -1.0]);
+
+    let single = interp::interp(&x, &vector::from_elems(&[2.0]), &vector::from_elems(&[7.0]));
+    assert_eq!(single.unwrap().data, vec![7.0; 7]);
+
+    let nan = interp::interp(&vector::from_elems(&[f64::NAN]), &xp, &fp).unwrap();
+    assert!(nan[0].is_nan());
+}
+
+#[test]
+fn test_interp_invalid() {
+    let x = vector::from_elems(&[0.5]);
+    let empty = vector::from_elems::<f64>(&[]);
+    assert!(interp::interp(&x, &empty, &empty).is_err());
+    assert!(interp::interp(&x, &vector::from_elems(&[0.0, 1.0]), &vector::from_elems(&[1.0])).is_err());
+    let unsorted = vector::from_elems(&[0.0, 2.0, 1.0]);
+    assert!(interp::interp(&x, &unsorted, &unsorted).is_err());
+}
+
+fn sine_spline(bc: BoundaryCondition<f64>) -> (Vector<f64>, CubicSpline<f64>) {
+    let tau = 2.0 * ::std::f64::consts::PI;
+    let x = vector::from_vec((0..21).map(|i| tau * i as f64 / 20.0).collect());
+    let y = vector::from_vec(x.data.iter().map(|t| t.sin()).collect());
+    let spline = CubicSpline::new(&x, &y, bc).unwrap();
+    (x, spline)
+}
+
+#[test]
+fn test_cubic_spline_interpolates() {
+    let x = vector::from_elems(&[0.0f64, 0.5, 2.0, 3.0, 4.5]);
+    let y = vector::from_elems(&[1.0, -1.0, 2.0, 0.5, 3.0]);
+    for &bc in &[BoundaryCondition::Natural, BoundaryCondition::Clamped(1.0, -2.0)] {
+        let s = CubicSpline::new(&x, &y, bc).unwrap();
+        let at = s.eval(&x);
+        for i in 0..x.len() {
+            assert!((at[i] - y[i]).abs() < 1e-12);
+        }
+    }
+    let s = CubicSpline::new(&x, &y, BoundaryCondition::Clamped(1.0, -2.0)).unwrap();
+    let d = s.derivative(&vector::from_elems(&[0.0, 4.5]));
+    assert!((d[0] - 1.0).abs() < 1e-12 && (d[1] + 2.0).abs() < 1e-12);
+    assert!(s.eval(&vector::from_elems(&[f64::NAN]))[0].is_nan());
+}
+
+#[test]
+fn test_cubic_spline_continuity() {
+    let x = vector::from_elems(&[0.0f64, 1.0, 1.5, 3.0, 4.0]);
+    let y = vector::from_elems(&[0.0, 2.0, -1.0, 1.0, 0.0]);
+    let s = CubicSpline::new(&x, &y, BoundaryCondition::Natural).unwrap();
+    let e = 1e-6;
+    for &k in &x.data[1..4] {
+        let v = s.eval(&vector::from_elems(&[k - e, k + e]));
+        assert!((v[1] - v[0]).abs() < 1e-4);
+        let d = s.derivative(&vector::from_elems(&[k - 2.0 * e, k - e, k + e, k + 2.0 * e]));
+        // C1: the slopes just left and right of the knot agree.
+        assert!((d[2] - d[1]).abs() < 1e-4);
+        // C2: so do the finite-difference second derivatives.
+        let (left, right) = ((d[1] - d[0]) / e, (d[3] - d[2]) / e);
+        assert!((right - left).abs() < 1e-2 * (1.0 + left.abs()));
+    }
+}
+
+#[test]
+fn test_cubic_spline_sine() {
+    let tau = 2.0 * ::std::f64::consts::PI;
+    let fine = vector::from_vec((0..=1000).map(|i| tau * i as f64 / 1000.0).collect());
+    for &bc in &[BoundaryCondition::Natural, BoundaryCondition::Clamped(1.0, 1.0)] {
+        let (_, s) = sine_spline(bc);
+        let (v, d) = (s.eval(&fine), s.derivative(&fine));
+        for (i, t) in fine.data.iter().enumerate() {
+            assert!((v[i] - t.sin()).abs() < 2e-4);
+            assert!((d[i] - t.cos()).abs() < 5e-3);
+        }
+    }
+    let (x, s) = sine_spline(BoundaryCondition::Natural);
+    assert_eq!(x.len(), 21);
+    let linear = interp::interp(&fine, &x, &s.eval(&x)).unwrap();
+    let spline = s.eval(&fine);
+    let worst = |v: &Vector<f64>| fine.data.iter().zip(v.data.iter())
+        .fold(0.0f64, |m, (t, y)| m.max((y - t.sin()).abs()));
+    assert!(worst(&spline) < worst(&linear) / 50.0);
+}
+
+#[test]
+fn test_cubic_spline_extrapolation() {
+    // The clamped spline of a cubic is the cubic itself, so extrapolating the
+    // end segments reproduces it exactly.
+    let f = |t: f64| t * t * t - 2.0 * t;
+    let x = vector::from_elems(&[0.0, 1.0, 2.0, 3.0]);
+    let y = vector::from_vec(x.data.iter().map(|&t| f(t)).collect());
+    let s = CubicSpline::new(&x, &y, BoundaryCondition::Clamped(-2.0, 25.0)).unwrap();
+    let out = s.eval(&vector::from_elems(&[-1.0, 0.5, 2.5, 4.0]));
+    for (i, &t) in [-1.0, 0.5, 2.5, 4.0].iter().enumerate() {
+        assert!((out[i] - f(t)).abs() < 1e-9);
+    }
+
+    // A natural spline has zero curvature at the ends but the end cubics
+    // keep bending beyond them.
+    let y = vector::from_elems(&[0.0, 1.0, 0.0, 1.0]);
+    let s = CubicSpline::new(&x, &y, BoundaryCondition::Natural).unwrap();
+    let d = s.derivative(&vector::from_elems(&[3.0, 4.0]));
+    assert!((d[1] - d[0]).abs() > 1.0);
+}
+
+#[test]
+fn test_cubic_spline_invalid() {
+    let bc = BoundaryCondition::Natural;
+    let two = vector::from_elems(&[0.0, 1.0]);
+    assert!(CubicSpline::new(&two, &two, bc).is_err());
+    let x = vector::from_elems(&[0.0, 1.0, 2.0]);
+    assert!(CubicSpline::new(&x, &two, bc).is_err());
+    let unsorted = vector::from_elems(&[0.0, 2.0, 1.0]);
+    assert!(CubicSpline::new(&unsorted, &x, bc).is_err());
+    let repeated = vector::from_elems(&[0.0, 1.0, 1.0]);
+    assert!(CubicSpline::new(&repeated, &x, bc).is_err());
+}
@@ -0,0 +1,29 @@
+extern crate numrs;
+
+use std::io::{Cursor, ErrorKind};
+use numrs::io;
+use numrs::vector;
+
+#[test]
+fn test_io_f32_le_round_trip() {
+    let v = vector::from_vec((0..5000).map(|i| i as f32 * 0.25 - 100.0).collect());
+    let mut bytes = Vec::new();
+    io::write_f32_le(&mut bytes, &v).unwrap();
+    assert_eq!(bytes.len(), 20000);
+    assert_eq!(&bytes[..4], &(-100.0f32).to_le_bytes());
+
+    let mut reader = Cursor::new(bytes);
+    let first = io::read_f32_le(&mut reader, 3000).unwrap();
+    let rest = io::read_f32_le(&mut reader, 2000).unwrap();
+    assert_eq!(first.data, &v.data[..3000]);
+    assert_eq!(rest.data, &v.data[3000..]);
+    assert!(io::read_f32_le(&mut reader, 0).unwrap().data.is_empty());
+}
+
+#[test]
+fn test_io_read_f32_le_short_input() {
+    let bytes = [0, 0, 128, 63, 0, 0];
+    let err = io::read_f32_le(&mut &bytes[..], 2).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(io::read_f32_le(&mut &bytes[..], 1).unwrap().data, vec![1.0]);
+}
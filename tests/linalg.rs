@@ -0,0 +1,114 @@
+extern crate numrs;
+use numrs::linalg::{self, CgOptions, MatVec};
+use numrs::matrix;
+use numrs::matrix::Matrix;
+use numrs::vector;
+use numrs::vector::Vector;
+
+/// The 1D discrete Laplacian with Dirichlet boundaries, tridiagonal with 2
+/// on the diagonal and -1 beside it, applied without storing it.
+struct Laplacian(usize);
+
+impl MatVec<f64> for Laplacian {
+    fn shape(&self) -> (usize, usize) {
+        (self.0, self.0)
+    }
+
+    fn matvec(&self, x: &Vector<f64>) -> Vector<f64> {
+        let n = self.0;
+        vector::from_vec((0..n).map(|i| {
+            let left = if i > 0 { x[i - 1] } else { 0.0 };
+            let right = if i + 1 < n { x[i + 1] } else { 0.0 };
+            2.0 * x[i] - left - right
+        }).collect())
+    }
+}
+
+fn dense_laplacian(n: usize) -> Matrix<f64> {
+    let mut m = Matrix::new(n, n, 0.0);
+    for i in 0..n {
+        m.set(i, i, 2.0);
+        if i + 1 < n {
+            m.set(i, i + 1, -1.0);
+            m.set(i + 1, i, -1.0);
+        }
+    }
+    m
+}
+
+#[test]
+fn test_cg_laplacian() {
+    let n = 64;
+    let expected = vector::from_vec((0..n).map(|i| (i as f64 * 0.3).sin()).collect());
+    let b = Laplacian(n).matvec(&expected);
+    let opts = CgOptions { tol: 1e-12, ..CgOptions::default() };
+    let result = linalg::conjugate_gradient(&Laplacian(n), &b, opts.clone()).unwrap();
+    assert!(result.iterations <= n);
+    assert!(result.residual <= 1e-12 * b.data.iter().map(|x| x * x).sum::<f64>().sqrt());
+    for i in 0..n {
+        assert!((result.solution[i] - expected[i]).abs() < 1e-8);
+    }
+
+    // The stored matrix gives the same solution.
+    let dense = linalg::conjugate_gradient(&dense_laplacian(n), &b, opts).unwrap();
+    assert_eq!(dense.iterations, result.iterations);
+    for i in 0..n {
+        assert!((dense.solution[i] - result.solution[i]).abs() < 1e-10);
+    }
+}
+
+#[test]
+fn test_cg_small_dense() {
+    let a = matrix::from_elems(3, 3, &[4.0f64, 1.0, 0.0, 1.0, 3.0, -1.0, 0.0, -1.0, 2.0]);
+    let expected = vector::from_elems(&[1.0, -2.0, 0.5]);
+    let b = a.mul_vec(&expected).unwrap();
+    let result = linalg::conjugate_gradient(&a, &b, CgOptions::default()).unwrap();
+    assert!(result.iterations <= 3);
+    let other = a.conjugate_gradient(&b, 10, 1e-12).unwrap();
+    for i in 0..3 {
+        assert!((result.solution[i] - expected[i]).abs() < 1e-10);
+        assert!((result.solution[i] - other[i]).abs() < 1e-10);
+    }
+    let zero = linalg::conjugate_gradient(&a, &Vector::new(3, 0.0), CgOptions::default()).unwrap();
+    assert_eq!((zero.solution, zero.iterations), (Vector::new(3, 0.0), 0));
+}
+
+#[test]
+fn test_cg_preconditioned() {
+    // A badly scaled diagonal system is solved in one step with the Jacobi
+    // preconditioner.
+    let n = 20;
+    let diag: Vec<f64> = (0..n).map(|i| 10f64.powi(i as i32 % 7)).collect();
+    let mut a = Matrix::new(n, n, 0.0);
+    for (i, &d) in diag.iter().enumerate() {
+        a.set(i, i, d);
+    }
+    let b = Vector::new(n, 1.0);
+    let plain = linalg::conjugate_gradient(&a, &b, CgOptions::default()).unwrap();
+    let opts = CgOptions { preconditioner: Some(vector::from_vec(diag.clone())), ..CgOptions::default() };
+    let jacobi = linalg::conjugate_gradient(&a, &b, opts).unwrap();
+    assert_eq!(jacobi.iterations, 1);
+    assert!(plain.iterations > jacobi.iterations);
+    for (x, d) in jacobi.solution.data.iter().zip(&diag) {
+        assert!((x * d - 1.0).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_cg_errors() {
+    let b = Vector::new(64, 1.0);
+    let opts = CgOptions { max_iter: 3, ..CgOptions::default() };
+    let err = linalg::conjugate_gradient(&Laplacian(64), &b, opts).unwrap_err();
+    assert!(err.starts_with("Conjugate gradient did not converge in 3 iterations"));
+
+    let a = matrix::from_elems(2, 2, &[1.0, 0.0, 0.0, -1.0]);
+    let b = vector::from_elems(&[0.0, 1.0]);
+    assert_eq!(linalg::conjugate_gradient(&a, &b, CgOptions::default()).unwrap_err(),
+               "The operator is not positive definite.");
+    assert!(linalg::conjugate_gradient(&Matrix::new(2, 3, 1.0), &b, CgOptions::default()).is_err());
+    assert!(linalg::conjugate_gradient(&Laplacian(3), &b, CgOptions::default()).is_err());
+    let bad = CgOptions { preconditioner: Some(vector::from_elems(&[1.0, 0.0])), ..CgOptions::default() };
+    assert!(linalg::conjugate_gradient(&Laplacian(2), &b, bad).is_err());
+    let short = CgOptions { preconditioner: Some(vector::from_elems(&[1.0])), ..CgOptions::default() };
+    assert!(linalg::conjugate_gradient(&Laplacian(2), &b, short).is_err());
+}
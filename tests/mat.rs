@@ -0,0 +1,110 @@
+#![cfg(feature = "mat")]
+extern crate numrs;
+
+use std::collections::HashMap;
+use std::env;
+use numrs::io::mat::{self, MatVariable};
+use numrs::matrix;
+use numrs::vector;
+
+fn fixture(name: &str) -> String {
+    format!("{}/tests/data/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn check_fixture_vars(vars: &HashMap<String, MatVariable>) {
+    assert_eq!(vars.len(), 3);
+    match vars["A"] {
+        MatVariable::Matrix(ref m) => {
+            assert!(*m == matrix::from_elems(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.5]))
+        }
+        _ => panic!("A should be a double matrix"),
+    }
+    match vars["v"] {
+        MatVariable::SingleVector(ref v) => assert_eq!(v.data, vec![1.5f32, -2.0, 3.0]),
+        _ => panic!("v should be a single vector"),
+    }
+    match vars["counts"] {
+        MatVariable::Vector(ref v) => assert_eq!(v.data, vec![1.0, 2.0, 250.0]),
+        _ => panic!("counts should be a double vector"),
+    }
+}
+
+#[test]
+fn test_mat_read_uncompressed() {
+    check_fixture_vars(&mat::read_mat(fixture("octave_v6.mat")).unwrap());
+}
+
+#[test]
+fn test_mat_read_compressed() {
+    check_fixture_vars(&mat::read_mat(fixture("octave_v7.mat")).unwrap());
+}
+
+#[test]
+fn test_mat_read_big_endian() {
+    check_fixture_vars(&mat::read_mat(fixture("big_endian.mat")).unwrap());
+}
+
+#[test]
+fn test_mat_read_errors() {
+    let err = mat::read_mat(fixture("char.mat")).err().unwrap();
+    assert_eq!(err, "Variable 'label' has unsupported class char.");
+    assert!(mat::read_mat(fixture("missing.mat")).is_err());
+    assert!(mat::read_mat(fixture("make_mat_fixtures.py")).is_err());
+}
+
+/// Appends a little-endian data element with a full tag, padded to 8 bytes.
+fn element(out: &mut Vec<u8>, ty: u32, data: &[u8]) {
+    out.extend_from_slice(&ty.to_le_bytes());
+    out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    out.extend_from_slice(data);
+    while !out.len().is_multiple_of(8) {
+        out.push(0);
+    }
+}
+
+/// Reads a file holding one empty double variable `x` whose dimensions are
+/// given as doubles.
+fn read_with_dims(dims: &[f64]) -> Result<HashMap<String, MatVariable>, String> {
+    let mut body = Vec::new();
+    element(&mut body, 6, &[6, 0, 0, 0, 0, 0, 0, 0]);
+    let dims: Vec<u8> = dims.iter().flat_map(|d| d.to_le_bytes().to_vec()).collect();
+    element(&mut body, 9, &dims);
+    element(&mut body, 1, b"x");
+    element(&mut body, 9, &[]);
+    let mut file = vec![b' '; 128];
+    file[124..128].copy_from_slice(&[0, 1, b'I', b'M']);
+    element(&mut file, 14, &body);
+
+    let path = env::temp_dir().join(format!("numrs_mat_dims_{}.mat", std::process::id()));
+    std::fs::write(&path, file).unwrap();
+    let read = mat::read_mat(&path);
+    std::fs::remove_file(&path).unwrap();
+    read
+}
+
+#[test]
+fn test_mat_read_invalid_dimensions() {
+    assert!(read_with_dims(&[0.0, 3.0]).unwrap().contains_key("x"));
+    for dims in [[1e300, 2.0], [-1.0, 0.0], [0.5, 0.0], [f64::NAN, 0.0], [f64::INFINITY, 0.0],
+                 [4294967296.0, 4294967296.0]].iter() {
+        assert_eq!(read_with_dims(dims).err().unwrap(), "Variable 'x' has invalid dimensions.");
+    }
+}
+
+#[test]
+fn test_mat_write_round_trip() {
+    let mut vars = HashMap::new();
+    vars.insert("m".to_string(),
+                MatVariable::Matrix(matrix::from_elems(2, 3, &[1.0, -2.0, 3.0, 4.0, 5.0, 1e-300])));
+    vars.insert("single".to_string(),
+                MatVariable::SingleMatrix(matrix::from_elems(3, 2, &[1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0])));
+    vars.insert("x".to_string(), MatVariable::Vector(vector::from_elems(&[0.5, 1.5])));
+    vars.insert("long_variable_name".to_string(),
+                MatVariable::SingleVector(vector::from_elems(&[7.0f32])));
+
+    let path = env::temp_dir().join(format!("numrs_mat_{}.mat", std::process::id()));
+    mat::write_mat(&path, &vars).unwrap();
+    let read = mat::read_mat(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(read == vars);
+}
@@ -1,6 +1,7 @@
 extern crate numrs;
 use numrs::matrix;
 use numrs::matrix::Matrix;
+use numrs::vector;
 
 #[test]
 fn test_basic_matrix() {
@@ -129,3 +130,243 @@ fn test_matrix_trace() {
     let mat = matrix::from_elems(2, 2, &elems);
     assert_eq!(mat.trace(), 5.0);
 }
+
+#[test]
+fn test_matrix_index_non_square() {
+    let mat = matrix::from_elems(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    assert_eq!(mat[0], [1.0, 2.0, 3.0]);
+    assert_eq!(mat[1], [4.0, 5.0, 6.0]);
+    let tall = matrix::from_elems(3, 1, &[1.0, 2.0, 3.0]);
+    assert_eq!(tall[2], [3.0]);
+}
+
+#[test]
+fn test_matrix_transpose_non_square() {
+    let elems = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+    let mut mat = matrix::from_elems(2, 3, &elems);
+    mat.transpose();
+    assert_eq!(mat.num_rows(), 3);
+    assert_eq!(mat.num_cols(), 2);
+    assert_eq!(mat[0][1], 4.0);
+    assert_eq!(mat[2][0], 3.0);
+    assert_eq!(mat[2][1], 6.0);
+}
+
+fn naive_matmul(a: &Matrix<f64>, b: &Matrix<f64>) -> Matrix<f64> {
+    let mut c = Matrix::new(a.num_rows(), b.num_cols(), 0.0);
+    for i in 0..a.num_rows() {
+        for j in 0..b.num_cols() {
+            let mut sum = 0.0;
+            for k in 0..a.num_cols() {
+                sum += a.get(i, k) * b.get(k, j);
+            }
+            c.set(i, j, sum);
+        }
+    }
+    c
+}
+
+#[test]
+fn test_matmul_large_matches_serial() {
+    // Large enough to take the multithreaded path with the `parallel` feature,
+    // with a row count that does not divide evenly into blocks.
+    let (m, n, p) = (131, 67, 45);
+    let a_elems: Vec<f64> = (0..m * n).map(|i| ((i * 7919) % 1000) as f64 / 997.0 - 0.5).collect();
+    let b_elems: Vec<f64> = (0..n * p).map(|i| ((i * 104729) % 1000) as f64 / 991.0 - 0.5).collect();
+    let a = matrix::from_elems(m, n, &a_elems);
+    let b = matrix::from_elems(n, p, &b_elems);
+    let c = a.matmul(&b);
+    assert_eq!(c.num_rows(), m);
+    assert_eq!(c.num_cols(), p);
+    assert_eq!(c.get_vec(), naive_matmul(&a, &b).get_vec());
+}
+
+#[test]
+fn test_matmul_non_square() {
+    let a = matrix::from_elems(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let b = matrix::from_elems(3, 1, &[1.0, 0.0, -1.0]);
+    let c = a.matmul(&b);
+    assert_eq!(c.get_vec(), vec![-2.0, -2.0]);
+}
+
+#[test]
+fn test_matrix_from_str() {
+    let expected = matrix::from_elems(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    for s in &["1 2 3; 4 5 6", "[1, 2, 3]\n[4, 5, 6]\n", "[1 2 3; 4 5 6]", "1,2,3;\n4,5,6;"] {
+        let m: Matrix<f64> = s.parse().unwrap();
+        assert!(m == expected);
+    }
+    let m: Matrix<f32> = "".parse().unwrap();
+    assert_eq!((m.num_rows(), m.num_cols()), (0, 0));
+}
+
+#[test]
+fn test_matrix_display_round_trip() {
+    let m = matrix::from_elems(2, 2, &[1.5, -2.0, 1e10, 0.25]);
+    assert_eq!(m.to_string(), "[1.5, -2]\n[10000000000, 0.25]");
+    assert!(m.to_string().parse::<Matrix<f64>>().unwrap() == m);
+}
+
+#[test]
+fn test_matrix_from_str_errors() {
+    assert_eq!("1 2; 3 x".parse::<Matrix<f64>>().err().unwrap(),
+               "Invalid number 'x' at offset 7.");
+    assert_eq!("1 2\n3".parse::<Matrix<f64>>().err().unwrap(),
+               "Row 1 at offset 4 has 1 elements instead of 2.");
+}
+
+#[test]
+fn test_matrix_from_vec_into_vec() {
+    let data = vec![1, 2, 3, 4, 5, 6];
+    let ptr = data.as_ptr();
+    let m = matrix::from_vec(3, 2, data);
+    assert_eq!(m.get(2, 1), 6);
+    let v = m.into_vec();
+    assert_eq!(v.as_ptr(), ptr);
+}
+
+#[test]
+#[should_panic]
+fn test_matrix_from_vec_wrong_length() {
+    matrix::from_vec(2, 2, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_mul_vec() {
+    let m = matrix::from_elems(2, 3, &[1, 2, 3, 4, 5, 6]);
+    assert_eq!(m.mul_vec(&vector::from_elems(&[1, 0, -1])).unwrap().data, vec![-2, -2]);
+    assert!(m.mul_vec(&vector::from_elems(&[1, 2])).is_err());
+}
+
+#[test]
+fn test_power_iteration() {
+    let m = matrix::from_elems(2, 2, &[2.0f64, 1.0, 1.0, 2.0]);
+    let (v, lambda) = m.power_iteration(100, 1e-12);
+    assert!((lambda - 3.0).abs() < 1e-9);
+    let s = 0.5f64.sqrt();
+    assert!((v[0] - s).abs() < 1e-6 && (v[1] - s).abs() < 1e-6);
+
+    let m = matrix::from_elems(3, 3, &[4.0f64, 1.0, 0.0, 1.0, 3.0, 1.0, 0.0, 1.0, 2.0]);
+    let (v, lambda) = m.power_iteration(1000, 1e-14);
+    let mv = m.mul_vec(&v).unwrap();
+    for i in 0..3 {
+        assert!((mv[i] - lambda * v[i]).abs() < 1e-6);
+    }
+
+    // A single iteration stops early whatever the tolerance.
+    let (_, first) = m.power_iteration(1, 0.0);
+    assert!((first - 13.0 / 3.0).abs() < 1e-12);
+}
+
+#[test]
+#[should_panic(expected = "The matrix should be a square matrix.")]
+fn test_power_iteration_not_square() {
+    matrix::from_elems(1, 2, &[1.0, 2.0]).power_iteration(10, 1e-6);
+}
+
+#[test]
+fn test_conjugate_gradient() {
+    use numrs::vector::VectorOps;
+
+    let a = matrix::from_elems(3, 3, &[4.0f64, 1.0, 0.0, 1.0, 3.0, 1.0, 0.0, 1.0, 2.0]);
+    let expected = vector::from_elems(&[1.0, -2.0, 3.0]);
+    let b = a.mul_vec(&expected).unwrap();
+    let x = a.conjugate_gradient(&b, 3, 1e-10).unwrap();
+    let err = x - expected.clone();
+    assert!(err.norm() < 1e-9);
+
+    // Converged before the first iteration.
+    let zero = a.conjugate_gradient(&vector::from_elems(&[0.0, 0.0, 0.0]), 0, 1e-10).unwrap();
+    assert_eq!(zero.data, vec![0.0, 0.0, 0.0]);
+
+    assert!(a.conjugate_gradient(&b, 1, 1e-10).unwrap_err().contains("did not converge"));
+    assert!(a.conjugate_gradient(&vector::from_elems(&[1.0]), 3, 1e-10).is_err());
+    let indefinite = matrix::from_elems(2, 2, &[1.0, 0.0, 0.0, -1.0]);
+    assert_eq!(indefinite.conjugate_gradient(&vector::from_elems(&[0.0, 1.0]), 5, 1e-10),
+               Err("The operator is not positive definite.".to_string()));
+}
+
+#[test]
+fn test_matrix_determinant() {
+    assert_eq!(matrix::from_elems(2, 2, &[3.0, 8.0, 4.0, 6.0]).determinant(), -14.0);
+    // Needs a row swap for the first pivot.
+    let m = matrix::from_elems(3, 3, &[0.0, 2.0, 1.0, 1.0, 1.0, 1.0, 2.0, 0.0, 3.0]);
+    assert!((m.determinant() - -4.0f64).abs() < 1e-12);
+    let singular = matrix::from_elems(3, 3, &[1.0, 2.0, 3.0, 2.0, 4.0, 6.0, 1.0, 0.0, 1.0]);
+    assert_eq!(singular.determinant(), 0.0);
+    assert_eq!(matrix::identity::<f64>(4).determinant(), 1.0);
+    assert_eq!(Matrix::<f64>::new(0, 0, 0.0).determinant(), 1.0);
+}
+
+#[test]
+#[should_panic]
+fn test_matrix_determinant_not_square() {
+    Matrix::new(2, 3, 1.0f64).determinant();
+}
+
+#[test]
+fn test_matrix_solve() {
+    let a = matrix::from_elems(3, 3, &[0.0, 2.0, 1.0, 1.0, 1.0, 1.0, 2.0, 0.0, 3.0]);
+    let x = vector::from_elems(&[1.0f64, -1.0, 2.0]);
+    let b = a.mul_vec(&x).unwrap();
+    let solved = a.solve(&b).unwrap();
+    for i in 0..3 {
+        assert!((solved[i] - x[i]).abs() < 1e-12);
+    }
+    let singular = matrix::from_elems(2, 2, &[1.0, 2.0, 2.0, 4.0]);
+    assert_eq!(singular.solve(&vector::from_elems(&[1.0, 2.0])).unwrap_err(), "The matrix is singular.");
+    assert!(a.solve(&vector::from_elems(&[1.0])).is_err());
+    assert!(Matrix::new(2, 3, 1.0).solve(&vector::from_elems(&[1.0, 2.0])).is_err());
+}
+
+#[test]
+fn test_matrix_singular_to_working_precision() {
+    // The second pivot is epsilon, within rounding of the entries: solve
+    // rejects the matrix, but its determinant is still exactly epsilon.
+    let m = matrix::from_elems(2, 2, &[1.0f64, 1.0, 1.0, 1.0 + f64::EPSILON]);
+    assert_eq!(m.solve(&vector::from_elems(&[1.0, 2.0])).unwrap_err(), "The matrix is singular.");
+    assert_eq!(m.determinant(), f64::EPSILON);
+    assert_eq!(m.slogdet().unwrap(), (1.0, f64::EPSILON.ln()));
+
+    // Badly scaled but well conditioned matrices are not singular.
+    let d = matrix::from_elems(2, 2, &[1e-10f64, 0.0, 0.0, 1e10]);
+    assert!((d.determinant() - 1.0).abs() < 1e-15);
+    let (sign, logdet) = d.slogdet().unwrap();
+    assert!(sign == 1.0 && logdet.abs() < 1e-15);
+    let e = matrix::from_elems(2, 2, &[1.0f64, 0.0, 0.0, 1e-17]);
+    assert_eq!(e.solve(&vector::from_elems(&[2.0, 3e-17])).unwrap().data, vec![2.0, 3.0]);
+
+    // Pivoting still permutes the right-hand side.
+    let p = matrix::from_elems(3, 3, &[0.0f64, 1.0, 0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0]);
+    assert_eq!(p.solve(&vector::from_elems(&[1.0, 2.0, 3.0])).unwrap().data, vec![3.0, 1.0, 2.0]);
+    assert_eq!(p.determinant(), 1.0);
+}
+
+#[test]
+fn test_matrix_slogdet() {
+    let small = [matrix::from_elems(2, 2, &[3.0f64, 8.0, 4.0, 6.0]),
+                 matrix::from_elems(3, 3, &[0.0, 2.0, 1.0, 1.0, 1.0, 1.0, 2.0, 0.0, 3.0]),
+                 matrix::from_elems(3, 3, &[2.0, -1.0, 0.0, -1.0, 2.0, -1.0, 0.0, -1.0, 2.0])];
+    for m in small.iter() {
+        let (sign, logdet) = m.slogdet().unwrap();
+        assert!((sign * logdet.exp() - m.determinant()).abs() < 1e-12);
+    }
+    assert_eq!(small[0].slogdet().unwrap().0, -1.0);
+    assert_eq!(small[2].slogdet().unwrap().0, 1.0);
+
+    // det(diag(0.01, ..)) is 1e-400, which underflows, but its log does not.
+    let n = 200;
+    let mut tiny = Matrix::new(n, n, 0.0f64);
+    for i in 0..n {
+        tiny.set(i, i, if i == 7 { -0.01 } else { 0.01 });
+    }
+    assert_eq!(tiny.determinant(), 0.0);
+    let (sign, logdet) = tiny.slogdet().unwrap();
+    assert_eq!(sign, -1.0);
+    assert!((logdet - 200.0 * 0.01f64.ln()).abs() < 1e-9);
+
+    let singular = matrix::from_elems(2, 2, &[1.0f64, 2.0, 2.0, 4.0]);
+    assert_eq!(singular.slogdet().unwrap(), (0.0, f64::NEG_INFINITY));
+    assert_eq!(Matrix::<f64>::new(0, 0, 0.0).slogdet().unwrap(), (1.0, 0.0));
+    assert!(Matrix::new(2, 3, 1.0f64).slogdet().is_err());
+}
@@ -0,0 +1,97 @@
+extern crate numrs;
+use numrs::matrix;
+use numrs::matrix::Matrix;
+use numrs::metrics;
+use numrs::metrics::Metric;
+
+fn naive(a: &Matrix<f64>, b: &Matrix<f64>, metric: Metric) -> Matrix<f64> {
+    let mut d = Matrix::new(a.num_rows(), b.num_rows(), 0.0);
+    for i in 0..a.num_rows() {
+        for j in 0..b.num_rows() {
+            let (mut sq, mut abs, mut dot, mut na, mut nb) = (0.0, 0.0, 0.0, 0.0, 0.0);
+            for k in 0..a.num_cols() {
+                let (x, y) = (a.get(i, k), b.get(j, k));
+                sq += (x - y) * (x - y);
+                abs += (x - y).abs();
+                dot += x * y;
+                na += x * x;
+                nb += y * y;
+            }
+            d.set(i, j, match metric {
+                Metric::Euclidean => sq.sqrt(),
+                Metric::SquaredEuclidean => sq,
+                Metric::Manhattan => abs,
+                Metric::Cosine => 1.0 - dot / (na.sqrt() * nb.sqrt()),
+            });
+        }
+    }
+    d
+}
+
+#[test]
+fn test_cdist_matches_naive() {
+    let a = matrix::from_elems(3, 2, &[1.0, 2.0, -3.0, 0.5, 4.0, 4.0]);
+    let b = matrix::from_elems(2, 2, &[0.0, 1.0, 2.5, -1.0]);
+    let metrics = [Metric::Euclidean, Metric::SquaredEuclidean, Metric::Manhattan, Metric::Cosine];
+    for &metric in metrics.iter() {
+        let d = metrics::cdist(&a, &b, metric).unwrap();
+        let expected = naive(&a, &b, metric);
+        assert_eq!(d.num_rows(), 3);
+        assert_eq!(d.num_cols(), 2);
+        for i in 0..3 {
+            for j in 0..2 {
+                assert!((d.get(i, j) - expected.get(i, j)).abs() < 1e-10);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_cdist_identical_rows_clamped() {
+    let a = matrix::from_elems(1, 3, &[0.1, 0.7, 1e8]);
+    let d = metrics::cdist(&a, &a, Metric::Euclidean).unwrap();
+    assert_eq!(d.get(0, 0), 0.0);
+}
+
+#[test]
+fn test_cdist_mismatch() {
+    let a = matrix::from_elems(1, 3, &[1.0, 2.0, 3.0]);
+    let b = matrix::from_elems(1, 2, &[1.0, 2.0]);
+    assert!(metrics::cdist(&a, &b, Metric::Manhattan).is_err());
+}
+
+#[test]
+fn test_pdist() {
+    let a = matrix::from_elems(3, 2, &[0.0, 0.0, 3.0, 4.0, 6.0, 8.0]);
+    let d = metrics::pdist::<f64>(&a, Metric::Euclidean);
+    assert_eq!(d.len(), 3);
+    assert!((d[0] - 5.0).abs() < 1e-12);
+    assert!((d[1] - 10.0).abs() < 1e-12);
+    assert!((d[2] - 5.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_cosine_similarity_matrix() {
+    use numrs::vector;
+
+    let vs = vec![vector::from_elems(&[1.0, 0.0]),
+                  vector::from_elems(&[1.0, 1.0]),
+                  vector::from_elems(&[-2.0, 0.0])];
+    let s = metrics::cosine_similarity_matrix(&vs).unwrap();
+    let r = 0.5f64.sqrt();
+    let expected = [1.0, r, -1.0, r, 1.0, -r, -1.0, -r, 1.0];
+    for i in 0..3 {
+        for j in 0..3 {
+            assert!((s.get(i, j) - expected[i * 3 + j]).abs() < 1e-12);
+            assert_eq!(s.get(i, j), s.get(j, i));
+            assert!((s.get(i, j) - vs[i].cosine_similarity(&vs[j]).unwrap()).abs() < 1e-12);
+        }
+    }
+
+    let with_zero = metrics::cosine_similarity_matrix(&[vector::from_elems(&[0.0f64, 0.0]),
+                                                        vector::from_elems(&[1.0, 0.0])]).unwrap();
+    assert!(with_zero.get(0, 1).is_nan() && with_zero.get(0, 0).is_nan());
+    assert_eq!(metrics::cosine_similarity_matrix::<f64>(&[]).unwrap().num_rows(), 0);
+    assert!(metrics::cosine_similarity_matrix(&[vector::from_elems(&[1.0]),
+                                                vector::from_elems(&[1.0, 2.0])]).is_err());
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "mmap")]
+extern crate numrs;
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use numrs::io::MmapVector;
+use numrs::vector;
+use numrs::vector::VectorOps;
+
+fn temp_file(name: &str, bytes: &[u8]) -> PathBuf {
+    let path = env::temp_dir().join(format!("numrs_mmap_{}_{}.bin", name, std::process::id()));
+    fs::write(&path, bytes).unwrap();
+    path
+}
+
+#[test]
+fn test_mmap_vector_matches_memory() {
+    let data: Vec<f64> = (0..1000).map(|i| (i as f64 * 0.37).sin()).collect();
+    let bytes: Vec<u8> = data.iter().flat_map(|x| x.to_ne_bytes().to_vec()).collect();
+    let path = temp_file("f64", &bytes);
+
+    let mapped = MmapVector::<f64>::open(&path).unwrap();
+    let v = vector::from_elems(&data);
+    assert_eq!(mapped.len(), 1000);
+    assert_eq!(mapped.sum(), v.sum());
+    assert_eq!(mapped.mean(), v.mean());
+    assert_eq!(mapped.min(), v.min());
+    assert_eq!(mapped.max(), v.max());
+    assert_eq!(mapped.dot(&v).unwrap(), v.dot(&v).unwrap());
+    assert!(mapped.dot(&vector::from_elems(&[1.0])).is_err());
+
+    assert_eq!(mapped.slice(10..13).unwrap().data, &data[10..13]);
+    assert!(mapped.slice(0..0).unwrap().data.is_empty());
+    assert!(mapped.slice(999..1001).is_err());
+    let (lo, hi) = (5, 3);
+    assert!(mapped.slice(lo..hi).is_err());
+
+    drop(mapped);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_mmap_vector_swapped() {
+    let data = [1.5f32, -2.25, 1e10];
+    let bytes: Vec<u8> = data.iter().flat_map(|x| x.to_bits().swap_bytes().to_ne_bytes().to_vec()).collect();
+    let path = temp_file("swapped", &bytes);
+
+    let mapped = MmapVector::<f32>::open(&path).unwrap();
+    assert_eq!(mapped.slice_swapped(0..3).unwrap().data, data.to_vec());
+    assert!(mapped.slice_swapped(2..4).is_err());
+
+    drop(mapped);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_mmap_vector_bad_files() {
+    let path = temp_file("odd", &[0; 12]);
+    assert!(MmapVector::<f64>::open(&path).is_err());
+    assert_eq!(MmapVector::<f32>::open(&path).unwrap().len(), 3);
+    fs::remove_file(&path).unwrap();
+
+    let path = temp_file("empty", &[]);
+    let empty = MmapVector::<f64>::open(&path).unwrap();
+    assert!(empty.is_empty());
+    assert_eq!(empty.sum(), 0.0);
+    fs::remove_file(&path).unwrap();
+
+    assert!(MmapVector::<f64>::open("/nonexistent/numrs.bin").is_err());
+}
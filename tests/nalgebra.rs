@@ -0,0 +1,41 @@
+#![cfg(feature = "nalgebra")]
+extern crate nalgebra;
+extern crate numrs;
+
+use nalgebra::{DMatrix, DVector};
+use numrs::matrix;
+use numrs::matrix::Matrix;
+use numrs::vector;
+use numrs::vector::Vector;
+
+#[test]
+fn test_nalgebra_vector_round_trip() {
+    let v = vector::from_elems(&[1.0, 2.0, 3.0]);
+    let ptr = v.as_ptr();
+    let dv: DVector<f64> = v.into();
+    assert_eq!(dv.as_ptr(), ptr);
+    assert_eq!(dv[2], 3.0);
+
+    let back = Vector::from(dv);
+    assert_eq!(back.as_ptr(), ptr);
+    assert_eq!(back.data, vec![1.0, 2.0, 3.0]);
+}
+
+#[test]
+fn test_nalgebra_matrix_element_order() {
+    let m = matrix::from_elems(2, 3, &[1, 2, 3, 4, 5, 6]);
+    let dm: DMatrix<i32> = m.clone().into();
+    assert_eq!(dm.shape(), (2, 3));
+    for i in 0..2 {
+        for j in 0..3 {
+            assert_eq!(dm[(i, j)], m.get(i, j));
+        }
+    }
+    assert_eq!(dm.as_slice(), &[1, 4, 2, 5, 3, 6]);
+    assert!(Matrix::from(dm) == m);
+
+    let dm = DMatrix::from_row_slice(3, 2, &[1.5, 2.5, 3.5, 4.5, 5.5, 6.5]);
+    let m = Matrix::from(dm);
+    assert_eq!((m.num_rows(), m.num_cols()), (3, 2));
+    assert_eq!(m.get_vec(), vec![1.5, 2.5, 3.5, 4.5, 5.5, 6.5]);
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "ndarray")]
+extern crate ndarray;
+extern crate numrs;
+
+use ndarray::{arr1, arr2, s, Array1, Array2, ShapeBuilder};
+use numrs::matrix;
+use numrs::matrix::Matrix;
+use numrs::vector;
+use numrs::vector::Vector;
+
+#[test]
+fn test_ndarray_vector_round_trip() {
+    let a = arr1(&[1.0, 2.0, 3.0]);
+    let ptr = a.as_ptr();
+    let v = Vector::from(a);
+    assert_eq!(v.data, vec![1.0, 2.0, 3.0]);
+    assert_eq!(v.as_ptr(), ptr);
+
+    let back: Array1<f64> = v.into();
+    assert_eq!(back.as_ptr(), ptr);
+    assert_eq!(back, arr1(&[1.0, 2.0, 3.0]));
+}
+
+#[test]
+fn test_ndarray_vector_from_slices() {
+    let a = arr1(&[0, 1, 2, 3, 4, 5]);
+    assert_eq!(Vector::from(a.slice(s![2..5]).to_owned()).data, vec![2, 3, 4]);
+    assert_eq!(Vector::from(a.slice_move(s![1..4])).data, vec![1, 2, 3]);
+    let a = arr1(&[0, 1, 2, 3, 4, 5]);
+    assert_eq!(Vector::from(a.clone().slice_move(s![..;2])).data, vec![0, 2, 4]);
+    assert_eq!(Vector::from(a.slice_move(s![..;-1])).data, vec![5, 4, 3, 2, 1, 0]);
+}
+
+#[test]
+fn test_ndarray_matrix_round_trip() {
+    let a = arr2(&[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    let m = Matrix::from(a.clone());
+    assert!(m == matrix::from_elems(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]));
+    let back: Array2<f64> = m.into();
+    assert_eq!(back, a);
+}
+
+#[test]
+fn test_ndarray_matrix_non_standard_layouts() {
+    let a = arr2(&[[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+    let t = Matrix::from(a.clone().reversed_axes());
+    assert_eq!(t.get_vec(), vec![1, 4, 7, 2, 5, 8, 3, 6, 9]);
+
+    let rows = Matrix::from(a.clone().slice_move(s![1.., ..]));
+    assert_eq!((rows.num_rows(), rows.num_cols()), (2, 3));
+    assert_eq!(rows.get_vec(), vec![4, 5, 6, 7, 8, 9]);
+
+    let block = Matrix::from(a.slice_move(s![..;2, 1..]));
+    assert_eq!(block.get_vec(), vec![2, 3, 8, 9]);
+
+    let fortran = Array2::from_shape_vec((2, 3).f(), vec![1, 4, 2, 5, 3, 6]).unwrap();
+    assert_eq!(Matrix::from(fortran).get_vec(), vec![1, 2, 3, 4, 5, 6]);
+    assert!(Vector::from(arr1::<f64>(&[])) == vector::from_elems(&[]));
+}
@@ -0,0 +1,155 @@
+extern crate numrs;
+use numrs::poly;
+use numrs::vector;
+
+/// Coefficients, highest power first, of the polynomial with the given real
+/// roots times `x^2 - 2ax + a^2 + b^2` for every complex pair `a +- bi`.
+fn from_roots(real: &[f64], pairs: &[(f64, f64)]) -> Vec<f64> {
+    let mut c = vec![1.0];
+    let mul = |c: &Vec<f64>, f: &[f64]| {
+        let mut out = vec![0.0; c.len() + f.len() - 1];
+        for (i, &x) in c.iter().enumerate() {
+            for (j, &y) in f.iter().enumerate() {
+                out[i + j] += x * y;
+            }
+        }
+        out
+    };
+    for &r in real {
+        c = mul(&c, &[1.0, -r]);
+    }
+    for &(a, b) in pairs {
+        c = mul(&c, &[1.0, -2.0 * a, a * a + b * b]);
+    }
+    c
+}
+
+fn assert_roots(actual: &[(f64, f64)], expected: &[(f64, f64)], tol: f64) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected.iter()) {
+        assert!((a.0 - e.0).abs() < tol && (a.1 - e.1).abs() < tol,
+                "{:?} differs from {:?}", actual, expected);
+    }
+}
+
+#[test]
+fn test_roots_quadratic() {
+    let r = poly::roots(&vector::from_elems(&[1.0, -3.0, 2.0])).unwrap();
+    assert_roots(&r, &[(1.0, 0.0), (2.0, 0.0)], 1e-12);
+    let r = poly::roots(&vector::from_elems(&[1.0, 2.0, 5.0])).unwrap();
+    assert_roots(&r, &[(-1.0, -2.0), (-1.0, 2.0)], 1e-12);
+    let r = poly::roots(&vector::from_elems(&[2.0, -1.0])).unwrap();
+    assert_roots(&r, &[(0.5, 0.0)], 1e-15);
+
+    // Leading zeros are stripped and trailing zeros give roots at zero.
+    let r = poly::roots(&vector::from_elems(&[0.0, 0.0, 1.0, -3.0, 2.0])).unwrap();
+    assert_roots(&r, &[(1.0, 0.0), (2.0, 0.0)], 1e-12);
+    let r = poly::roots(&vector::from_elems(&[1.0, -1.0, 0.0, 0.0])).unwrap();
+    assert_eq!(r[..2], [(0.0, 0.0), (0.0, 0.0)]);
+    assert_roots(&r[2..], &[(1.0, 0.0)], 1e-12);
+    let r = poly::roots(&vector::from_elems(&[4.0f32, 0.0, -1.0])).unwrap();
+    assert!((r[0].0 + 0.5).abs() < 1e-6 && (r[1].0 - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn test_roots_invalid() {
+    assert!(poly::roots(&vector::from_elems(&[3.0])).is_err());
+    assert!(poly::roots(&vector::from_elems(&[0.0, 0.0, 3.0])).is_err());
+    assert!(poly::roots(&vector::from_elems(&[0.0, 0.0])).is_err());
+    assert!(poly::roots(&vector::from_elems::<f64>(&[])).is_err());
+    assert!(poly::roots(&vector::from_elems(&[1.0, f64::NAN])).is_err());
+    // The iteration overflows for x^3 + 1e300.
+    assert_eq!(poly::roots(&vector::from_elems(&[1.0, 0.0, 0.0, 1e300])).unwrap_err(),
+               "Root finding did not converge.");
+}
+
+#[test]
+fn test_roots_recovered() {
+    let c = from_roots(&[-2.0, 0.25, 1.5, 3.0], &[(0.5, 1.0), (-1.0, 0.5)]);
+    let r = poly::roots(&vector::from_vec(c)).unwrap();
+    assert_roots(&r, &[(-2.0, 0.0), (-1.0, -0.5), (-1.0, 0.5), (0.25, 0.0),
+                       (0.5, -1.0), (0.5, 1.0), (1.5, 0.0), (3.0, 0.0)], 1e-9);
+}
+
+#[test]
+fn test_roots_repeated() {
+    // A double root is only found to about the square root of the machine
+    // precision, and a triple root to about its cube root.
+    let c = from_roots(&[1.0, 1.0, -2.0], &[]);
+    let r = poly::roots(&vector::from_vec(c)).unwrap();
+    assert_roots(&r, &[(-2.0, 0.0), (1.0, 0.0), (1.0, 0.0)], 1e-6);
+    let c = from_roots(&[0.5, 0.5, 0.5], &[]);
+    let r = poly::roots(&vector::from_vec(c)).unwrap();
+    assert_roots(&r, &[(0.5, 0.0), (0.5, 0.0), (0.5, 0.0)], 1e-4);
+}
+
+#[test]
+fn test_roots_wilkinson() {
+    // The roots 1..=n of Wilkinson's polynomial are badly conditioned: tiny
+    // rounding errors in the coefficients move them far more than for the
+    // well separated roots above, so only a loose tolerance holds.
+    let expected: Vec<f64> = (1..11).map(|k| k as f64).collect();
+    let r = poly::roots(&vector::from_vec(from_roots(&expected, &[]))).unwrap();
+    let expected: Vec<(f64, f64)> = expected.iter().map(|&k| (k, 0.0)).collect();
+    assert_roots(&r, &expected, 1e-6);
+}
+
+#[test]
+fn test_polyfit_exact() {
+    let x = vector::from_elems(&[-2.0, -1.0, 0.0, 0.5, 1.0, 3.0]);
+    let coeffs = vector::from_elems(&[0.5f64, -1.0, 2.0, -3.0]);
+    let fit = poly::polyfit(&x, &x.polyval(&coeffs), 3).unwrap();
+    for i in 0..4 {
+        assert!((fit[i] - coeffs[i]).abs() < 1e-10);
+    }
+    // Interpolation through exactly degree + 1 points.
+    let three = vector::from_elems(&[1.0, 2.0, 4.0]);
+    let fit = poly::polyfit(&three, &vector::from_elems(&[1.0, 4.0, 16.0]), 2).unwrap();
+    assert!((fit[0] - 1.0f64).abs() < 1e-12 && fit[1].abs() < 1e-12 && fit[2].abs() < 1e-12);
+}
+
+#[test]
+fn test_polyfit_least_squares() {
+    // The least-squares line through (0, 0), (1, 1), (2, 1), (3, 3) is
+    // y = 0.9x - 0.1 (from the closed-form slope and intercept).
+    let x = vector::from_elems(&[0.0, 1.0, 2.0, 3.0]);
+    let y = vector::from_elems(&[0.0, 1.0, 1.0, 3.0]);
+    let fit = poly::polyfit(&x, &y, 1).unwrap();
+    assert!((fit[0] - 0.9f64).abs() < 1e-12 && (fit[1] + 0.1f64).abs() < 1e-12);
+    // A degree 0 fit is the mean.
+    let mean = poly::polyfit(&x, &y, 0).unwrap();
+    assert_eq!(mean.len(), 1);
+    assert!((mean[0] - 1.25f64).abs() < 1e-12);
+
+    // Noise that is orthogonal to the quadratics leaves the fit unchanged.
+    let x = vector::from_vec((0..7).map(|i| i as f64 - 3.0).collect());
+    let clean = x.polyval(&vector::from_elems(&[1.0, -2.0, 0.5]));
+    let noise = vector::from_elems(&[-1.0, 6.0, -15.0, 20.0, -15.0, 6.0, -1.0]);
+    let fit = poly::polyfit(&x, &(clean + noise * 0.01), 2).unwrap();
+    for (a, e) in fit.data.iter().zip([1.0, -2.0, 0.5].iter()) {
+        assert!((a - e).abs() < 1e-10);
+    }
+}
+
+#[test]
+fn test_polyfit_far_from_zero() {
+    // 21 points at x = 100..120: the raw normal equations are numerically
+    // singular here, but the fit should recover the polynomial.
+    let x = vector::from_vec((100..121).map(|i| i as f64).collect());
+    for degree in 3..6 {
+        let coeffs = vector::from_vec((0..degree + 1).map(|k| 1.0 - 0.25 * k as f64).collect());
+        let y = x.polyval(&coeffs);
+        let fit = poly::polyfit(&x, &y, degree).unwrap();
+        let err = (x.polyval(&fit) - y.clone()).max_abs() / y.max_abs();
+        assert!(err < 1e-8);
+    }
+}
+
+#[test]
+fn test_polyfit_invalid() {
+    let x = vector::from_elems(&[0.0, 1.0, 2.0]);
+    assert!(poly::polyfit(&x, &vector::from_elems(&[1.0, 2.0]), 1).is_err());
+    assert!(poly::polyfit(&x, &x, 3).is_err());
+    let repeated = vector::from_elems(&[1.0, 1.0, 1.0]);
+    assert_eq!(poly::polyfit(&repeated, &x, 2).unwrap_err(), "Sample points do not determine the fit.");
+}
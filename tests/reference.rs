@@ -0,0 +1,111 @@
+//! Property tests of the vector operations against `numrs::reference`, with
+//! random lengths and values that include NaN, infinities and subnormals.
+extern crate numrs;
+extern crate proptest;
+
+use numrs::reference;
+use numrs::vector;
+use numrs::vector::VectorOps;
+use numrs::view::VectorView;
+use proptest::collection::vec;
+use proptest::num;
+use proptest::prelude::*;
+
+/// Pairs of vectors of the same random length below 64.
+fn pair() -> impl Strategy<Value = (Vec<f64>, Vec<f64>)> {
+    (0..64usize).prop_flat_map(|n| (vec(num::f64::ANY, n), vec(num::f64::ANY, n)))
+}
+
+/// Values drawn mostly from the edge cases of division: zeros of both signs,
+/// infinities, NaN and subnormals.
+fn edge() -> impl Strategy<Value = f64> {
+    prop_oneof![
+        Just(0.0),
+        Just(-0.0),
+        Just(f64::INFINITY),
+        Just(f64::NEG_INFINITY),
+        Just(f64::NAN),
+        Just(f64::MIN_POSITIVE / 4.0),
+        Just(-5e-324),
+        num::f64::ANY,
+    ]
+}
+
+/// Like `pair`, but with most elements taken from `edge`.
+fn edge_pair() -> impl Strategy<Value = (Vec<f64>, Vec<f64>)> {
+    (0..64usize).prop_flat_map(|n| (vec(edge(), n), vec(edge(), n)))
+}
+
+/// Element-wise results must match bit for bit, including the sign of zero,
+/// with any NaN matching any NaN.
+fn same(a: &[f64], b: &[f64]) -> bool {
+    a.len() == b.len()
+        && a.iter().zip(b).all(|(x, y)| x.to_bits() == y.to_bits() || (x.is_nan() && y.is_nan()))
+}
+
+/// Reductions may be reassociated, so only approximate equality is required.
+fn close(x: f64, y: f64, tol: f64) -> bool {
+    if x.is_nan() || y.is_nan() {
+        return x.is_nan() && y.is_nan();
+    }
+    x == y || (x - y).abs() <= tol * x.abs().max(y.abs())
+}
+
+proptest! {
+    #[test]
+    fn elementwise_ops_match_reference((a, b) in pair(), k in num::f64::ANY) {
+        let (va, vb) = (vector::from_elems(&a), vector::from_elems(&b));
+        prop_assert!(same(&(va.clone() + vb.clone()).data, &reference::add(&a, &b)));
+        prop_assert!(same(&(va.clone() - vb.clone()).data, &reference::sub(&a, &b)));
+        prop_assert!(same(&(va.clone() * vb.clone()).data, &reference::mul(&a, &b)));
+        prop_assert!(same(&(va.clone() / vb.clone()).data, &reference::div(&a, &b)));
+        prop_assert!(same(&(-va.clone()).data, &reference::neg(&a)));
+        prop_assert!(same(&(va.clone() * k).data, &reference::scale(&a, k)));
+        prop_assert!(same(&va.square().data, &reference::square(&a)));
+        prop_assert!(same(&va.cumsum().data, &reference::cumsum(&a)));
+        prop_assert_eq!(va == vb, reference::eq(&a, &b));
+        prop_assert_eq!(va == va.clone(), reference::eq(&a, &a));
+    }
+
+    #[test]
+    fn division_matches_reference((a, b) in edge_pair()) {
+        let (va, vb) = (vector::from_elems(&a), vector::from_elems(&b));
+        let expected = reference::div(&a, &b);
+        prop_assert!(same(&(va.clone() / vb.clone()).data, &expected));
+        prop_assert!(same(&(va.clone() / &vb).data, &expected));
+        prop_assert!(same(&(&va / vb.clone()).data, &expected));
+        prop_assert!(same(&(&va / &vb).data, &expected));
+    }
+
+    #[test]
+    fn view_ops_match_reference((a, b) in pair()) {
+        let (va, vb) = (VectorView::from_slice(&a), VectorView::from_slice(&b));
+        prop_assert!(same(&(va + &vb).data, &reference::add(&a, &b)));
+        prop_assert!(same(&(va - &vb).data, &reference::sub(&a, &b)));
+        prop_assert!(same(&(va * &vb).data, &reference::mul(&a, &b)));
+    }
+
+    #[test]
+    fn reductions_match_reference((a, b) in pair()) {
+        let (va, vb) = (vector::from_elems(&a), vector::from_elems(&b));
+        prop_assert!(close(va.sum(), reference::sum(&a), 1e-12));
+        prop_assert!(close(va.dot(&vb).unwrap(), reference::dot(&a, &b), 1e-12));
+        prop_assert!(close(va.norm(), reference::norm(&a), 1e-12));
+        let view = VectorView::from_slice(&a);
+        prop_assert!(close(view.dot(&vb).unwrap(), reference::dot(&a, &b), 1e-12));
+    }
+}
+
+#[test]
+fn test_reference_neg_signed_zero() {
+    let neg = reference::neg(&[0.0f64, -0.0]);
+    assert!(neg[0] == 0.0 && neg[0].is_sign_negative());
+    assert!(neg[1] == 0.0 && neg[1].is_sign_positive());
+    assert!(!same(&[0.0], &[-0.0]));
+}
+
+#[test]
+#[should_panic]
+fn test_reference_length_mismatch() {
+    reference::add(&[1.0, 2.0], &[1.0]);
+}
@@ -0,0 +1,53 @@
+extern crate numrs;
+use std::thread;
+use numrs::vector;
+use numrs::vector::VectorOps;
+use numrs::shared::SharedVector;
+
+#[test]
+fn test_shared_clone_then_mutate() {
+    let base = SharedVector::from(vector::from_elems(&[1.0, 2.0, 3.0]));
+    let mut copy = base.clone();
+    assert_eq!(SharedVector::strong_count(&base), 2);
+    assert_eq!(copy.data.as_ptr(), base.data.as_ptr());
+
+    copy[1] = 20.0;
+    assert_eq!(SharedVector::strong_count(&base), 1);
+    assert_eq!(base.data, vec![1.0, 2.0, 3.0]);
+    assert_eq!(copy.data, vec![1.0, 20.0, 3.0]);
+    assert!(base != copy);
+}
+
+#[test]
+fn test_shared_unique_no_copy() {
+    let mut v = SharedVector::from(vector::from_elems(&[1.0, 2.0, 3.0]));
+    let ptr = v.data.as_ptr();
+    v[0] = 5.0;
+    assert_eq!(v.data.as_ptr(), ptr);
+    v.make_mut().data.push(4.0);
+    assert_eq!(SharedVector::strong_count(&v), 1);
+    assert_eq!(v.len(), 4);
+    assert_eq!(v.sum(), 14.0);
+
+    let ptr = v.data.as_ptr();
+    let owned = v.into_vector();
+    assert_eq!(owned.data.as_ptr(), ptr);
+}
+
+#[test]
+fn test_shared_across_threads() {
+    let base = SharedVector::from(vector::from_elems(&[1.0, 2.0, 3.0, 4.0]));
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let mut local = base.clone();
+            thread::spawn(move || {
+                local[i] = 0.0;
+                local.sum()
+            })
+        })
+        .collect();
+    let sums: Vec<f64> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert_eq!(sums, vec![9.0, 8.0, 7.0, 6.0]);
+    assert_eq!(base.sum(), 10.0);
+    assert_eq!(SharedVector::strong_count(&base), 1);
+}
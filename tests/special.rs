@@ -0,0 +1,88 @@
+extern crate numrs;
+use numrs::special;
+use numrs::vector;
+
+fn assert_rel(actual: f64, expected: f64, tol: f64) {
+    assert!((actual - expected).abs() <= tol * expected.abs(),
+            "{} differs from {}", actual, expected);
+}
+
+#[test]
+fn test_erf_erfc() {
+    // (x, erf(x), erfc(x)) from scipy.special.
+    let fixtures = [
+        (-3.0, -0.9999779095030014, 1.9999779095030015),
+        (-1.5, -0.9661051464753108, 1.9661051464753108),
+        (-0.5, -0.5204998778130465, 1.5204998778130465),
+        (-0.1, -0.1124629160182849, 1.1124629160182848),
+        (0.1, 0.1124629160182849, 0.8875370839817152),
+        (0.5, 0.5204998778130465, 0.4795001221869535),
+        (1.0, 0.8427007929497149, 0.15729920705028513),
+        (2.0, 0.9953222650189527, 0.004677734981047265),
+        (2.9, 0.9999589021219005, 4.109787809945886e-05),
+        (3.1, 0.9999883513426328, 1.1648657367199589e-05),
+        (4.5, 0.9999999998033839, 1.9661604415428873e-10),
+        (6.0, 1.0, 2.1519736712498916e-17),
+        (10.0, 1.0, 2.088487583762545e-45),
+    ];
+    for &(x, erf, erfc) in &fixtures {
+        assert_rel(special::erf(x), erf, 1e-13);
+        assert_rel(special::erfc(x), erfc, 1e-12);
+    }
+    assert_eq!(special::erf(0.0), 0.0);
+    assert_eq!(special::erfc(0.0), 1.0);
+    assert_eq!(special::erfc(30.0), 0.0);
+    assert_eq!(special::erf(f64::INFINITY), 1.0);
+    assert_eq!(special::erfc(f64::NEG_INFINITY), 2.0);
+    assert!(special::erf(f64::NAN).is_nan());
+    assert_rel(special::erf(0.5f32) as f64, 0.5204998778130465, 1e-7);
+}
+
+#[test]
+fn test_lgamma() {
+    // (x, gammaln(x)) from scipy.special.
+    let fixtures = [
+        (0.1, 2.2527126517342055),
+        (0.5, 0.5723649429247004),
+        (1.5, -0.12078223763524543),
+        (2.5, 0.2846828704729196),
+        (3.7, 1.4280723266653883),
+        (10.0, 12.801827480081467),
+        (100.0, 359.1342053695754),
+        (100000.0, 1051287.7089736566),
+        (-0.5, 1.265512123484645),
+        (-1.5, 0.8600470153764812),
+        (-2.5, -0.05624371649767457),
+        (-3.3, -0.824355805017426),
+    ];
+    for &(x, expected) in &fixtures {
+        assert_rel(special::lgamma(x), expected, 1e-13);
+    }
+    assert_eq!(special::lgamma(f64::INFINITY), f64::INFINITY);
+
+    // lgamma(n + 1) = ln(n!)
+    let mut log_factorial = 0.0f64;
+    for n in 1..30 {
+        log_factorial += (n as f64).ln();
+        assert_rel(special::lgamma(n as f64 + 1.0), log_factorial, 1e-13);
+    }
+    assert!(special::lgamma(1.0f64).abs() < 1e-15);
+    assert!(special::lgamma(2.0f64).abs() < 1e-15);
+
+    for &pole in &[0.0, -1.0, -2.0, -10.0] {
+        assert_eq!(special::lgamma(pole), f64::INFINITY);
+    }
+}
+
+#[test]
+fn test_vector_special() {
+    let v = vector::from_elems(&[-1.0f64, 0.0, 1.0, 4.0]);
+    let erf = v.erf();
+    let erfc = v.erfc();
+    for i in 0..4 {
+        assert_eq!(erf[i], special::erf(v[i]));
+        assert!((erf[i] + erfc[i] - 1.0).abs() < 1e-15);
+    }
+    assert_eq!(v.lgamma()[3], special::lgamma(4.0));
+    assert_eq!(v.lgamma()[1], f64::INFINITY);
+}
@@ -0,0 +1,163 @@
+extern crate numrs;
+use std::f64;
+use numrs::stats::{Ecdf, QuantileSketch, RunningStats};
+use numrs::vector;
+use numrs::vector::VectorOps;
+
+#[test]
+fn test_ecdf() {
+    let v = vector::from_elems(&[2.0, 8.0, 4.0, 4.0, f64::NAN, 6.0]);
+    let ecdf = Ecdf::new(&v);
+    assert_eq!(ecdf.eval(1.0), 0.0);
+    assert_eq!(ecdf.eval(4.0), 0.5);
+    assert_eq!(ecdf.eval(5.0), 0.5);
+    assert_eq!(ecdf.eval(100.0), 5.0 / 6.0);
+    assert_eq!(ecdf.eval(f64::NAN), 0.0);
+    assert_eq!(v.ecdf(2.0), 1.0 / 6.0);
+    assert_eq!(v.percentile_rank(4.0), 50.0);
+    assert!(Ecdf::new(&vector::from_elems::<f64>(&[])).eval(1.0).is_nan());
+}
+
+fn batch_mean_var(data: &[f64]) -> (f64, f64) {
+    let n = data.len() as f64;
+    let mean = data.iter().sum::<f64>() / n;
+    let var = data.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / (n - 1.0);
+    (mean, var)
+}
+
+/// Deterministic pseudo-random numbers in [0, 1).
+fn noise(n: usize) -> Vec<f64> {
+    let mut state = 0x2545f4914f6cdd1du64;
+    (0..n).map(|_| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state >> 11) as f64 / (1u64 << 53) as f64
+    }).collect()
+}
+
+#[test]
+fn test_running_stats() {
+    let data = noise(10000);
+    let (mean, var) = batch_mean_var(&data);
+
+    let mut stats = RunningStats::new();
+    stats.push_slice(&data);
+    assert_eq!(stats.count(), 10000);
+    assert!((stats.mean() - mean).abs() < 1e-12);
+    assert!((stats.var() - var).abs() < 1e-12);
+    assert!((stats.std() - var.sqrt()).abs() < 1e-12);
+    assert!((stats.sum() - data.iter().sum::<f64>()).abs() < 1e-9);
+    let v = vector::from_elems(&data);
+    assert_eq!((stats.min(), stats.max()), (v.min(), v.max()));
+
+    let mut pushed = RunningStats::new();
+    pushed.push_vector(&v);
+    assert_eq!(pushed.count(), 10000);
+    assert!((pushed.mean() - mean).abs() < 1e-12);
+    assert!((pushed.var() - var).abs() < 1e-12);
+    assert_eq!((pushed.min(), pushed.max()), (stats.min(), stats.max()));
+
+    let empty = RunningStats::<f64>::new();
+    assert!(empty.mean().is_nan() && empty.var().is_nan());
+    assert_eq!(empty.min(), None);
+}
+
+#[test]
+fn test_running_stats_combine() {
+    let data = noise(999);
+    let (mut left, mut right, mut whole) = (RunningStats::new(), RunningStats::new(), RunningStats::new());
+    left.push_slice(&data[..400]);
+    right.push_slice(&data[400..]);
+    whole.push_slice(&data);
+    let merged = left.combine(&right);
+    assert_eq!(merged.count(), whole.count());
+    assert!((merged.mean() - whole.mean()).abs() < 1e-12);
+    assert!((merged.var() - whole.var()).abs() < 1e-12);
+    assert_eq!((merged.min(), merged.max()), (whole.min(), whole.max()));
+    assert_eq!(RunningStats::new().combine(&left).count(), 400);
+}
+
+#[test]
+fn test_running_stats_large_offset() {
+    // Naive sum-of-squares loses every digit of the variance here.
+    let data: Vec<f64> = noise(1000).iter().map(|x| 1e9 + x).collect();
+    let (_, var) = batch_mean_var(&noise(1000));
+    let mut stats = RunningStats::new();
+    stats.push_slice(&data);
+    assert!((stats.var() - var).abs() < 1e-6 * var);
+}
+
+/// Checks that `est` lies between the exact quantiles of `data` at
+/// `q -/+ eps`.
+fn assert_within_rank(sorted: &[f64], q: f64, eps: f64, est: f64) {
+    let rank = |q: f64| (q.clamp(0.0, 1.0) * (sorted.len() - 1) as f64).round() as usize;
+    let (lo, hi) = (sorted[rank(q - eps)], sorted[rank(q + eps)]);
+    assert!(lo <= est && est <= hi, "q {}: {} not in [{}, {}]", q, est, lo, hi);
+}
+
+#[test]
+fn test_quantile_sketch() {
+    let eps = 0.01;
+    let uniform = noise(1000000);
+    let exponential: Vec<f64> = uniform.iter().map(|u| -(1.0 - u).ln()).collect();
+    for data in &[uniform, exponential] {
+        let mut sketch = QuantileSketch::new(eps);
+        sketch.push_vector(&vector::from_elems(data));
+        assert_eq!(sketch.count(), 1000000);
+        let mut sorted = data.clone();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        for &q in &[0.0, 0.5, 0.99, 1.0] {
+            assert_within_rank(&sorted, q, eps, sketch.quantile(q));
+        }
+    }
+}
+
+#[test]
+fn test_quantile_sketch_merge() {
+    let eps = 0.01;
+    let data = noise(50000);
+    let (mut left, mut right) = (QuantileSketch::new(eps), QuantileSketch::new(eps));
+    for &x in &data[..20000] {
+        left.push(x);
+    }
+    for &x in &data[20000..] {
+        right.push(x);
+    }
+    left.merge(&right);
+    assert_eq!(left.count(), 50000);
+    let mut sorted = data.clone();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    for &q in &[0.0, 0.1, 0.5, 0.99, 1.0] {
+        assert_within_rank(&sorted, q, eps, left.quantile(q));
+    }
+}
+
+#[test]
+fn test_quantile_sketch_small() {
+    let data = [5.0, 1.0, f64::NAN, 4.0, 2.0, 3.0];
+    let mut sketch = QuantileSketch::new(0.05);
+    for &x in &data {
+        sketch.push(x);
+    }
+    assert_eq!(sketch.count(), 5);
+    assert_eq!(sketch.quantile(0.5), 3.0);
+    assert_eq!(sketch.quantile(0.3), 2.2);
+    assert_eq!(sketch.quantile(1.0), 5.0);
+    assert!(QuantileSketch::<f32>::new(0.1).quantile(0.5).is_nan());
+}
+
+#[test]
+fn test_welford_accumulator() {
+    use numrs::stats::WelfordAccumulator;
+
+    let data: Vec<f64> = noise(1000).iter().map(|x| 1e8 + x).collect();
+    let (mean, _) = batch_mean_var(&data);
+    let (_, var) = batch_mean_var(&noise(1000));
+    let mut acc = WelfordAccumulator::new();
+    acc.push(data[0]);
+    acc.push_slice(&data[1..]);
+    assert!((acc.mean() - mean).abs() < 1e-6);
+    assert!((acc.variance() - var).abs() < 1e-6 * var);
+    assert_eq!(acc.variance(), acc.var());
+}
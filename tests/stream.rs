@@ -0,0 +1,101 @@
+extern crate numrs;
+use numrs::stream;
+use numrs::stream::{ChunkedReducer, DotAccumulator};
+use numrs::vector;
+use numrs::vector::VectorOps;
+
+#[test]
+fn test_dot_accumulator() {
+    let a: Vec<f64> = (0..37).map(|i| i as f64 * 0.5).collect();
+    let b: Vec<f64> = (0..37).map(|i| 3.0 - i as f64).collect();
+    let expected = vector::from_elems(&a).dot(&vector::from_elems(&b)).unwrap();
+
+    let mut acc = DotAccumulator::new();
+    acc.push(a[0], b[0]);
+    acc.push_chunk(&a[1..10], &b[1..10]).unwrap();
+    acc.push_chunk(&a[10..], &b[10..]).unwrap();
+    assert_eq!(acc.count(), 37);
+    assert!((acc.value() - expected).abs() < 1e-9);
+
+    let empty: DotAccumulator<i32> = DotAccumulator::default();
+    assert_eq!(empty.value(), 0);
+}
+
+#[test]
+fn test_dot_accumulator_mismatch() {
+    let mut acc = DotAccumulator::new();
+    acc.push(2.0, 3.0);
+    assert!(acc.push_chunk(&[1.0, 2.0], &[1.0]).is_err());
+    assert_eq!((acc.value(), acc.count()), (6.0, 1));
+}
+
+fn reduce(data: &[f64]) -> ChunkedReducer<f64> {
+    let mut r = ChunkedReducer::new();
+    r.push_chunk(data);
+    r
+}
+
+#[test]
+fn test_chunked_reducer_matches_whole() {
+    let data: Vec<f64> = (0..101).map(|i| ((i * 37) % 23) as f64 - 7.5).collect();
+    let v = vector::from_elems(&data);
+    let mean = v.mean();
+    let var = data.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / 100.0;
+
+    let mut r = ChunkedReducer::new();
+    for chunk in data.chunks(16) {
+        r.push_chunk(chunk);
+    }
+    let stats = r.finalize();
+    assert_eq!(stats.count, 101);
+    assert!((stats.sum - v.sum()).abs() < 1e-9);
+    assert_eq!(stats.min, v.min().unwrap());
+    assert_eq!(stats.max, v.max().unwrap());
+    assert!((stats.mean - mean).abs() < 1e-12);
+    assert!((stats.variance - var).abs() < 1e-9);
+
+    let empty = ChunkedReducer::<f64>::new().finalize();
+    assert_eq!(empty.count, 0);
+    assert!(empty.mean.is_nan() && empty.variance.is_nan() && empty.min.is_nan());
+}
+
+#[test]
+fn test_chunked_reducer_merge() {
+    let data: Vec<f64> = (0..60).map(|i| (i as f64).sin() * 10.0).collect();
+    let whole = reduce(&data).finalize();
+    let (a, b, c) = (reduce(&data[..7]), reduce(&data[7..31]), reduce(&data[31..]));
+
+    let mut left = a.clone();
+    left.merge(&b);
+    left.merge(&c);
+    let mut bc = b.clone();
+    bc.merge(&c);
+    let mut right = a.clone();
+    right.merge(&bc);
+
+    for stats in &[left.finalize(), right.finalize()] {
+        assert_eq!(stats.count, whole.count);
+        assert_eq!((stats.min, stats.max), (whole.min, whole.max));
+        assert!((stats.mean - whole.mean).abs() < 1e-12);
+        assert!((stats.variance - whole.variance).abs() < 1e-9);
+    }
+
+    let mut empty = ChunkedReducer::new();
+    empty.merge(&a);
+    assert_eq!(empty.finalize(), a.finalize());
+}
+
+#[test]
+fn test_apply_chunked() {
+    let data: Vec<f64> = (0..10).map(|i| i as f64 * 1.5).collect();
+    let bytes: Vec<u8> = data.iter().flat_map(|x| x.to_le_bytes().to_vec()).collect();
+
+    let sums = stream::apply_chunked(&mut &bytes[..], 4, |v| (v.len(), v.sum())).unwrap();
+    assert_eq!(sums, vec![(4, 9.0), (4, 33.0), (2, 25.5)]);
+
+    let exact = stream::apply_chunked(&mut &bytes[..], 5, |v| v.len()).unwrap();
+    assert_eq!(exact, vec![5, 5]);
+
+    let err = stream::apply_chunked(&mut &bytes[..bytes.len() - 3], 4, |v| v.len()).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
@@ -76,3 +76,1290 @@ fn test_vector_eq() {
     let v2 = vector::from_elems(&elem2);
     assert!(v1 != v2)
 }
+
+#[test]
+fn test_window_functions() {
+    let w = vector::hann::<f64>(5, false);
+    assert_eq!(w.len(), 5);
+    assert!(w[0].abs() < 1e-12);
+    assert!((w[2] - 1.0).abs() < 1e-12);
+    assert!((w[1] - w[3]).abs() < 1e-12);
+
+    let w = vector::hann::<f64>(4, true);
+    assert!(w[0].abs() < 1e-12);
+    assert!((w[1] - 0.5).abs() < 1e-12);
+    assert!((w[2] - 1.0).abs() < 1e-12);
+
+    let w = vector::hamming::<f64>(5, false);
+    assert!((w[0] - 0.08).abs() < 1e-12);
+    assert!((w[4] - 0.08).abs() < 1e-12);
+
+    let w = vector::blackman::<f32>(5, false);
+    assert!(w[0].abs() < 1e-6);
+    assert!((w[2] - 1.0).abs() < 1e-6);
+
+    assert_eq!(vector::hann::<f64>(0, false).len(), 0);
+    assert_eq!(vector::hann::<f64>(1, false)[0], 1.0);
+}
+
+#[test]
+fn test_vector_square() {
+    let elems = [1.0, -2.0, 3.0, -4.0, 5.0];
+    let mut v = vector::from_elems(&elems);
+    let sq = v.square();
+    assert_eq!(sq.data, vec![1.0, 4.0, 9.0, 16.0, 25.0]);
+    v.square_mut();
+    assert!(v == sq);
+}
+
+#[test]
+fn test_vector_cumsum() {
+    let elems: Vec<i64> = (1..1001).map(|i| (i * 37) % 101 - 50).collect();
+    let v = vector::from_elems(&elems);
+    let c = v.cumsum();
+    let mut sum = 0;
+    for i in 0..elems.len() {
+        sum += elems[i];
+        assert_eq!(c[i], sum);
+    }
+    assert_eq!(vector::from_elems::<f64>(&[]).cumsum().len(), 0);
+}
+
+#[test]
+fn test_vector_try_from() {
+    use std::convert::TryFrom;
+    use std::f64;
+    use numrs::vector::VectorError;
+
+    let v = Vector::try_from(vec![1.0, -2.5, 1e300]).unwrap();
+    assert_eq!(v.len(), 3);
+    assert_eq!(Vector::try_from(vec![1.0, f64::NAN]), Err(VectorError::NonFinite { index: 1 }));
+    assert_eq!(Vector::try_from(vec![f64::INFINITY]), Err(VectorError::NonFinite { index: 0 }));
+    assert!(Vector::try_from(vec![1.0f32, f32::NEG_INFINITY]).is_err());
+
+    let unchecked = vector::from_vec(vec![f64::NAN, 1.0]);
+    assert_eq!(unchecked.len(), 2);
+}
+
+#[test]
+fn test_vector_argsort() {
+    use std::f64;
+
+    let v = vector::from_elems(&[3.0, f64::NAN, 1.0, 2.0, 1.0, -5.0]);
+    let idx = v.argsort();
+    assert_eq!(idx, vec![5, 2, 4, 3, 0, 1]);
+    let sorted = v.take(&idx).unwrap();
+    assert_eq!(&sorted.data[..5], &[-5.0, 1.0, 1.0, 2.0, 3.0]);
+    assert!(sorted[5].is_nan());
+
+    let ints = vector::from_elems(&[2, 2, 1, 2]);
+    assert_eq!(ints.argsort(), vec![2, 0, 1, 3]);
+    assert_eq!(vector::from_elems::<f32>(&[]).argsort().len(), 0);
+}
+
+#[test]
+fn test_vector_take_put() {
+    let v = vector::from_elems(&[10, 20, 30, 40]);
+    assert_eq!(v.take(&[3, 0, 0, 2]).unwrap().data, vec![40, 10, 10, 30]);
+    assert_eq!(v.take(&[]).unwrap().len(), 0);
+    assert!(v.take(&[1, 4]).is_err());
+
+    let mut w = Vector::new(4, 0);
+    w.put(&[2, 0], &[7, 9]).unwrap();
+    assert_eq!(w.data, vec![9, 0, 7, 0]);
+    assert!(w.put(&[1, 4], &[1, 1]).is_err());
+    assert!(w.put(&[1], &[1, 1]).is_err());
+    assert_eq!(w.data, vec![9, 0, 7, 0]);
+}
+
+#[test]
+fn test_vector_raw_parts() {
+    let mut v = vector::from_elems(&[1.0, 2.0, 3.0]);
+    let ptr = v.as_mut_ptr();
+    assert_eq!(v.as_ptr(), ptr as *const f64);
+
+    let (raw, len, cap) = v.into_raw_parts();
+    assert_eq!(raw, ptr);
+    assert_eq!(len, 3);
+    assert!(cap >= 3);
+    unsafe {
+        *raw.offset(1) = 20.0;
+    }
+    let v = unsafe { Vector::from_raw_parts(raw, len, cap) };
+    assert_eq!(v.data, vec![1.0, 20.0, 3.0]);
+}
+
+#[test]
+fn test_vector_normalized_project() {
+    use numrs::vector::VectorOps;
+
+    let v = vector::from_elems(&[3.0f64, 4.0]);
+    assert_eq!(v.normalized().data, vec![0.6, 0.8]);
+    assert_eq!(Vector::new(2, 0.0).normalized().data, vec![0.0, 0.0]);
+
+    let x = vector::from_elems(&[2.0, 0.0]);
+    assert_eq!(v.project_onto(&x).unwrap().data, vec![3.0, 0.0]);
+    assert_eq!(v.project_onto(&Vector::new(2, 0.0)).unwrap().data, vec![0.0, 0.0]);
+    assert!(v.project_onto(&Vector::new(3, 1.0)).is_err());
+    assert!((v.normalized().norm() - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_gram_schmidt() {
+    use numrs::vector::VectorOps;
+
+    let vs = vec![vector::from_elems(&[1.0f64, 1.0, 0.0]),
+                  vector::from_elems(&[2.0, 2.0, 0.0]),
+                  vector::from_elems(&[1.0, 0.0, 1.0]),
+                  vector::from_elems(&[0.0, 1.0, 1.0])];
+    let basis = vector::gram_schmidt(&vs).unwrap();
+    // The second vector is dependent on the first and is skipped
+    assert_eq!(basis.len(), 3);
+    for i in 0..3 {
+        for j in 0..3 {
+            let expected = if i == j { 1.0 } else { 0.0 };
+            assert!((basis[i].dot(&basis[j]).unwrap() - expected).abs() < 1e-12);
+        }
+    }
+    assert!(vector::gram_schmidt(&[Vector::new(2, 1.0), Vector::new(3, 1.0)]).is_err());
+}
+
+#[test]
+fn test_vector_clamp() {
+    use std::f64;
+
+    let v = vector::from_elems(&[-3.0, -1.0, 0.0, 1.0, 2.0, f64::NAN, 5.0]);
+    let (c, clipped) = v.clamp_counted(-1.0, 2.0);
+    assert_eq!(clipped, 2);
+    assert_eq!(&c.data[..5], &[-1.0, -1.0, 0.0, 1.0, 2.0]);
+    assert!(c[5].is_nan());
+    assert_eq!(c[6], 2.0);
+    assert_eq!(v.clamp(0.0, 0.0).data[..5], [0.0; 5]);
+
+    let ints = vector::from_elems(&[1, 5, 9]);
+    let (c, clipped) = ints.clamp_counted(2, 8);
+    assert_eq!(c.data, vec![2, 5, 8]);
+    assert_eq!(clipped, 2);
+}
+
+#[test]
+fn test_row_col_products() {
+    let a = vector::from_elems(&[1, 2, 3]);
+    let b = vector::from_elems(&[4, 5, 6]);
+    assert_eq!(vector::row_times_col(&a, &b).unwrap(), 32);
+    assert!(vector::row_times_col(&a, &vector::from_elems(&[1])).is_err());
+
+    let outer = vector::col_times_row(&a, &vector::from_elems(&[1, -1]));
+    assert_eq!(outer.len(), 3);
+    assert_eq!(outer[0].data, vec![1, -1]);
+    assert_eq!(outer[2].data, vec![3, -3]);
+}
+
+#[test]
+fn test_vector_quantiles() {
+    use std::f64;
+
+    let v = vector::from_elems(&[7.0, 1.0, 3.0, 5.0, 9.0]);
+    assert_eq!(v.quantile(0.0), 1.0);
+    assert_eq!(v.quantile(1.0), 9.0);
+    assert_eq!(v.quantile(0.5), 5.0);
+    assert_eq!(v.quantile(0.125), 2.0);
+    assert_eq!(v.percentile(75.0), 7.0);
+    assert!(vector::from_elems(&[1.0, f64::NAN]).quantile(0.5).is_nan());
+    assert!(vector::from_elems::<f64>(&[]).quantile(0.5).is_nan());
+
+    let data: Vec<f64> = (0..101).map(|i| i as f64).collect();
+    let mut with_outliers = data.clone();
+    with_outliers[0] = -1000.0;
+    with_outliers[100] = 1000.0;
+    let clipped = vector::from_vec(with_outliers).clip_to_quantiles(0.05, 0.95);
+    assert_eq!(clipped[0], 5.0);
+    assert_eq!(clipped[50], 50.0);
+    assert_eq!(clipped[100], 95.0);
+}
+
+#[test]
+#[should_panic]
+fn test_vector_clip_to_quantiles_invalid() {
+    vector::from_elems(&[1.0, 2.0]).clip_to_quantiles(0.9, 0.1);
+}
+
+#[test]
+fn test_vector_masked_dot() {
+    use std::f64;
+
+    let a = vector::from_elems(&[1.0, f64::NAN, 3.0, 4.0]);
+    let b = vector::from_elems(&[2.0, 5.0, f64::NAN, 0.5]);
+    assert_eq!(a.masked_dot(&b).unwrap(), (4.0, 2));
+
+    let all_missing = vector::from_elems(&[f64::NAN, f64::NAN]);
+    assert_eq!(all_missing.masked_dot(&all_missing).unwrap(), (0.0, 0));
+
+    assert!(a.masked_dot(&vector::from_elems(&[1.0])).is_err());
+}
+
+#[test]
+fn test_vector_resample() {
+    let v = vector::from_elems(&[0.0, 10.0, 20.0]);
+    assert_eq!(v.resample(5).data, vec![0.0, 5.0, 10.0, 15.0, 20.0]);
+    assert_eq!(v.resample(2).data, vec![0.0, 20.0]);
+    assert_eq!(v.resample(3).data, v.data);
+    assert_eq!(v.resample(1).data, vec![0.0]);
+    assert!(v.resample(0).data.is_empty());
+    assert_eq!(vector::from_elems(&[4.0]).resample(3).data, vec![4.0; 3]);
+
+    let down = vector::from_elems(&[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).resample(3);
+    assert_eq!(down.data, vec![0.0, 3.0, 6.0]);
+}
+
+#[test]
+#[should_panic]
+fn test_vector_resample_empty() {
+    vector::from_elems::<f64>(&[]).resample(2);
+}
+
+#[test]
+fn test_vector_from_str() {
+    let v: Vector<f64> = "[1.0, 2.5, -3e2]".parse().unwrap();
+    assert_eq!(v.data, vec![1.0, 2.5, -300.0]);
+    let v: Vector<f32> = " 1 2.5,-3E2 ,\t4 ".parse().unwrap();
+    assert_eq!(v.data, vec![1.0, 2.5, -300.0, 4.0]);
+    assert_eq!("[1, 2,]".parse::<Vector<f64>>().unwrap().data, vec![1.0, 2.0]);
+    assert!("[]".parse::<Vector<f64>>().unwrap().data.is_empty());
+    assert!("".parse::<Vector<f64>>().unwrap().data.is_empty());
+
+    let special: Vector<f64> = "[inf, -inf, NaN]".parse().unwrap();
+    assert_eq!(special[0], f64::INFINITY);
+    assert_eq!(special[1], f64::NEG_INFINITY);
+    assert!(special[2].is_nan());
+}
+
+#[test]
+fn test_vector_display_round_trip() {
+    let v = vector::from_elems(&[1.0, -0.1, 1e-300, 123456789.125, f64::INFINITY]);
+    assert_eq!(format!("{}", vector::from_elems(&[1.0, 2.5])), "[1, 2.5]");
+    assert!(v.to_string().parse::<Vector<f64>>().unwrap() == v);
+}
+
+#[test]
+fn test_vector_from_str_errors() {
+    assert_eq!("[1, 2x, 3]".parse::<Vector<f64>>().err().unwrap(),
+               "Invalid number '2x' at offset 4.");
+    assert_eq!("  1;2".parse::<Vector<f64>>().err().unwrap(),
+               "Invalid number '1;2' at offset 2.");
+    assert_eq!("[1, 2".parse::<Vector<f64>>().err().unwrap(),
+               "Missing closing ']' at offset 5.");
+    assert_eq!("1 2]".parse::<Vector<f64>>().err().unwrap(),
+               "Invalid number '2]' at offset 2.");
+}
+
+#[test]
+fn test_vector_cosine_distance() {
+    let a = vector::from_elems(&[1.0f64, 0.0]);
+    let b = vector::from_elems(&[0.0, 2.0]);
+    assert_eq!(a.cosine_similarity(&b).unwrap(), 0.0);
+    assert_eq!(a.cosine_distance(&b).unwrap(), 1.0);
+    assert_eq!(a.cosine_distance(&(a.clone() * 3.0)).unwrap(), 0.0);
+    assert_eq!(a.cosine_distance(&-a.clone()).unwrap(), 2.0);
+    assert!(a.cosine_distance(&vector::from_elems(&[0.0, 0.0])).unwrap().is_nan());
+    assert!(a.cosine_distance(&vector::from_elems(&[1.0])).is_err());
+}
+
+#[test]
+fn test_vector_entropy() {
+    let uniform = vector::from_elems(&[0.25f64; 4]);
+    assert!((uniform.entropy() - 4f64.ln()).abs() < 1e-12);
+    assert_eq!(vector::from_elems(&[0.0, 1.0, 0.0f64]).entropy(), 0.0);
+    let p = vector::from_elems(&[0.5, 0.5, 0.0f64]);
+    assert!((p.entropy() - 2f64.ln()).abs() < 1e-12);
+}
+
+#[test]
+fn test_vector_kl_divergence() {
+    let p = vector::from_elems(&[0.5, 0.5, 0.0f64]);
+    let q = vector::from_elems(&[0.25, 0.25, 0.5f64]);
+    assert_eq!(p.kl_divergence(&p).unwrap(), 0.0);
+    assert!((p.kl_divergence(&q).unwrap() - 2f64.ln()).abs() < 1e-12);
+    assert_eq!(q.kl_divergence(&p).unwrap(), f64::INFINITY);
+    assert!(p.kl_divergence(&vector::from_elems(&[1.0])).is_err());
+}
+
+#[test]
+fn test_vector_whiten_diag() {
+    let x = vector::from_elems(&[2.0, -3.0, 1.0]);
+    let var = vector::from_elems(&[4.0, 9.0, 0.25]);
+    assert_eq!(x.whiten_diag(&var).unwrap().data, vec![1.0, -1.0, 2.0]);
+    assert_eq!(x.whiten_diag(&vector::from_elems(&[1.0, 0.0, 1.0])).err().unwrap(),
+               "Variance at index 1 is not positive.");
+    assert!(x.whiten_diag(&vector::from_elems(&[1.0, 1.0, f64::NAN])).is_err());
+    assert!(x.whiten_diag(&vector::from_elems(&[1.0])).is_err());
+}
+
+#[test]
+fn test_vector_dot_scaled() {
+    use numrs::vector::VectorOps;
+
+    let a = vector::from_elems(&[3e20f32, 1e20, -2e20]);
+    let b = vector::from_elems(&[1e-10f32, 4e-10, 1e-10]);
+    assert!(a.dot(&a).unwrap().is_infinite());
+    let scaled = a.dot_scaled(&b).unwrap();
+    assert!((scaled - 5e10).abs() <= 5e10 * 1e-6);
+
+    let c = vector::from_elems(&[1e19f32, 1e19]);
+    let d = vector::from_elems(&[1e19f32, -1e19]);
+    assert_eq!(c.dot_scaled(&d).unwrap(), 0.0);
+
+    let x = vector::from_elems(&[1.5, -2.0, 0.25]);
+    let y = vector::from_elems(&[4.0, 0.5, 8.0]);
+    assert_eq!(x.dot_scaled(&y).unwrap(), x.dot(&y).unwrap());
+    assert_eq!(vector::from_elems::<f64>(&[0.0, 0.0]).dot_scaled(&vector::from_elems(&[1.0, 2.0])).unwrap(), 0.0);
+    assert!(x.dot_scaled(&vector::from_elems(&[1.0])).is_err());
+
+    // Subnormal elements must not make the scale factor overflow.
+    let tiny = vector::from_elems(&[1e-40f32, 2e-40]);
+    let r = tiny.dot_scaled(&vector::from_elems(&[1.0f32, 1.0])).unwrap();
+    assert!((r - 3e-40).abs() <= 3e-40 * 1e-3);
+    let r = vector::from_elems(&[1e-310f64]).dot_scaled(&vector::from_elems(&[1e300])).unwrap();
+    assert!((r - 1e-10).abs() <= 1e-10 * 1e-6);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_vector_par_apply() {
+    let v = vector::from_vec((0..10001).map(|i| i as f32 * 0.5).collect());
+    let expected: Vec<f32> = v.data.iter().map(|x| x.sin() * 2.0).collect();
+    assert_eq!(v.par_apply(|x| x.sin() * 2.0).data, expected);
+    assert!(vector::from_elems::<f32>(&[]).par_apply(|x| x + 1.0).data.is_empty());
+}
+
+#[test]
+fn test_vector_dot_checked() {
+    use numrs::vector::VectorError;
+
+    let a = vector::from_elems(&[1.0, 2.0]);
+    assert_eq!(a.dot_checked(&vector::from_elems(&[3.0, 4.0])), Ok(11.0));
+    let err = a.dot_checked(&vector::from_elems(&[3.0])).err().unwrap();
+    assert_eq!(err, VectorError::DimensionMismatch { left: 2, right: 1 });
+    assert_eq!(err.to_string(), "Vectors are not conformable: lengths 2 and 1.");
+    let empty = vector::from_elems::<f64>(&[]);
+    assert_eq!(empty.dot_checked(&empty), Err(VectorError::EmptyInput));
+}
+
+#[test]
+fn test_vector_sum_product_of_vectors() {
+    let vs = vec![vector::from_elems(&[1.0f32, 2.0]),
+                  vector::from_elems(&[3.0, 4.0]),
+                  vector::from_elems(&[5.0, 6.0])];
+    let total: Vector<f32> = vs.iter().sum();
+    assert_eq!(total.data, vec![9.0, 12.0]);
+    let prod: Vector<f32> = vs.iter().product();
+    assert_eq!(prod.data, vec![15.0, 48.0]);
+    let owned: Vector<f32> = vs.into_iter().sum();
+    assert_eq!(owned.data, vec![9.0, 12.0]);
+
+    let empty: Vector<f32> = Vec::<Vector<f32>>::new().into_iter().sum();
+    assert!(empty.data.is_empty());
+    let empty: Vector<f32> = Vec::<Vector<f32>>::new().into_iter().product();
+    assert!(empty.data.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Vectors are not conformable for addition.")]
+fn test_vector_sum_mismatch() {
+    let vs = [vector::from_elems(&[1.0, 2.0]), vector::from_elems(&[3.0])];
+    let _: Vector<f64> = vs.iter().sum();
+}
+
+#[test]
+fn test_vector_extend() {
+    let mut v = vector::from_elems(&[1, 2, 3]);
+    v.extend(4..10);
+    assert_eq!(v.data, (1..10).collect::<Vec<i32>>());
+    v.extend(Vec::new());
+    assert_eq!(v.len(), 9);
+}
+
+#[test]
+fn test_vector_huber() {
+    let v = vector::from_elems(&[-3.0f64, -1.0, 0.0, 0.5, 1.0, 2.0, f64::NAN]);
+    let h = v.huber(1.0);
+    assert_eq!(&h.data[..6], &[2.5, 0.5, 0.0, 0.125, 0.5, 1.5]);
+    assert!(h[6].is_nan());
+}
+
+#[test]
+#[should_panic(expected = "Huber delta should be positive.")]
+fn test_vector_huber_zero_delta() {
+    vector::from_elems(&[1.0f32]).huber(0.0);
+}
+
+#[test]
+fn test_vector_rolling_correlation() {
+    let x = vector::from_elems(&[1.0f64, 2.0, 3.0, 4.0, 5.0, 4.0, 2.0]);
+    let y = vector::from_elems(&[2.0f64, 4.0, 6.0, 8.0, 6.0, 4.0, 2.0]);
+    let r = x.rolling_correlation(&y, 3).unwrap();
+    assert_eq!(r.len(), 5);
+    for &c in &r.data[..2] {
+        assert!((c - 1.0).abs() < 1e-12);
+    }
+    // Windows computed directly.
+    for (i, &c) in r.data.iter().enumerate() {
+        let (a, b) = (&x.data[i..i + 3], &y.data[i..i + 3]);
+        let (ma, mb) = (a.iter().sum::<f64>() / 3.0, b.iter().sum::<f64>() / 3.0);
+        let cov: f64 = a.iter().zip(b).map(|(p, q)| (p - ma) * (q - mb)).sum();
+        let va: f64 = a.iter().map(|p| (p - ma) * (p - ma)).sum();
+        let vb: f64 = b.iter().map(|q| (q - mb) * (q - mb)).sum();
+        assert!((c - cov / (va * vb).sqrt()).abs() < 1e-9);
+    }
+
+    let flat = vector::from_elems(&[1.0f64, 1.0, 1.0]);
+    assert!(flat.rolling_correlation(&y.clone(), 2).is_err());
+    assert!(flat.rolling_correlation(&vector::from_elems(&[1.0, 2.0, 3.0]), 2).unwrap()[0].is_nan());
+    assert!(x.rolling_correlation(&y, 1).is_err());
+    assert!(x.rolling_correlation(&y, 8).is_err());
+}
+
+#[test]
+fn test_vector_apply_window() {
+    let signal = vector::from_elems(&[2.0f64, 2.0, 2.0, 2.0, 2.0]);
+    let w = vector::hann(5, false);
+    let out = signal.apply_window(&w).unwrap();
+    for i in 0..5 {
+        assert_eq!(out[i], 2.0 * w[i]);
+    }
+    assert_eq!(out[0], 0.0);
+    assert!(signal.apply_window(&vector::hann(4, false)).is_err());
+}
+
+#[test]
+fn test_vector_f32_wide_accumulation() {
+    use numrs::vector::VectorOps;
+
+    // 2^24 + 1 is not representable in f32, so adding ones to 2^24 in f32
+    // never changes the sum.
+    let mut data = vec![16777216.0f32];
+    data.extend(vec![1.0f32; 1001]);
+    let v = vector::from_vec(data);
+    assert_eq!(v.sum(), 16777216.0f32);
+    assert_eq!(v.sum_f64(), 16778217.0);
+
+    let ones = Vector::new(v.len(), 1.0f32);
+    assert_eq!(v.dot(&ones).unwrap(), 16777216.0f32);
+    assert_eq!(v.dot_f64(&ones).unwrap(), 16778217.0);
+    assert!(v.dot_f64(&Vector::new(3, 1.0f32)).is_err());
+
+    // 0.1f32 is 13421773 / 2^27 exactly; 1000 of its squares sum to an exact
+    // rational that f64 represents to within a few ulps.
+    let tenths = Vector::new(1000, 0.1f32);
+    let exact = 1000.0 * (13421773.0f64 / 134217728.0).powi(2);
+    assert!((tenths.dot_f64(&tenths).unwrap() - exact).abs() <= 1e-13 * exact);
+    assert!((tenths.dot(&tenths).unwrap() as f64 - exact).abs() > 1e-6 * exact);
+}
+
+#[test]
+fn test_vector_energy_power() {
+    let v = vector::from_elems(&[3.0f64, -4.0, 0.0, 1.0]);
+    assert_eq!(v.energy(), 26.0);
+    assert_eq!(v.power(), 6.5);
+    assert_eq!(v.rms(), 6.5f64.sqrt());
+
+    let empty = vector::from_elems::<f32>(&[]);
+    assert_eq!(empty.energy(), 0.0);
+    assert!(empty.power().is_nan());
+    assert!(empty.rms().is_nan());
+}
+
+#[test]
+fn test_vector_zero_crossings() {
+    assert_eq!(vector::from_elems(&[1.0, -1.0, 2.0, 3.0, -0.5]).zero_crossings(), 3);
+    assert_eq!(vector::from_elems(&[1, 0, 0, -1]).zero_crossings(), 1);
+    assert_eq!(vector::from_elems(&[1, 0, 1, 0]).zero_crossings(), 0);
+    assert_eq!(vector::from_elems(&[0.0, -2.0, f64::NAN, 2.0]).zero_crossings(), 1);
+    assert_eq!(vector::from_elems::<f32>(&[]).zero_crossings(), 0);
+}
+
+#[test]
+fn test_vector_autocorrelation() {
+    let v = vector::from_elems(&[1.0f64, 2.0, 3.0, 4.0]);
+    assert_eq!(v.autocorrelation(0), 1.0);
+    assert_eq!(v.autocorrelation(1), 20.0 / 30.0);
+    assert_eq!(v.autocorrelation(3), 4.0 / 30.0);
+    assert_eq!(v.autocorrelation(4), 0.0);
+    assert_eq!(v.autocorrelation_all().data, vec![1.0, 20.0 / 30.0, 11.0 / 30.0, 4.0 / 30.0]);
+
+    // A period-4 signal correlates best with itself at lag 4.
+    let periodic = vector::from_elems(&[1.0f64, 0.0, -1.0, 0.0, 1.0, 0.0, -1.0, 0.0]);
+    assert_eq!(periodic.autocorrelation(2), -0.75);
+    assert_eq!(periodic.autocorrelation(4), 0.5);
+
+    assert!(Vector::new(3, 0.0f32).autocorrelation(1).is_nan());
+    assert!(vector::from_elems::<f64>(&[]).autocorrelation_all().data.is_empty());
+}
+
+#[test]
+fn test_vector_fallible_allocation() {
+    use numrs::vector::VectorError;
+
+    let v = Vector::<f64>::try_zeros(1000).unwrap();
+    assert_eq!(v.data, vec![0.0; 1000]);
+    assert_eq!(Vector::try_filled(3, 7).unwrap().data, vec![7, 7, 7]);
+    let empty = Vector::<f32>::try_with_capacity(64).unwrap();
+    assert!(empty.data.is_empty() && empty.data.capacity() >= 64);
+
+    let huge = usize::MAX / 2;
+    let err = Vector::<f64>::try_zeros(huge).err().unwrap();
+    assert_eq!(err, VectorError::AllocationFailed { requested: huge });
+    assert!(err.to_string().contains(&huge.to_string()));
+    assert!(Vector::<u8>::try_with_capacity(huge * 2).is_err());
+}
+
+#[test]
+fn test_vector_checked_ops() {
+    use numrs::vector::VectorError;
+
+    let a = vector::from_elems(&[1, 2, 3]);
+    let b = vector::from_elems(&[4, 5, 6]);
+    assert_eq!(a.checked_add(&b).unwrap().data, vec![5, 7, 9]);
+    assert_eq!(a.checked_sub(&b).unwrap().data, vec![-3, -3, -3]);
+    assert_eq!(a.checked_mul(&b).unwrap().data, vec![4, 10, 18]);
+    assert_eq!(a.checked_add(&vector::from_elems(&[1])).err(),
+               Some(VectorError::DimensionMismatch { left: 3, right: 1 }));
+}
+
+#[test]
+fn test_vector_solve_diagonal() {
+    let d = vector::from_elems(&[2.0f64, -4.0, 0.5]);
+    let x = d.solve_diagonal(&vector::from_elems(&[1.0, 2.0, 3.0])).unwrap();
+    assert_eq!(x.data, vec![0.5, -0.5, 6.0]);
+    assert_eq!(vector::from_elems(&[1.0, 0.0]).solve_diagonal(&vector::from_elems(&[1.0, 1.0])),
+               Err("Diagonal entry at index 1 is zero.".to_string()));
+    assert!(d.solve_diagonal(&vector::from_elems(&[1.0])).is_err());
+}
+
+#[test]
+fn test_vector_householder() {
+    use numrs::vector::VectorOps;
+
+    fn reflect(x: &Vector<f64>, v: &Vector<f64>, beta: f64) -> Vector<f64> {
+        let s = beta * v.dot(x).unwrap();
+        x.clone() - v.clone() * s
+    }
+
+    for x in &[vector::from_elems(&[3.0, 4.0]),
+               vector::from_elems(&[-1.0, 2.0, 2.0]),
+               vector::from_elems(&[0.0, 1.0, 1.0, 1.0])] {
+        let (v, beta) = x.householder();
+        assert_eq!(v[0], 1.0);
+        let y = reflect(x, &v, beta);
+        let expected = if x[0] >= 0.0 { -x.norm() } else { x.norm() };
+        assert!((y[0] - expected).abs() < 1e-12);
+        for i in 1..y.len() {
+            assert!(y[i].abs() < 1e-12);
+        }
+    }
+
+    let (v, beta) = vector::from_elems(&[3.0f64, 0.0]).householder();
+    assert_eq!((v.data, beta), (vec![1.0, 0.0], 0.0));
+}
+
+#[test]
+fn test_vector_dot_indexed() {
+    use numrs::vector::VectorOps;
+
+    let table = vector::from_elems(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+    let weights = vector::from_elems(&[0.5, -1.0, 2.0, 1.0]);
+    let indices = [4, 0, 4, 2];
+    let gathered = table.take(&indices).unwrap().dot(&weights).unwrap();
+    assert_eq!(table.dot_indexed(&weights, &indices).unwrap(), gathered);
+    assert_eq!(table.dot_indexed(&weights, &indices).unwrap(), 14.5);
+
+    let empty = vector::from_elems::<f64>(&[]);
+    assert_eq!(table.dot_indexed(&empty, &[]).unwrap(), 0.0);
+    assert_eq!(table.dot_indexed(&weights, &[0, 1, 5, 2]),
+               Err("Index 5 at position 2 out of bounds for vector of length 5.".to_string()));
+    assert!(table.dot_indexed(&weights, &[0, 1]).is_err());
+}
+
+#[test]
+fn test_vector_axpy() {
+    let mut y = vector::from_elems(&[1.0, 2.0, 3.0]);
+    y.axpy(2.0, &vector::from_elems(&[1.0, 0.0, -1.0])).unwrap();
+    assert_eq!(y.data, vec![3.0, 2.0, 1.0]);
+    assert!(y.axpy(1.0, &vector::from_elems(&[1.0])).is_err());
+}
+
+#[test]
+fn test_vector_rotate() {
+    let base = vector::from_elems(&[1, 2, 3, 4, 5]);
+    for &(n, ref left) in &[(0, [1, 2, 3, 4, 5]), (2, [3, 4, 5, 1, 2]), (5, [1, 2, 3, 4, 5]),
+                            (7, [3, 4, 5, 1, 2])] {
+        let mut v = base.clone();
+        v.rotate_left(n);
+        assert_eq!(&v.data[..], &left[..]);
+        v.rotate_right(n);
+        assert_eq!(v, base);
+    }
+    let mut v = base.clone();
+    v.rotate_right(6);
+    assert_eq!(v.data, vec![5, 1, 2, 3, 4]);
+
+    let mut empty = vector::from_elems::<f64>(&[]);
+    empty.rotate_left(3);
+    empty.rotate_right(3);
+    assert!(empty.data.is_empty());
+}
+
+#[test]
+fn test_vector_shift() {
+    let v = vector::from_elems(&[1.0, 2.0, 3.0, 4.0]);
+    assert_eq!(v.shift(0, 0.0), v);
+    assert_eq!(v.shift(1, 0.0).data, vec![0.0, 1.0, 2.0, 3.0]);
+    assert_eq!(v.shift(-1, 0.0).data, vec![2.0, 3.0, 4.0, 0.0]);
+    assert_eq!(v.shift(3, -1.0).data, vec![-1.0, -1.0, -1.0, 1.0]);
+    assert_eq!(v.shift(4, 9.0).data, vec![9.0; 4]);
+    assert_eq!(v.shift(-10, 9.0).data, vec![9.0; 4]);
+    assert!(v.shift(2, f64::NAN)[0].is_nan());
+    assert!(vector::from_elems::<i32>(&[]).shift(1, 0).data.is_empty());
+}
+
+#[test]
+fn test_vector_max_abs() {
+    assert_eq!(vector::from_elems(&[1.0, -7.5, 3.0]).max_abs(), 7.5);
+    assert_eq!(vector::from_elems(&[f32::NAN, -2.0]).max_abs(), 2.0);
+    assert_eq!(vector::from_elems(&[1.0, f64::NEG_INFINITY]).max_abs(), f64::INFINITY);
+    assert_eq!(vector::from_elems::<f64>(&[]).max_abs(), 0.0);
+}
+
+#[test]
+fn test_vector_pad() {
+    use numrs::vector::PadMode;
+
+    // Expected values from numpy.pad.
+    let v = vector::from_elems(&[1, 2, 3, 4, 5]);
+    assert_eq!(v.pad(2, 3, PadMode::Constant(0)).data, vec![0, 0, 1, 2, 3, 4, 5, 0, 0, 0]);
+    assert_eq!(v.pad(2, 3, PadMode::Edge).data, vec![1, 1, 1, 2, 3, 4, 5, 5, 5, 5]);
+    assert_eq!(v.pad(2, 3, PadMode::Reflect).data, vec![3, 2, 1, 2, 3, 4, 5, 4, 3, 2]);
+
+    let short = vector::from_elems(&[1.0, 2.0, 3.0]);
+    assert_eq!(short.pad(5, 0, PadMode::Reflect).data, vec![2.0, 1.0, 2.0, 3.0, 2.0, 1.0, 2.0, 3.0]);
+    assert_eq!(short.pad(0, 6, PadMode::Reflect).data,
+               vec![1.0, 2.0, 3.0, 2.0, 1.0, 2.0, 3.0, 2.0, 1.0]);
+    assert_eq!(vector::from_elems(&[7]).pad(2, 2, PadMode::Reflect).data, vec![7; 5]);
+
+    assert_eq!(short.pad(0, 0, PadMode::Edge), short);
+    assert_eq!(short.pad(0, 0, PadMode::Constant(1.0)), short);
+
+    let empty = vector::from_elems::<f64>(&[]);
+    assert_eq!(empty.pad(1, 2, PadMode::Constant(4.0)).data, vec![4.0; 3]);
+    assert!(empty.pad(0, 0, PadMode::Reflect).data.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Cannot pad an empty vector in edge or reflect mode.")]
+fn test_vector_pad_empty_edge() {
+    vector::from_elems::<f64>(&[]).pad(1, 0, numrs::vector::PadMode::Edge);
+}
+
+#[test]
+fn test_vector_isclose() {
+    let a = vector::from_elems(&[1.0, 100.0, 0.0, 1e-9]);
+    let b = vector::from_elems(&[1.0 + 1e-9, 101.0, 1e-9, 0.0]);
+    assert_eq!(a.isclose(&b, 1e-5, 1e-8, false).unwrap(), vec![true, false, true, true]);
+    assert!(a.isclose(&vector::from_elems(&[1.0]), 1e-5, 1e-8, false).is_err());
+
+    // |a - b| = 10 is within 0.1 * |b| for b = 100 but not for b = 90.
+    let (x, y) = (vector::from_elems(&[90.0]), vector::from_elems(&[100.0]));
+    assert_eq!(x.isclose(&y, 0.1, 0.0, false).unwrap(), vec![true]);
+    assert_eq!(y.isclose(&x, 0.1, 0.0, false).unwrap(), vec![false]);
+
+    let nan = vector::from_elems(&[f64::NAN, f64::NAN]);
+    let mixed = vector::from_elems(&[f64::NAN, 1.0]);
+    assert_eq!(nan.isclose(&mixed, 1e-5, 1e-8, false).unwrap(), vec![false, false]);
+    assert_eq!(nan.isclose(&mixed, 1e-5, 1e-8, true).unwrap(), vec![true, false]);
+
+    let inf = vector::from_elems(&[f64::INFINITY, f64::INFINITY, f64::NEG_INFINITY]);
+    let other = vector::from_elems(&[f64::INFINITY, f64::NEG_INFINITY, 1e308]);
+    assert_eq!(inf.isclose(&other, 1.0, 1.0, false).unwrap(), vec![true, false, false]);
+
+    // Zero tolerances only accept exact equality.
+    let c = vector::from_elems(&[1.0, 2.0]);
+    assert_eq!(c.isclose(&vector::from_elems(&[1.0, 2.0 + 1e-15]), 0.0, 0.0, false).unwrap(),
+               vec![true, false]);
+}
+
+#[test]
+fn test_vector_allclose() {
+    let a = vector::from_elems(&[1.0, 2.0, 3.0]);
+    assert!(a.allclose(&vector::from_elems(&[1.0, 2.0 + 1e-10, 3.0]), 1e-5, 1e-8));
+    assert!(!a.allclose(&vector::from_elems(&[1.0, 2.1, 3.0]), 1e-5, 1e-8));
+    assert!(!a.allclose(&vector::from_elems(&[1.0, 2.0]), 1e-5, 1e-8));
+    assert!(!vector::from_elems(&[f64::NAN]).allclose(&vector::from_elems(&[f64::NAN]), 1.0, 1.0));
+}
+
+#[test]
+fn test_vector_bilinear() {
+    use numrs::matrix;
+
+    let m = matrix::from_elems(2, 3, &[1, 2, 3, 4, 5, 6]);
+    let x = vector::from_elems(&[1, -1]);
+    let y = vector::from_elems(&[1, 0, 2]);
+    assert_eq!(x.bilinear(&m, &y), Ok(-9));
+    assert!(y.bilinear(&m, &y).is_err());
+    assert!(x.bilinear(&m, &x).is_err());
+}
+
+#[test]
+fn test_vector_any_all_count_nonzero() {
+    let zeros = Vector::new(37, 0.0f32);
+    assert!(!zeros.any() && !zeros.all());
+    assert_eq!(zeros.count_nonzero(), 0);
+
+    let ones = Vector::new(37, 1);
+    assert!(ones.any() && ones.all());
+    assert_eq!(ones.count_nonzero(), 37);
+
+    let mut last = Vector::new(37, 0.0f64);
+    last[36] = 2.0;
+    assert!(last.any() && !last.all());
+    assert_eq!(last.count_nonzero(), 1);
+
+    let special = vector::from_elems(&[-0.0, f64::NAN, 1.0]);
+    assert_eq!(special.count_nonzero(), 2);
+    assert!(!special.all());
+    assert!(vector::from_elems(&[f64::NAN]).all());
+
+    let empty = vector::from_elems::<f64>(&[]);
+    assert!(!empty.any() && empty.all());
+    assert_eq!(empty.count_nonzero(), 0);
+}
+
+#[test]
+fn test_vector_quadratic_form() {
+    use numrs::matrix;
+
+    let m = matrix::from_elems(2, 2, &[2.0, 1.0, 1.0, 3.0]);
+    let x = vector::from_elems(&[1.0, -2.0]);
+    assert_eq!(x.quadratic_form(&m), Ok(10.0));
+    assert_eq!(x.quadratic_form(&m), x.bilinear(&m, &x));
+    assert!(vector::from_elems(&[1.0]).quadratic_form(&m).is_err());
+    assert_eq!(x.quadratic_form(&matrix::from_elems(2, 1, &[1.0, 2.0])),
+               Err("The matrix should be a square matrix.".to_string()));
+}
+
+#[test]
+fn test_vector_angle_conversion() {
+    use std::f64::consts::PI;
+
+    let deg = vector::from_elems(&[0.0, 90.0, 180.0, -360.0]);
+    assert_eq!(deg.to_radians().data, vec![0.0, PI / 2.0, PI, -2.0 * PI]);
+    assert_eq!(deg.to_radians().to_degrees(), deg);
+}
+
+#[test]
+fn test_vector_wrap_angles() {
+    use std::f64::consts::PI;
+
+    let v = vector::from_elems(&[0.0, PI, -PI, 2.0 * PI, -2.0 * PI, PI / 2.0, -PI / 2.0]);
+    assert_eq!(v.wrap_to_pi().data, vec![0.0, PI, PI, 0.0, 0.0, PI / 2.0, -PI / 2.0]);
+    assert_eq!(v.wrap_to_2pi().data, vec![0.0, PI, PI, 0.0, 0.0, PI / 2.0, 1.5 * PI]);
+
+    let big = vector::from_elems(&[100.0, -100.0, 7.0 * PI + 0.5, -1e-20]);
+    let w = big.wrap_to_pi();
+    let w2 = big.wrap_to_2pi();
+    for i in 0..big.len() {
+        assert!(w[i] > -PI && w[i] <= PI);
+        assert!(w2[i] >= 0.0 && w2[i] < 2.0 * PI);
+        let turns = (big[i] - w[i]) / (2.0 * PI);
+        assert!((turns - turns.round()).abs() < 1e-12);
+        let turns = (big[i] - w2[i]) / (2.0 * PI);
+        assert!((turns - turns.round()).abs() < 1e-12);
+    }
+    assert!((w[2] - (-PI + 0.5)).abs() < 1e-12);
+}
+
+#[test]
+fn test_vector_unwrap_phase() {
+    let ramp = vector::from_vec((0..50).map(|i| 0.9 * i as f64 - 3.0).collect());
+    let unwrapped = ramp.wrap_to_pi().unwrap_phase();
+    for i in 0..50 {
+        assert!((unwrapped[i] - ramp[i]).abs() < 1e-12);
+    }
+    let falling = vector::from_vec((0..50).map(|i| 2.0 - 1.1 * i as f64).collect());
+    let unwrapped = falling.wrap_to_pi().unwrap_phase();
+    for i in 0..50 {
+        assert!((unwrapped[i] - falling[i]).abs() < 1e-12);
+    }
+
+    assert!(vector::from_elems::<f64>(&[]).unwrap_phase().data.is_empty());
+    assert_eq!(vector::from_elems(&[5.0]).unwrap_phase().data, vec![5.0]);
+    assert_eq!(vector::from_elems::<f32>(&[]).wrap_to_pi().len(), 0);
+}
+
+#[test]
+fn test_vector_to_f32_clamped() {
+    let v = vector::from_elems(&[1.5, 1e39, -1e39, f64::INFINITY, f64::NEG_INFINITY, 0.1]);
+    let lossy = v.to_f32();
+    assert_eq!(lossy.data, vec![1.5, f32::INFINITY, f32::NEG_INFINITY, f32::INFINITY,
+                                f32::NEG_INFINITY, 0.1]);
+    let clamped = v.to_f32_clamped();
+    assert_eq!(clamped.data, vec![1.5, f32::MAX, f32::MIN, f32::INFINITY, f32::NEG_INFINITY, 0.1]);
+    assert!(vector::from_elems(&[f64::NAN]).to_f32_clamped()[0].is_nan());
+}
+
+#[test]
+fn test_vector_hyperbolic() {
+    let x = vector::from_vec((-40..=40).map(|i| i as f64 * 0.25).collect());
+    let (s, c) = (x.sinh(), x.cosh());
+    for i in 0..x.len() {
+        let identity = c[i] * c[i] - s[i] * s[i];
+        assert!((identity - 1.0).abs() < 1e-12 * c[i] * c[i]);
+    }
+    let t = x.tanh_v();
+    for i in 0..x.len() {
+        assert!((t[i] - s[i] / c[i]).abs() < 1e-12);
+    }
+
+    let inverse = vector::from_elems(&[0.0f64, 0.5, -2.0]);
+    let back = inverse.sinh().asinh();
+    for i in 0..3 {
+        assert!((back[i] - inverse[i]).abs() < 1e-12);
+    }
+    let mut m = vector::from_elems(&[1.0f64, 2.0]);
+    m.cosh_mut();
+    m.acosh_mut();
+    assert!((m[0] - 1.0).abs() < 1e-12 && (m[1] - 2.0).abs() < 1e-12);
+
+    let big = vector::from_elems(&[1000.0f64, -1000.0]);
+    assert_eq!(big.sinh().data, vec![f64::INFINITY, f64::NEG_INFINITY]);
+    assert_eq!(big.cosh().data, vec![f64::INFINITY, f64::INFINITY]);
+    assert_eq!(big.tanh_v().data, vec![1.0, -1.0]);
+
+    assert!(vector::from_elems(&[0.5f32]).acosh()[0].is_nan());
+    let a = vector::from_elems(&[1.0, -1.0, 1.5, 0.0]).atanh();
+    assert_eq!(&a.data[..2], &[f64::INFINITY, f64::NEG_INFINITY]);
+    assert!(a[2].is_nan());
+    assert_eq!(a[3], 0.0);
+}
+
+#[test]
+fn test_vector_normalize_l1() {
+    let v = vector::from_elems(&[1.0, 3.0, 4.0]);
+    assert_eq!(v.l1_norm(), 8.0);
+    assert_eq!(v.normalize_l1().data, vec![0.125, 0.375, 0.5]);
+    assert_eq!(vector::from_elems(&[-1.0, 3.0]).normalize_l1().data, vec![-0.25, 0.75]);
+    let zero = Vector::new(3, 0.0f32);
+    assert_eq!(zero.normalize_l1(), zero);
+}
+
+#[test]
+fn test_vector_cummax_cummin() {
+    let up = vector::from_elems(&[1.0, 2.0, 2.0, 5.0]);
+    assert_eq!(up.cummax(), up);
+    assert_eq!(up.cummin().data, vec![1.0; 4]);
+    let v = vector::from_elems(&[3.0, 1.0, 4.0, 1.0, 5.0, 0.0]);
+    assert_eq!(v.cummax().data, vec![3.0, 3.0, 4.0, 4.0, 5.0, 5.0]);
+    assert_eq!(v.cummin().data, vec![3.0, 1.0, 1.0, 1.0, 1.0, 0.0]);
+    assert!(Vector::new(0, 0.0f64).cummax().len() == 0);
+    assert!(Vector::new(0, 0.0f64).nancummin().len() == 0);
+}
+
+#[test]
+fn test_vector_cummax_nan() {
+    let nan = f64::NAN;
+    let v = vector::from_elems(&[2.0, nan, 3.0, 1.0]);
+    let m = v.cummax();
+    assert_eq!(m[0], 2.0);
+    assert!(m.data[1..].iter().all(|x| x.is_nan()));
+    assert_eq!(v.nancummax().data, vec![2.0, 2.0, 3.0, 3.0]);
+    assert_eq!(v.nancummin().data, vec![2.0, 2.0, 2.0, 1.0]);
+
+    let v = vector::from_elems(&[nan, 4.0, 1.0]);
+    assert!(v.cummin().data.iter().all(|x| x.is_nan()));
+    let m = v.nancummin();
+    assert!(m[0].is_nan());
+    assert_eq!(m.data[1..], [4.0, 1.0]);
+}
+
+#[test]
+fn test_vector_winsorize() {
+    let v = vector::from_elems(&[10.0, 11.0, 9.0, 10.0, 12.0, 10.0, 9.0, 11.0, 10.0, 100.0]);
+    let w = v.winsorize(0.0, 0.9).unwrap();
+    assert_eq!(w.data[..9], v.data[..9]);
+    assert!((w[9] - 20.8f64).abs() < 1e-12);
+    assert_eq!(v.winsorize(0.25, 0.75).unwrap().data,
+               vec![10.0, 11.0, 10.0, 10.0, 11.0, 10.0, 10.0, 11.0, 10.0, 11.0]);
+    assert_eq!(v.winsorize(0.5, 0.5).unwrap().data, vec![10.0; 10]);
+    assert!(v.winsorize(0.8, 0.2).is_err());
+    assert!(v.winsorize(-0.1, 0.5).is_err());
+    assert!(v.winsorize(0.5, 1.5).is_err());
+    let c = Vector::new(4, 3.0f64);
+    assert_eq!(c.winsorize(0.1, 0.9).unwrap(), c);
+}
+
+#[test]
+fn test_vector_outlier_mask() {
+    use numrs::vector::OutlierMethod;
+
+    let v = vector::from_elems(&[10.0, 11.0, 9.0, 10.0, 12.0, 10.0, 9.0, 11.0, 10.0, 100.0]);
+    let mut expected = vec![false; 10];
+    expected[9] = true;
+    assert_eq!(v.outlier_mask(2.0, OutlierMethod::StdDev), expected);
+    assert_eq!(v.outlier_mask(1.5, OutlierMethod::Iqr), expected);
+    assert_eq!(v.outlier_mask(0.5, OutlierMethod::Iqr).iter().filter(|&&b| b).count(), 4);
+
+    // The elements winsorizing changes are the ones the mask flags.
+    let w = v.winsorize(0.0, 0.9).unwrap();
+    let changed: Vec<bool> = v.data.iter().zip(w.data.iter()).map(|(a, b)| a != b).collect();
+    assert_eq!(changed, expected);
+
+    let c = Vector::new(5, 2.0f64);
+    assert_eq!(c.outlier_mask(0.0, OutlierMethod::StdDev), vec![false; 5]);
+    assert_eq!(c.outlier_mask(0.0, OutlierMethod::Iqr), vec![false; 5]);
+    assert!(Vector::new(0, 0.0f64).outlier_mask(1.0, OutlierMethod::Iqr).is_empty());
+}
+
+#[test]
+fn test_vector_lerp_slerp() {
+    use numrs::vector::VectorOps;
+
+    let a = vector::from_elems(&[1.0f64, 0.0]);
+    let b = vector::from_elems(&[0.0f64, 1.0]);
+    assert_eq!(a.lerp(&b, 0.25).unwrap().data, vec![0.75, 0.25]);
+
+    let mid = a.slerp(&b, 0.5).unwrap();
+    let h = 0.5f64.sqrt();
+    assert!((mid[0] - h).abs() < 1e-12 && (mid[1] - h).abs() < 1e-12);
+    let third = a.slerp(&b, 1.0 / 3.0).unwrap();
+    let angle = std::f64::consts::FRAC_PI_6;
+    assert!((third[0] - angle.cos()).abs() < 1e-12 && (third[1] - angle.sin()).abs() < 1e-12);
+    assert_eq!(a.slerp(&b, 0.0).unwrap(), a);
+    assert!((a.slerp(&b, 1.0).unwrap() - b.clone()).norm() < 1e-12);
+
+    // Parallel and zero vectors fall back to lerp.
+    let c = vector::from_elems(&[2.0f64, 0.0]);
+    assert_eq!(a.slerp(&c, 0.5).unwrap().data, vec![1.5, 0.0]);
+    let zero = Vector::new(2, 0.0f64);
+    assert_eq!(a.slerp(&zero, 0.5).unwrap().data, vec![0.5, 0.0]);
+
+    assert!(a.slerp(&vector::from_elems(&[1.0, 0.0, 0.0]), 0.5).is_err());
+    assert!(a.lerp(&vector::from_elems(&[1.0]), 0.5).is_err());
+}
+
+#[test]
+fn test_vector_reflect() {
+    let up = vector::from_elems(&[0.0, 1.0, 0.0]);
+    let ray = vector::from_elems(&[1.0, -1.0, 2.0]);
+    assert_eq!(ray.reflect(&up).unwrap().data, vec![1.0, 1.0, 2.0]);
+    assert_eq!(ray.reflect(&up).unwrap().reflect(&up).unwrap(), ray);
+
+    // A tilted unit normal, and the same reflection from a non-unit normal.
+    let h = 0.5f64.sqrt();
+    let n = vector::from_elems(&[h, h]);
+    let r = vector::from_elems(&[1.0f64, 0.0]).reflect(&n).unwrap();
+    assert!(r[0].abs() < 1e-12 && (r[1] + 1.0).abs() < 1e-12);
+    let v = vector::from_elems(&[1.0f64, 0.0]);
+    let m = vector::from_elems(&[3.0f64, 3.0]);
+    let via_projection = v.clone() - v.project_onto(&m).unwrap() * 2.0;
+    assert!((via_projection[0] - r[0]).abs() < 1e-12 && (via_projection[1] - r[1]).abs() < 1e-12);
+
+    assert_eq!(ray.reflect(&vector::from_elems(&[1.0, 0.0])).unwrap_err(),
+               "Vectors are not conformable for dot product.");
+}
+
+#[test]
+fn test_vector_refract() {
+    let up = vector::from_elems(&[0.0f64, 1.0]);
+    let h = 0.5f64.sqrt();
+    let incident = vector::from_elems(&[h, -h]);
+
+    // Equal indices leave the direction unchanged.
+    let same = incident.refract(&up, 1.0).unwrap().unwrap();
+    assert!((same[0] - h).abs() < 1e-12 && (same[1] + h).abs() < 1e-12);
+
+    // Entering glass bends towards the normal: sin(t) = sin(45 deg) / 1.5.
+    let into = incident.refract(&up, 1.0 / 1.5).unwrap().unwrap();
+    let sin_t = h / 1.5;
+    assert!((into[0] - sin_t).abs() < 1e-12);
+    assert!((into[1] + (1.0 - sin_t * sin_t).sqrt()).abs() < 1e-12);
+
+    // Leaving glass at 45 degrees is beyond the critical angle of about 41.8.
+    assert_eq!(incident.refract(&up, 1.5).unwrap(), None);
+    // Just below the critical angle the ray grazes the surface.
+    let critical = (1.0f64 / 1.5).asin() - 1e-6;
+    let steep = vector::from_elems(&[critical.sin(), -critical.cos()]);
+    let out = steep.refract(&up, 1.5).unwrap().unwrap();
+    assert!(out[0] > 0.999 && out[1] < 0.0);
+    // Head-on rays pass straight through.
+    let down = vector::from_elems(&[0.0f64, -1.0]);
+    assert_eq!(down.refract(&up, 1.5).unwrap(), Some(down.clone()));
+
+    assert!(incident.refract(&vector::from_elems(&[1.0]), 1.0).is_err());
+}
+
+#[test]
+fn test_vector_scalar_lhs_ops() {
+    // Nine elements so that any chunked loop would also have a tail.
+    let v = vector::from_vec((1..10).map(|i| i as f64).collect());
+    assert_eq!(2.0 * v.clone(), v.clone() * 2.0);
+    assert_eq!((2.0 * &v).data[8], 18.0);
+    assert_eq!((1.0 + &v).data, (2..11).map(|i| i as f64).collect::<Vec<_>>());
+
+    // Subtraction and division are not commutative: s - v[i] and s / v[i].
+    let diff = 10.0 - &v;
+    assert_eq!(diff.data, (1..10).rev().map(|i| i as f64).collect::<Vec<_>>());
+    assert_eq!(diff.data[8], 1.0);
+    assert_eq!((v.clone() - Vector::new(9, 10.0)).data[8], -1.0);
+    let quot = 36.0 / v.clone();
+    assert_eq!(quot.data[..4], [36.0, 18.0, 12.0, 9.0]);
+    assert_eq!(quot.data[8], 4.0);
+
+    let f = vector::from_vec((1..10).map(|i| i as f32).collect());
+    assert_eq!((3.0f32 * &f).data[8], 27.0);
+    assert_eq!((0.5f32 + f.clone()).data[8], 9.5);
+    assert_eq!((1.0f32 - &f).data[..3], [0.0, -1.0, -2.0]);
+    assert_eq!((1.0f32 / f).data[8], 1.0 / 9.0);
+    assert_eq!(1.0 / Vector::new(1, 0.0f64), Vector::new(1, f64::INFINITY));
+}
+
+#[test]
+fn test_vector_smoothstep() {
+    let v = vector::from_elems(&[-1.0, 0.0, 0.25, 0.5, 0.75, 1.0, 2.0]);
+    assert_eq!(v.smoothstep(0.0, 1.0).data, vec![0.0, 0.0, 0.15625, 0.5, 0.84375, 1.0, 1.0]);
+    // Wider edges rescale the input, reversed edges mirror the step.
+    assert_eq!(v.smoothstep(0.0, 2.0).data[3], 0.15625);
+    assert_eq!(v.smoothstep(1.0, 0.0).data, vec![1.0, 1.0, 0.84375, 0.5, 0.15625, 0.0, 0.0]);
+    // Equal edges give a hard step.
+    assert_eq!(v.smoothstep(0.5, 0.5).data, vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0]);
+    let nan = vector::from_elems(&[f64::NAN]);
+    assert!(nan.smoothstep(0.0, 1.0)[0].is_nan() && nan.smoothstep(0.0, 0.0)[0].is_nan());
+}
+
+#[test]
+fn test_vector_try_new() {
+    use std::convert::TryFrom;
+    use numrs::vector::{ValidationPolicy, VectorError};
+
+    let elems = [1.0, f64::NAN, 3.0, f64::INFINITY];
+    let permissive = Vector::try_new(&elems, ValidationPolicy::default()).unwrap();
+    assert_eq!(permissive.len(), 4);
+    assert!(permissive[1].is_nan() && permissive[3] == f64::INFINITY);
+    let plain = vector::from_elems(&[1.0, 2.0]);
+    assert_eq!(Vector::try_new(&[1.0, 2.0], ValidationPolicy::default()).unwrap(), plain);
+    assert_eq!(Vector::try_new(&[0.0f32; 3], ValidationPolicy::default()).unwrap(),
+               Vector::new(3, 0.0f32));
+
+    let finite = ValidationPolicy::finite();
+    assert_eq!(Vector::try_new(&elems, finite), Err(VectorError::NonFinite { index: 1 }));
+    assert_eq!(Vector::try_new(&elems[2..], finite), Err(VectorError::NonFinite { index: 1 }));
+    assert_eq!(Vector::try_new(&[1.0, 2.0], finite).unwrap(), plain);
+    assert_eq!(Vector::try_from(&elems[..]), Err(VectorError::NonFinite { index: 1 }));
+    assert_eq!(Vector::try_from(&[1.0, 2.0][..]).unwrap(), plain);
+    assert_eq!(VectorError::NonFinite { index: 1 }.to_string(),
+               "Vector element at index 1 is not finite.");
+
+    let non_empty = ValidationPolicy { non_empty: true, ..ValidationPolicy::default() };
+    assert_eq!(Vector::<f64>::try_new(&[], non_empty), Err(VectorError::EmptyInput));
+    assert_eq!(Vector::try_new(&[f64::NAN], non_empty).unwrap().len(), 1);
+    assert_eq!(Vector::<f64>::try_new(&[], finite).unwrap().len(), 0);
+
+    let triples = ValidationPolicy { multiple_of: Some(3), ..ValidationPolicy::finite() };
+    assert_eq!(Vector::try_new(&[1.0; 6], triples).unwrap().len(), 6);
+    assert_eq!(Vector::try_new(&elems, triples),
+               Err(VectorError::LengthNotMultiple { len: 4, multiple: 3 }));
+    assert_eq!(Vector::<f64>::try_new(&[], triples).unwrap().len(), 0);
+}
+
+#[test]
+fn test_barycentric() {
+    let a = vector::from_elems(&[0.0, 0.0]);
+    let b = vector::from_elems(&[4.0, 0.0]);
+    let c = vector::from_elems(&[0.0, 2.0]);
+    assert_eq!(vector::barycentric(&a, &a, &b, &c).unwrap(), (1.0, 0.0, 0.0));
+    assert_eq!(vector::barycentric(&c, &a, &b, &c).unwrap(), (0.0, 0.0, 1.0));
+    let centroid = vector::from_elems(&[4.0 / 3.0, 2.0 / 3.0]);
+    let (u, v, w) = vector::barycentric(&centroid, &a, &b, &c).unwrap();
+    let third = 1.0f64 / 3.0;
+    assert!((u - third).abs() < 1e-12 && (v - third).abs() < 1e-12 && (w - third).abs() < 1e-12);
+    // Outside the triangle a weight is negative.
+    let (u, v, w) = vector::barycentric(&vector::from_elems(&[3.0, 2.0]), &a, &b, &c).unwrap();
+    assert_eq!((u, v, w), (-0.75, 0.75, 1.0));
+
+    // In 3D the point is projected onto the plane of the triangle.
+    let a3 = vector::from_elems(&[0.0, 0.0, 1.0]);
+    let b3 = vector::from_elems(&[4.0, 0.0, 1.0]);
+    let c3 = vector::from_elems(&[0.0, 2.0, 1.0]);
+    let above = vector::from_elems(&[2.0, 1.0, 7.0]);
+    assert_eq!(vector::barycentric(&above, &a3, &b3, &c3).unwrap(), (0.0, 0.5, 0.5));
+
+    let collinear = vector::from_elems(&[8.0, 0.0]);
+    assert_eq!(vector::barycentric(&c, &a, &b, &collinear).unwrap_err(), "Triangle is degenerate.");
+    assert!(vector::barycentric(&a, &a, &a, &c).is_err());
+    assert!(vector::barycentric(&above, &a, &b, &c).is_err());
+}
+
+#[test]
+fn test_vector_checked_div_elem() {
+    use numrs::vector::VectorError;
+
+    // Nine elements so that a chunked scan would also have tail lanes.
+    let x = vector::from_vec((1..10).map(|i| i as f64).collect());
+    let mut y = Vector::new(9, 2.0);
+    assert_eq!(x.checked_div_elem(&y).unwrap(), x.clone() * 0.5);
+    assert_eq!(x.div_or(&y, -1.0).unwrap(), x.clone() * 0.5);
+
+    y[8] = 0.0;
+    assert_eq!(x.checked_div_elem(&y), Err(VectorError::DivisionByZero { index: 8 }));
+    y[0] = -0.0;
+    assert_eq!(x.checked_div_elem(&y), Err(VectorError::DivisionByZero { index: 0 }));
+    let d = x.div_or(&y, -1.0).unwrap();
+    assert_eq!(d[0], -1.0);
+    assert_eq!(d[4], 2.5);
+    assert_eq!(d[8], -1.0);
+    assert_eq!(VectorError::DivisionByZero { index: 3 }.to_string(), "Division by zero at index 3.");
+
+    let ints = vector::from_elems(&[6, 7]);
+    assert_eq!(ints.checked_div_elem(&vector::from_elems(&[3, 0])),
+               Err(VectorError::DivisionByZero { index: 1 }));
+    assert_eq!(ints.div_or(&vector::from_elems(&[3, 0]), 0).unwrap().data, vec![2, 0]);
+
+    let short = vector::from_elems(&[0.0]);
+    assert_eq!(x.checked_div_elem(&short), Err(VectorError::DimensionMismatch { left: 9, right: 1 }));
+    assert_eq!(x.div_or(&short, 0.0), Err(VectorError::DimensionMismatch { left: 9, right: 1 }));
+}
+
+#[test]
+fn test_gram_determinant() {
+    let a = vector::from_elems(&[1.0f64, 2.0, 0.0]);
+    let b = vector::from_elems(&[0.0, 1.0, 3.0]);
+    let g = vector::gram_matrix(&[a.clone(), b.clone()]).unwrap();
+    assert_eq!(g.get_vec(), vec![5.0, 2.0, 2.0, 10.0]);
+
+    // An axis-aligned box with sides 2, 3 and 4 has volume 24.
+    let sides = [vector::from_elems(&[2.0f64, 0.0, 0.0]), vector::from_elems(&[0.0, 3.0, 0.0]),
+                 vector::from_elems(&[0.0, 0.0, 4.0])];
+    assert!((vector::gram_determinant(&sides).unwrap().sqrt() - 24.0f64).abs() < 1e-12);
+    // A sheared parallelogram keeps the area of its base times height.
+    let area = vector::gram_determinant(&[vector::from_elems(&[3.0f64, 0.0]),
+                                          vector::from_elems(&[5.0, 2.0])]).unwrap();
+    assert!((area.sqrt() - 6.0f64).abs() < 1e-12);
+
+    let dependent = [a.clone(), b.clone(), a.clone() * 2.0 - b.clone()];
+    assert!(vector::gram_determinant(&dependent).unwrap().abs() < 1e-10);
+    assert_eq!(vector::gram_determinant::<f64>(&[]).unwrap(), 1.0);
+    assert!(vector::gram_determinant(&[a, vector::from_elems(&[1.0])]).is_err());
+}
+
+#[test]
+fn test_vector_mixed_ownership_ops() {
+    let a = vector::from_elems(&[8.0, -6.0, 1.5, 9.0, 2.0]);
+    let b = vector::from_elems(&[2.0, 3.0, -0.5, 4.0, 8.0]);
+
+    let sums = [a.clone() + b.clone(), a.clone() + &b, &a + b.clone(), &a + &b];
+    let diffs = [a.clone() - b.clone(), a.clone() - &b, &a - b.clone(), &a - &b];
+    let prods = [a.clone() * b.clone(), a.clone() * &b, &a * b.clone(), &a * &b];
+    let quots = [a.clone() / b.clone(), a.clone() / &b, &a / b.clone(), &a / &b];
+    for i in 0..4 {
+        assert_eq!(sums[i].data, vec![10.0, -3.0, 1.0, 13.0, 10.0]);
+        assert_eq!(diffs[i].data, vec![6.0, -9.0, 2.0, 5.0, -6.0]);
+        assert_eq!(prods[i].data, vec![16.0, -18.0, -0.75, 36.0, 16.0]);
+        assert_eq!(quots[i].data, vec![4.0, -2.0, -3.0, 2.25, 0.25]);
+    }
+    let f = vector::from_elems(&[1.0f32, 6.0]);
+    let g = vector::from_elems(&[4.0f32, 3.0]);
+    assert_eq!((&f - g.clone()).data, vec![-3.0, 3.0]);
+    assert_eq!((f.clone() / &g).data, vec![0.25, 2.0]);
+
+    // The owned operand's buffer holds the result.
+    let lhs = a.clone();
+    let ptr = lhs.data.as_ptr();
+    assert_eq!((lhs - &b).data.as_ptr(), ptr);
+    let rhs = b.clone();
+    let ptr = rhs.data.as_ptr();
+    let q = &a / rhs;
+    assert_eq!(q.data.as_ptr(), ptr);
+    assert_eq!(q, quots[3]);
+    let lhs = a.clone();
+    let ptr = lhs.data.as_ptr();
+    assert_eq!((lhs * b.clone()).data.as_ptr(), ptr);
+}
+
+#[test]
+#[should_panic(expected = "Vectors are not conformable for division.")]
+fn test_vector_div_not_conformable() {
+    let _ = &vector::from_elems(&[1.0]) / vector::from_elems(&[1.0, 2.0]);
+}
+
+#[test]
+fn test_vector_project_onto_plane() {
+    let v = vector::from_elems(&[3.0, -2.0, 5.0]);
+    let z = vector::from_elems(&[0.0, 0.0, 2.0]);
+    assert_eq!(v.project_onto_plane(&z).unwrap().data, vec![3.0, -2.0, 0.0]);
+
+    let n = vector::from_elems(&[1.0f64, 1.0, 0.0]);
+    let p = v.project_onto_plane(&n).unwrap();
+    assert_eq!(p.data, vec![2.5, -2.5, 5.0]);
+    let along: f64 = p.data.iter().zip(n.data.iter()).map(|(a, b)| a * b).sum();
+    assert_eq!(along, 0.0);
+    // Projecting again changes nothing.
+    assert_eq!(p.project_onto_plane(&n).unwrap(), p);
+
+    assert_eq!(v.project_onto_plane(&Vector::new(3, 0.0)).unwrap(), v);
+    assert!(v.project_onto_plane(&vector::from_elems(&[1.0])).is_err());
+}
+
+#[test]
+fn test_vector_eq_and_bitwise_eq() {
+    let a = vector::from_vec((0..9).map(|i| i as f64).collect());
+    let mut b = a.clone();
+    assert!(a == b && a.bitwise_eq(&b));
+    b[8] = 8.5;
+    assert!(a != b && !a.bitwise_eq(&b));
+    assert!(a != vector::from_vec(a.data[..8].to_vec()));
+    assert!(!a.bitwise_eq(&vector::from_vec(a.data[..8].to_vec())));
+
+    // Only the final element differs, in sign of zero or as NaN.
+    let mut z = vector::from_vec((1..10).map(|i| i as f64).collect());
+    z[8] = 0.0;
+    let mut nz = z.clone();
+    nz[8] = -0.0;
+    assert!(z == nz && !z.bitwise_eq(&nz));
+    let mut n1 = z.clone();
+    n1[8] = f64::NAN;
+    let n2 = n1.clone();
+    assert!(n1 != n2 && n1.bitwise_eq(&n2));
+    assert!(!n1.bitwise_eq(&z));
+
+    let f = vector::from_elems(&[1.0f32, f32::NAN]);
+    assert!(f != f.clone() && f.bitwise_eq(&f.clone()));
+    assert!(!vector::from_elems(&[0.0f32]).bitwise_eq(&vector::from_elems(&[-0.0f32])));
+}
+
+#[test]
+fn test_cosine_schedule() {
+    let s = vector::cosine_schedule(5, 0.1f64, 0.001);
+    assert_eq!(s.len(), 5);
+    assert_eq!(s[0], 0.1);
+    assert_eq!(s[4], 0.001);
+    assert!((s[2] - 0.0505).abs() < 1e-15);
+    let quarter = 0.001 + 0.099 * (1.0 + 0.5f64.sqrt()) / 2.0;
+    assert!((s[1] - quarter).abs() < 1e-15);
+    assert!(s.data.windows(2).all(|w| w[1] < w[0]));
+
+    let f = vector::cosine_schedule(1000, 3e-4f32, 1e-6);
+    assert_eq!((f[0], f[999]), (3e-4, 1e-6));
+    assert_eq!(vector::cosine_schedule(1, 0.1, 0.0).data, vec![0.1]);
+    assert_eq!(vector::cosine_schedule(0, 0.1, 0.0).len(), 0);
+}
+
+#[test]
+fn test_vector_polyval() {
+    let x = vector::from_elems(&[-1.0, 0.0, 0.5, 2.0]);
+    // 3x^2 - 2x + 1
+    let p = vector::from_elems(&[3.0, -2.0, 1.0]);
+    assert_eq!(x.polyval(&p).data, vec![6.0, 1.0, 0.75, 9.0]);
+    assert_eq!(x.polyval(&vector::from_elems(&[0.0, 0.0, 5.0])).data, vec![5.0; 4]);
+    assert_eq!(x.polyval(&vector::from_elems(&[])).data, vec![0.0; 4]);
+    assert_eq!(vector::from_elems(&[2, 3]).polyval(&vector::from_elems(&[1, 0, 0, -1])).data,
+               vec![7, 26]);
+    assert_eq!(vector::from_elems::<f64>(&[]).polyval(&p).len(), 0);
+}
@@ -0,0 +1,105 @@
+extern crate numrs;
+use numrs::vector;
+use numrs::vector::{Vector, VectorOps};
+use numrs::view::{VectorView, VectorViewMut};
+
+#[test]
+fn test_view_zero_copy() {
+    let mut v = vector::from_elems(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+    let ptr = v.data[1..].as_ptr();
+    assert_eq!(v.view(1..4).as_slice().as_ptr(), ptr);
+    assert_eq!(v.view_mut(1..4).as_slice().as_ptr(), ptr);
+
+    let buf = [1.0, 2.0];
+    assert_eq!(VectorView::from_slice(&buf).as_slice().as_ptr(), buf.as_ptr());
+}
+
+#[test]
+fn test_view_op_parity() {
+    let a = vector::from_elems(&[1.0, -2.0, 3.0, 4.0, 0.5, 6.0]);
+    let b = vector::from_elems(&[2.0, 1.0, -1.0, 0.0, 3.0, 2.0]);
+    let (va, vb) = (a.view(0..6), b.view(0..6));
+
+    assert!((va + &vb) == a.clone() + b.clone());
+    assert!((va - &b) == a.clone() - b.clone());
+    assert!((a.clone() * &vb) == a.clone() * b.clone());
+    assert_eq!(va.sum(), a.sum());
+    assert_eq!(va.mean(), a.mean());
+    assert_eq!(va.dot(&vb), a.dot(&b));
+    assert_eq!(va.norm(), a.norm());
+    assert!(va.to_vector() == a);
+
+    assert_eq!(a.sum(), 12.5);
+    assert_eq!(a.dot(&b).unwrap(), 10.5);
+    assert!(a.dot(&b.view(0..2)).is_err());
+}
+
+#[test]
+fn test_view_mut_ops() {
+    let mut v = vector::from_elems(&[1.0, 2.0, 3.0, 4.0]);
+    let other = vector::from_elems(&[10.0, 20.0]);
+    {
+        let mut tail = v.view_mut(2..4);
+        tail += &other;
+        tail[0] *= 2.0;
+        assert_eq!(tail.sum(), 50.0);
+    }
+    assert_eq!(v.data, vec![1.0, 2.0, 26.0, 24.0]);
+
+    let mut buf = [1.0, 2.0, 3.0];
+    {
+        let mut view = VectorViewMut::from_slice(&mut buf);
+        view *= &vector::from_elems(&[2.0, 2.0, 2.0]);
+        view -= &vector::from_elems(&[1.0, 1.0, 1.0]);
+    }
+    assert_eq!(buf, [1.0, 3.0, 5.0]);
+}
+
+#[test]
+#[should_panic]
+fn test_view_mismatch() {
+    let v = vector::from_elems(&[1.0, 2.0, 3.0]);
+    let _ = v.view(0..2) + &v;
+}
+
+#[test]
+fn test_strided_view_channels() {
+    // Interleaved stereo: left = 0, 1, 2, ..., right = 0, -10, -20, ...
+    let mut elems = Vec::new();
+    for i in 0..5 {
+        elems.push(i as f64);
+        elems.push(-10.0 * i as f64);
+    }
+    let mut v = vector::from_vec(elems);
+    {
+        let left = v.strided_view(0, 2).unwrap();
+        let right = v.strided_view(1, 2).unwrap();
+        assert_eq!(left.len(), 5);
+        assert_eq!(right[4], -40.0);
+        assert_eq!(left.to_vector().data, vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+
+        let copied = right.to_vector();
+        assert_eq!(right.sum(), copied.sum());
+        assert_eq!(right.mean(), copied.mean());
+        assert_eq!(right.min(), copied.min());
+        assert_eq!(right.max(), copied.max());
+        assert_eq!(left.dot(&right).unwrap(), left.to_vector().dot(&copied).unwrap());
+        let ones = vector::from_elems(&[1.0; 5]);
+        assert_eq!(left.dot(&ones.view(0..5).into()).unwrap(), 10.0);
+    }
+
+    let gain = vector::from_elems(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+    v.strided_view_mut(1, 2).unwrap().copy_from(&gain).unwrap();
+    assert_eq!(v.data, vec![0.0, 1.0, 1.0, 2.0, 2.0, 3.0, 3.0, 4.0, 4.0, 5.0]);
+    assert!(v.strided_view_mut(1, 2).unwrap().copy_from(&Vector::new(4, 1.0)).is_err());
+}
+
+#[test]
+fn test_strided_view_errors() {
+    let v = vector::from_elems(&[1.0, 2.0, 3.0]);
+    assert!(v.strided_view(0, 0).is_err());
+    assert!(v.strided_view(3, 1).is_err());
+    let s = v.strided_view(2, 5).unwrap();
+    assert_eq!(s.len(), 1);
+    assert_eq!(s[0], 3.0);
+}